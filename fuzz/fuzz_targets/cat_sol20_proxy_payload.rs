@@ -0,0 +1,15 @@
+#![no_main]
+
+use anchor_lang::prelude::*;
+use cat_sol20_proxy::CATSOLStructs;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // Decoding arbitrary VAA payloads must fail cleanly, and whatever decodes
+    // must re-encode to the bytes it was read from.
+    if let Ok(message) = CATSOLStructs::deserialize(&mut &data[..]) {
+        let mut encoded = Vec::new();
+        message.serialize(&mut encoded).unwrap();
+        assert_eq!(&encoded[..], &data[..encoded.len()]);
+    }
+});
@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
-use std::{io, ops::Deref};
-
-use crate::token_bridge::{message::TransferWithMeta, program::ID};
+use std::{
+    io::{self, Read, Write},
+    ops::Deref,
+};
+
+use crate::token_bridge::{
+    message::{AssetMeta, TransferWithMeta},
+    program::ID,
+};
 use crate::wormhole::{PostedVaa, CHAIN_ID_SOLANA};
 
 #[derive(Default, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -13,6 +19,10 @@ pub struct Config {
 impl Config {
     /// AKA `b"config"`
     pub const SEED_PREFIX: &'static [u8; 6] = b"config";
+
+    pub fn address() -> Pubkey {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX], &ID).0
+    }
 }
 
 impl AccountDeserialize for Config {
@@ -36,6 +46,16 @@ pub struct WrappedMint(anchor_spl::token::Mint);
 impl WrappedMint {
     /// AKA `b"wrapped"`
     pub const SEED_PREFIX: &'static [u8; 7] = b"wrapped";
+
+    /// Address of the wrapped mint of the token at `token_address` on
+    /// `token_chain`.
+    pub fn address(token_chain: u16, token_address: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(
+            &[Self::SEED_PREFIX, &token_chain.to_be_bytes(), token_address],
+            &ID,
+        )
+        .0
+    }
 }
 
 impl AccountDeserialize for WrappedMint {
@@ -73,6 +93,30 @@ pub struct WrappedMeta {
 impl WrappedMeta {
     /// AKA `b"meta"`
     pub const SEED_PREFIX: &'static [u8; 4] = b"meta";
+
+    /// Address of the metadata of wrapped `mint`.
+    pub fn address(mint: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, mint.as_ref()], &ID).0
+    }
+
+    /// The wrapped mint this metadata describes. A mint is a Token Bridge
+    /// wrapped asset of this token only if it is this address.
+    pub fn wrapped_mint(&self) -> Pubkey {
+        WrappedMint::address(self.chain, &self.token_address)
+    }
+
+    /// Whether `mint` is the wrapped asset of the token at `token_address` on
+    /// `token_chain`.
+    pub fn is_wrapped_asset(
+        &self,
+        mint: &Pubkey,
+        token_chain: u16,
+        token_address: &[u8; 32],
+    ) -> bool {
+        self.chain == token_chain
+            && self.token_address == *token_address
+            && self.wrapped_mint() == *mint
+    }
 }
 
 impl AccountDeserialize for WrappedMeta {
@@ -96,6 +140,14 @@ pub struct EndpointRegistration {
     pub emitter_address: [u8; 32],
 }
 
+impl EndpointRegistration {
+    /// Address of the registration of the Token Bridge at `emitter_address`
+    /// on `emitter_chain`.
+    pub fn address(emitter_chain: u16, emitter_address: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(&[&emitter_chain.to_be_bytes(), emitter_address], &ID).0
+    }
+}
+
 impl AccountDeserialize for EndpointRegistration {
     fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
         Self::deserialize(buf).map_err(Into::into)
@@ -110,7 +162,7 @@ impl Owner for EndpointRegistration {
     }
 }
 
-#[derive(Default, AnchorSerialize, Clone, PartialEq, Eq)]
+#[derive(Default, Clone, PartialEq, Eq)]
 /// Token Bridge Transfer With Payload data. This data is found as the payload
 /// of a posted Wormhole message.
 pub struct TransferWithPayload {
@@ -119,6 +171,17 @@ pub struct TransferWithPayload {
 }
 
 impl TransferWithPayload {
+    pub fn new(meta: &TransferWithMeta, payload: Vec<u8>) -> Self {
+        Self {
+            meta: *meta,
+            payload,
+        }
+    }
+
+    pub fn meta(&self) -> &TransferWithMeta {
+        &self.meta
+    }
+
     pub fn amount(&self) -> u64 {
         self.meta.amount
     }
@@ -164,12 +227,25 @@ impl TransferWithPayload {
     }
 }
 
+impl AnchorSerialize for TransferWithPayload {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        // The payload is the rest of the message, without a length prefix.
+        self.meta.serialize(writer)?;
+        writer.write_all(&self.payload)
+    }
+}
+
 impl AnchorDeserialize for TransferWithPayload {
     fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
-        Ok(TransferWithPayload {
-            meta: TransferWithMeta::deserialize(&mut &buf[..133])?,
-            payload: buf[133..].to_vec(),
-        })
+        let meta = TransferWithMeta::deserialize(buf)?;
+        let payload = std::mem::take(buf).to_vec();
+        Ok(TransferWithPayload { meta, payload })
+    }
+
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Self::deserialize(&mut &buf[..])
     }
 }
 
@@ -189,6 +265,10 @@ impl<P: AnchorDeserialize + AnchorSerialize + Copy> TransferWith<P> {
         }
     }
 
+    pub fn meta(&self) -> &TransferWithMeta {
+        &self.meta
+    }
+
     pub fn amount(&self) -> u64 {
         self.meta.amount
     }
@@ -237,10 +317,16 @@ impl<P: AnchorDeserialize + AnchorSerialize + Copy> TransferWith<P> {
 impl<P: AnchorSerialize + AnchorDeserialize> AnchorDeserialize for TransferWith<P> {
     fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
         Ok(TransferWith {
-            meta: TransferWithMeta::deserialize(&mut &buf[..133])?,
-            payload: P::deserialize(&mut &buf[133..])?,
+            meta: TransferWithMeta::deserialize(buf)?,
+            payload: P::deserialize(buf)?,
         })
     }
+
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf)?;
+        Self::deserialize(&mut &buf[..])
+    }
 }
 
 /// Posted VAA (verified Wormhole message) of a Token Bridge transfer with
@@ -250,3 +336,6 @@ pub type PostedTransferWithPayload = PostedVaa<TransferWithPayload>;
 /// Posted VAA (verified Wormhole message) of a Token Bridge transfer with
 /// generic payload type `P`.
 pub type PostedTransferWith<P> = PostedVaa<TransferWith<P>>;
+
+/// Posted VAA (verified Wormhole message) of a Token Bridge attestation.
+pub type PostedAssetMeta = PostedVaa<AssetMeta>;
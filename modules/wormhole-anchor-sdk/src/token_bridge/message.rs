@@ -1,11 +1,17 @@
 use anchor_lang::prelude::*;
-use std::io;
+use std::io::{self, Read, Write};
 
 pub const PAYLOAD_ID_TRANSFER: u8 = 1;
 pub const PAYLOAD_ID_ASSET_META: u8 = 2;
 pub const PAYLOAD_ID_TRANSFER_WITH_PAYLOAD: u8 = 3;
 
-#[derive(Default, AnchorSerialize, Clone, Copy, PartialEq, Eq)]
+/// Size of an encoded [`TransferWithMeta`], payload ID included.
+pub const TRANSFER_WITH_META_SIZE: usize = 133;
+
+/// Size of an encoded [`AssetMeta`], payload ID included.
+pub const ASSET_META_SIZE: usize = 100;
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
 pub struct TransferWithMeta {
     /// Amount being transferred (big-endian uint256 -> u64)
     pub amount: u64,
@@ -21,8 +27,29 @@ pub struct TransferWithMeta {
     pub from_address: [u8; 32],
 }
 
+impl AnchorSerialize for TransferWithMeta {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&[PAYLOAD_ID_TRANSFER_WITH_PAYLOAD])?;
+        // Amounts are encoded as big-endian uint256.
+        writer.write_all(&[0u8; 24])?;
+        writer.write_all(&self.amount.to_be_bytes())?;
+        writer.write_all(&self.token_address)?;
+        writer.write_all(&self.token_chain.to_be_bytes())?;
+        writer.write_all(&self.to_address)?;
+        writer.write_all(&self.to_chain.to_be_bytes())?;
+        writer.write_all(&self.from_address)
+    }
+}
+
 impl AnchorDeserialize for TransferWithMeta {
     fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+        if buf.len() < TRANSFER_WITH_META_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Token Bridge Transfer With Payload is too short",
+            ));
+        }
+
         // Verify Payload ID is a token transfer with payload.
         if buf[0] != PAYLOAD_ID_TRANSFER_WITH_PAYLOAD {
             return Err(io::Error::new(
@@ -33,6 +60,12 @@ impl AnchorDeserialize for TransferWithMeta {
 
         // Encoded amount should be the last 8 bytes of bytes 1 through 33,
         // otherwise we will have serious issues in the Token Bridge program.
+        if buf[1..25].iter().any(|byte| *byte != 0) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Token Bridge amount does not fit in a u64",
+            ));
+        }
         let amount = {
             let mut out = [0u8; 8];
             out.copy_from_slice(&buf[25..33]);
@@ -52,6 +85,7 @@ impl AnchorDeserialize for TransferWithMeta {
         let mut from_address = [0u8; 32];
         from_address.copy_from_slice(&buf[101..133]);
 
+        *buf = &buf[TRANSFER_WITH_META_SIZE..];
         Ok(TransferWithMeta {
             amount,
             token_address,
@@ -61,6 +95,105 @@ impl AnchorDeserialize for TransferWithMeta {
             from_address,
         })
     }
+
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut buf = [0u8; TRANSFER_WITH_META_SIZE];
+        reader.read_exact(&mut buf)?;
+        Self::deserialize(&mut &buf[..])
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+/// Token Bridge attestation of a token. `create_wrapped` creates the wrapped
+/// mint of the token from it.
+pub struct AssetMeta {
+    /// Address of the token. Left-zero-padded if shorter than 32 bytes
+    pub token_address: [u8; 32],
+    /// Chain ID of the token
+    pub token_chain: u16,
+    /// Decimals of the token on its chain
+    pub decimals: u8,
+    /// Symbol of the token, right-zero-padded
+    pub symbol: [u8; 32],
+    /// Name of the token, right-zero-padded
+    pub name: [u8; 32],
+}
+
+impl AssetMeta {
+    /// Symbol without its padding.
+    pub fn symbol(&self) -> String {
+        trimmed_string(&self.symbol)
+    }
+
+    /// Name without its padding.
+    pub fn name(&self) -> String {
+        trimmed_string(&self.name)
+    }
+}
+
+impl AnchorSerialize for AssetMeta {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&[PAYLOAD_ID_ASSET_META])?;
+        writer.write_all(&self.token_address)?;
+        writer.write_all(&self.token_chain.to_be_bytes())?;
+        writer.write_all(&[self.decimals])?;
+        writer.write_all(&self.symbol)?;
+        writer.write_all(&self.name)
+    }
+}
+
+impl AnchorDeserialize for AssetMeta {
+    fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
+        if buf.len() < ASSET_META_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Token Bridge Asset Meta is too short",
+            ));
+        }
+
+        if buf[0] != PAYLOAD_ID_ASSET_META {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid Token Bridge Asset Meta",
+            ));
+        }
+
+        let mut token_address = [0u8; 32];
+        token_address.copy_from_slice(&buf[1..33]);
+
+        let token_chain = to_u16_be(&buf[33..35]);
+
+        let decimals = buf[35];
+
+        let mut symbol = [0u8; 32];
+        symbol.copy_from_slice(&buf[36..68]);
+
+        let mut name = [0u8; 32];
+        name.copy_from_slice(&buf[68..100]);
+
+        *buf = &buf[ASSET_META_SIZE..];
+        Ok(AssetMeta {
+            token_address,
+            token_chain,
+            decimals,
+            symbol,
+            name,
+        })
+    }
+
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        let mut buf = [0u8; ASSET_META_SIZE];
+        reader.read_exact(&mut buf)?;
+        Self::deserialize(&mut &buf[..])
+    }
+}
+
+fn trimmed_string(bytes: &[u8; 32]) -> String {
+    let end = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
 }
 
 fn to_u16_be(buf: &[u8]) -> u16 {
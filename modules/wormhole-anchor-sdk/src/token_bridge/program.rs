@@ -17,3 +17,11 @@ impl Id for TokenBridge {
         ID
     }
 }
+
+/// Metaplex Token Metadata program. The Token Bridge reads the symbol and
+/// name of an attested mint from it, and writes those of wrapped mints.
+pub mod spl_metadata {
+    use anchor_lang::prelude::*;
+
+    declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
@@ -1,3 +1,10 @@
+use anchor_lang::prelude::*;
+
+use crate::token_bridge::{
+    constants::*,
+    program::{spl_metadata, ID},
+};
+
 pub const MAX_WRAPPED_ASSET_DECIMALS: u8 = 8;
 
 /// Normalize raw amount based on this native mint's decimals.
@@ -25,3 +32,62 @@ fn amount_adjustment(mint_decimals: u8) -> u64 {
         1
     }
 }
+
+/// Token Bridge custody account holding the locked tokens of native `mint`.
+pub fn custody_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[mint.as_ref()], &ID).0
+}
+
+/// Delegate a sender approves before the Token Bridge takes its tokens.
+pub fn authority_signer_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_AUTHORITY_SIGNER], &ID).0
+}
+
+/// Owner of the Token Bridge custody accounts.
+pub fn custody_signer_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_CUSTODY_SIGNER], &ID).0
+}
+
+/// Mint authority of the Token Bridge wrapped mints.
+pub fn mint_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_MINT_AUTHORITY], &ID).0
+}
+
+/// Wormhole emitter of the Token Bridge.
+pub fn emitter_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_EMITTER], &ID).0
+}
+
+/// Sender PDA `program_id` signs a transfer with payload with. The Token
+/// Bridge then records `program_id` as the transfer's `from_address`.
+pub fn sender_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_SENDER], program_id).0
+}
+
+/// Redeemer PDA `program_id` signs a completed transfer with payload with,
+/// when `program_id` is the transfer's `to_address`.
+pub fn redeemer_address(program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], program_id).0
+}
+
+/// Claim account marking a transfer as completed.
+pub fn claim_address(emitter_chain: u16, emitter_address: &[u8; 32], sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            emitter_address,
+            &emitter_chain.to_be_bytes(),
+            &sequence.to_be_bytes(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+/// Metaplex metadata account of `mint`.
+pub fn spl_metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", spl_metadata::ID.as_ref(), mint.as_ref()],
+        &spl_metadata::ID,
+    )
+    .0
+}
@@ -130,6 +130,89 @@ impl Owner for SignatureSetData {
     }
 }
 
+impl SignatureSetData {
+    /// Number of guardians whose signature was verified.
+    pub fn num_verified(&self) -> usize {
+        self.signatures.iter().filter(|signed| **signed).count()
+    }
+}
+
+#[derive(Default, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub struct GuardianSet {
+    /// Index of this guardian set
+    pub index: u32,
+
+    /// Ethereum addresses of the guardians
+    pub keys: Vec<[u8; 20]>,
+
+    /// Time this guardian set was created
+    pub creation_time: u32,
+
+    /// Time this guardian set expires. Zero while the set is current.
+    pub expiration_time: u32,
+}
+
+impl GuardianSet {
+    /// AKA `b"GuardianSet"`.
+    pub const SEED_PREFIX: &'static [u8; 11] = b"GuardianSet";
+
+    /// Address of the guardian set with `index`.
+    pub fn address(index: u32) -> Pubkey {
+        Pubkey::find_program_address(&[Self::SEED_PREFIX, &index.to_be_bytes()], &ID).0
+    }
+
+    /// Minimum number of signatures the core bridge accepts: more than two
+    /// thirds of the guardians.
+    pub fn quorum(&self) -> usize {
+        quorum(self.keys.len())
+    }
+
+    /// Whether VAAs signed by this set are still accepted at `now`. A
+    /// replaced set stays active until its expiration time.
+    pub fn is_active(&self, now: i64) -> bool {
+        self.expiration_time == 0 || i64::from(self.expiration_time) > now
+    }
+
+    /// Whether `signature_set` holds signatures of this set that reach
+    /// quorum.
+    pub fn has_quorum(&self, signature_set: &SignatureSetData) -> bool {
+        self.has_signers(signature_set, self.quorum())
+    }
+
+    /// Whether `signature_set` holds at least `min_signers` signatures of
+    /// this set, for policies stricter than quorum.
+    pub fn has_signers(&self, signature_set: &SignatureSetData, min_signers: usize) -> bool {
+        signature_set.guardian_set_index == self.index
+            && signature_set.signatures.len() == self.keys.len()
+            && signature_set.num_verified() >= min_signers
+    }
+
+    /// Index of the guardian with `key` in this set, e.g. of a signer of a
+    /// query response from [`super::query_signers`].
+    pub fn key_index(&self, key: &[u8; 20]) -> Option<usize> {
+        self.keys.iter().position(|guardian| guardian == key)
+    }
+}
+
+impl AccountDeserialize for GuardianSet {
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        Self::deserialize(buf).map_err(Into::into)
+    }
+}
+
+impl AccountSerialize for GuardianSet {}
+
+impl Owner for GuardianSet {
+    fn owner() -> Pubkey {
+        ID
+    }
+}
+
+/// Quorum of a guardian set of `num_guardians`: more than two thirds.
+pub fn quorum(num_guardians: usize) -> usize {
+    (num_guardians * 2) / 3 + 1
+}
+
 #[derive(Default, AnchorSerialize, Clone, PartialEq, Eq)]
 pub struct PostedVaaData {
     pub meta: PostedVaaMeta,
@@ -174,14 +257,29 @@ impl PostedVaaData {
     pub fn emitter_address(&self) -> &[u8; 32] {
         &self.meta.emitter_address
     }
+
+    /// Keccak256 of the posted VAA body. See [`super::VaaBody::body_hash`].
+    pub fn body_hash(&self) -> [u8; 32] {
+        super::body_hash(
+            self.meta.timestamp,
+            self.meta.batch_id,
+            self.meta.emitter_chain,
+            &self.meta.emitter_address,
+            self.meta.sequence,
+            self.meta.finality,
+            &self.payload,
+        )
+    }
 }
 
 impl AnchorDeserialize for PostedVaaData {
     fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
-        let buf = remove_vaa_discriminator(buf)?;
+        // We accept "vaa", "msg", or "msu" because it's convenient to read all of
+        // these as PostedVAAData
+        let (meta, payload) = split_posted(buf, &[b"vaa", b"msg", b"msu"])?;
         Ok(PostedVaaData {
-            meta: PostedVaaMeta::deserialize(&mut &buf[..88])?,
-            payload: Vec::deserialize(&mut &buf[88..])?,
+            meta,
+            payload: payload.to_vec(),
         })
     }
 
@@ -260,19 +358,22 @@ impl<D: AnchorDeserialize + AnchorSerialize> PostedVaa<D> {
     pub fn message(&self) -> &D {
         self.data()
     }
+
+    /// Decode the payload of a `PostedVAA` account's `data` as `D`. Fails if
+    /// the account is not a posted VAA, its payload size does not match the
+    /// account, or `D` leaves payload bytes unread.
+    pub fn try_payload(data: &[u8]) -> io::Result<D> {
+        let (_, payload) = split_posted(data, &[b"vaa"])?;
+        decode_payload(payload)
+    }
 }
 
 impl<D: AnchorDeserialize + AnchorSerialize> AnchorDeserialize for PostedVaa<D> {
     fn deserialize(buf: &mut &[u8]) -> io::Result<Self> {
-        let buf = remove_vaa_discriminator(buf)?;
-        let data_size = u32::deserialize(&mut &buf[88..92])?;
-
+        let (meta, payload) = split_posted(buf, &[b"vaa"])?;
         Ok(PostedVaa {
-            meta: PostedVaaMeta::deserialize(&mut &buf[..88])?,
-            payload: (
-                data_size,
-                D::deserialize(&mut &buf[92..(92 + data_size as usize)])?,
-            ),
+            meta,
+            payload: (payload.len() as u32, decode_payload(payload)?),
         })
     }
 
@@ -297,23 +398,66 @@ impl<D: AnchorDeserialize + AnchorSerialize> Owner for PostedVaa<D> {
     }
 }
 
-fn remove_vaa_discriminator(buf: &'_ [u8]) -> io::Result<&[u8]> {
-    if buf.len() < 3 {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "Invalid Wormhole Message",
+/// Keccak256 of the body of a `PostedVAA` account, recomputed from its meta and
+/// raw payload the way guardians sign it. See [`super::VaaBody::body_hash`].
+pub fn posted_vaa_hash(posted: &AccountInfo) -> Result<[u8; 32]> {
+    let data = posted.try_borrow_data()?;
+    let (meta, payload) = split_posted(&data, &[b"vaa"])?;
+    Ok(super::body_hash(
+        meta.timestamp,
+        meta.batch_id,
+        meta.emitter_chain,
+        &meta.emitter_address,
+        meta.sequence,
+        meta.finality,
+        payload,
+    ))
+}
+
+/// Size of [`PostedVaaMeta`] in a posted message account.
+const POSTED_META_SIZE: usize = 88;
+
+/// Size of a posted message account without its payload: discriminator, meta
+/// and payload length.
+pub const POSTED_MESSAGE_HEADER_SIZE: usize = 3 + POSTED_META_SIZE + 4;
+
+/// Meta and payload of a posted message account whose discriminator is one of
+/// `magics`. The payload size must match the rest of the account.
+fn split_posted<'a>(buf: &'a [u8], magics: &[&[u8; 3]]) -> io::Result<(PostedVaaMeta, &'a [u8])> {
+    let magic = buf
+        .get(..3)
+        .ok_or_else(|| invalid_posted("Wormhole message account is empty"))?;
+    if !magics.iter().any(|expected| &expected[..] == magic) {
+        return Err(invalid_posted(
+            "Wormhole message account has an unexpected discriminator",
+        ));
+    }
+    let buf = &buf[3..];
+
+    if buf.len() < POSTED_META_SIZE + 4 {
+        return Err(invalid_posted("Wormhole message account is too short"));
+    }
+    let meta = PostedVaaMeta::deserialize(&mut &buf[..POSTED_META_SIZE])?;
+    let payload_size = u32::deserialize(&mut &buf[POSTED_META_SIZE..POSTED_META_SIZE + 4])?;
+    let payload = &buf[POSTED_META_SIZE + 4..];
+    if payload.len() != payload_size as usize {
+        return Err(invalid_posted(
+            "Wormhole message payload size does not match the account",
         ));
     }
+    Ok((meta, payload))
+}
 
-    // We accept "vaa", "msg", or "msu" because it's convenient to read all of
-    // these as PostedVAAData
-    let expected: [&[u8]; 3] = [b"vaa", b"msg", b"msu"];
-    let magic: &[u8] = &buf[0..3];
-    if !expected.contains(&magic) {
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            "Invalid Wormhole Message",
+fn decode_payload<D: AnchorDeserialize>(mut payload: &[u8]) -> io::Result<D> {
+    let data = D::deserialize(&mut payload)?;
+    if !payload.is_empty() {
+        return Err(invalid_posted(
+            "Wormhole message payload has trailing bytes",
         ));
-    };
-    Ok(&buf[3..])
+    }
+    Ok(data)
+}
+
+fn invalid_posted(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
 }
@@ -0,0 +1,95 @@
+use anchor_lang::{prelude::*, system_program};
+
+use super::{BridgeData, POSTED_MESSAGE_HEADER_SIZE};
+
+/// Lamports the fee collector still needs before the next `post_message`.
+///
+/// The core bridge accepts a message when the collector holds at least
+/// `last_lamports + fee`, so lamports already sent to it count towards the fee.
+pub fn message_fee_due(bridge: &BridgeData, fee_collector_lamports: u64) -> u64 {
+    bridge
+        .last_lamports
+        .saturating_add(bridge.fee())
+        .saturating_sub(fee_collector_lamports)
+}
+
+/// Pay the fee of one message from `payer` to `fee_collector`, before
+/// [`super::post_message`]. Only what the collector lacks is transferred.
+/// Returns the lamports paid.
+pub fn pay_message_fee<'info>(
+    bridge: &BridgeData,
+    payer: &AccountInfo<'info>,
+    fee_collector: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    let due = message_fee_due(bridge, fee_collector.lamports());
+    if due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: fee_collector.clone(),
+                },
+            ),
+            due,
+        )?;
+    }
+    Ok(due)
+}
+
+/// Size of the account of a posted message with a `payload_size` byte payload.
+pub fn message_account_size(payload_size: usize) -> usize {
+    POSTED_MESSAGE_HEADER_SIZE + payload_size
+}
+
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+/// Lamports a transaction posting one message costs its payer, on top of the
+/// transaction fee. Built off-chain from the current bridge and fee collector
+/// accounts, so wallets can show the cost before signing.
+pub struct MessageCost {
+    /// Lamports transferred to the fee collector.
+    pub fee: u64,
+    /// Rent of a new message account. Zero when a message account is reused.
+    pub message_rent: u64,
+    /// Rent of the other accounts the transaction creates.
+    pub account_rent: u64,
+}
+
+impl MessageCost {
+    /// Cost of posting a `payload_size` byte payload to a new message account.
+    pub fn estimate(
+        bridge: &BridgeData,
+        fee_collector_lamports: u64,
+        rent: &Rent,
+        payload_size: usize,
+    ) -> Self {
+        Self {
+            fee: message_fee_due(bridge, fee_collector_lamports),
+            message_rent: rent.minimum_balance(message_account_size(payload_size)),
+            account_rent: 0,
+        }
+    }
+
+    /// Add the rent of a new `space` byte account.
+    pub fn with_new_account(mut self, rent: &Rent, space: usize) -> Self {
+        self.account_rent += rent.minimum_balance(space);
+        self
+    }
+
+    /// Add the rent of a new SPL token account, e.g. an associated token
+    /// account created in the same transaction.
+    pub fn with_new_token_account(self, rent: &Rent) -> Self {
+        self.with_new_account(rent, anchor_spl::token::TokenAccount::LEN)
+    }
+
+    /// Cost of writing the message to an existing account.
+    pub fn reusing_message_account(mut self) -> Self {
+        self.message_rent = 0;
+        self
+    }
+
+    pub fn total(&self) -> u64 {
+        self.fee + self.message_rent + self.account_rent
+    }
+}
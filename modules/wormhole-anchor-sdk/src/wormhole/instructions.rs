@@ -1,6 +1,9 @@
 use anchor_lang::{prelude::*, solana_program};
 
-use super::Finality;
+use super::{program::ID, BridgeData, Finality};
+
+/// Number of guardians a `VerifySignatures` instruction can address.
+pub const MAX_VERIFY_SIGNERS: usize = 19;
 
 #[derive(AnchorDeserialize, AnchorSerialize)]
 /// Wormhole instructions.
@@ -27,9 +30,13 @@ pub enum Instruction {
     UpgradeContract,    // placeholder (governance action)
     UpgradeGuardianSet, // placeholder (governance action)
     VerifySignatures {
-        signers: [i8; 19],
+        signers: [i8; MAX_VERIFY_SIGNERS],
+    },
+    PostMessageUnreliable {
+        batch_id: u32,
+        payload: Vec<u8>,
+        finality: Finality,
     },
-    PostMessageUnreliable, // placeholder (unused)
 }
 
 #[derive(Accounts)]
@@ -50,6 +57,40 @@ pub fn post_message<'info>(
     batch_id: u32,
     payload: Vec<u8>,
     finality: Finality,
+) -> Result<()> {
+    invoke_post_message(
+        ctx,
+        Instruction::PostMessage {
+            batch_id,
+            payload,
+            finality,
+        },
+    )
+}
+
+/// Same as [`post_message`], but the message account can be posted to again.
+/// If it already holds a message, the emitter must be the same and the payload
+/// the same size. The previous message is overwritten, so guardians must have
+/// observed it by then.
+pub fn post_message_unreliable<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PostMessage<'info>>,
+    batch_id: u32,
+    payload: Vec<u8>,
+    finality: Finality,
+) -> Result<()> {
+    invoke_post_message(
+        ctx,
+        Instruction::PostMessageUnreliable {
+            batch_id,
+            payload,
+            finality,
+        },
+    )
+}
+
+fn invoke_post_message<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PostMessage<'info>>,
+    instruction: Instruction,
 ) -> Result<()> {
     let ix = solana_program::instruction::Instruction {
         program_id: ctx.program.key(),
@@ -64,12 +105,111 @@ pub fn post_message<'info>(
             AccountMeta::new_readonly(ctx.accounts.rent.key(), false),
             AccountMeta::new_readonly(ctx.accounts.system_program.key(), false),
         ],
-        data: Instruction::PostMessage {
-            batch_id,
-            payload,
-            finality,
+        data: instruction.try_to_vec()?,
+    };
+
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+#[derive(Accounts)]
+pub struct VerifySignatures<'info> {
+    pub payer: AccountInfo<'info>,
+    pub guardian_set: AccountInfo<'info>,
+    pub signature_set: AccountInfo<'info>,
+    pub instructions: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Record the guardian signatures checked by the secp256k1 instruction right
+/// before this one in `signature_set`. `signers[guardian_index]` is the
+/// position of that guardian's signature in the secp256k1 instruction, or `-1`
+/// if it did not sign. A new signature set must sign the transaction.
+pub fn verify_signatures<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, VerifySignatures<'info>>,
+    signers: [i8; MAX_VERIFY_SIGNERS],
+) -> Result<()> {
+    let ix = solana_program::instruction::Instruction {
+        program_id: ctx.program.key(),
+        accounts: verify_signatures_metas(
+            ctx.accounts.payer.key(),
+            ctx.accounts.guardian_set.key(),
+            ctx.accounts.signature_set.key(),
+        ),
+        data: Instruction::VerifySignatures { signers }.try_to_vec()?,
+    };
+
+    solana_program::program::invoke_signed(
+        &ix,
+        &ToAccountInfos::to_account_infos(&ctx),
+        ctx.signer_seeds,
+    )
+    .map_err(Into::into)
+}
+
+#[derive(Accounts)]
+pub struct PostVaa<'info> {
+    pub guardian_set: AccountInfo<'info>,
+    pub config: AccountInfo<'info>,
+    pub signature_set: AccountInfo<'info>,
+    pub posted_vaa: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub clock: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Default, AnchorDeserialize, AnchorSerialize, Clone, PartialEq, Eq)]
+/// Arguments of `PostVAA`: the VAA body and the guardian set that signed it.
+pub struct PostVaaArgs {
+    pub version: u8,
+    pub guardian_set_index: u32,
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub consistency_level: u8,
+    pub payload: Vec<u8>,
+}
+
+impl From<PostVaaArgs> for Instruction {
+    fn from(args: PostVaaArgs) -> Self {
+        Instruction::PostVAA {
+            version: args.version,
+            guardian_set_index: args.guardian_set_index,
+            timestamp: args.timestamp,
+            nonce: args.nonce,
+            emitter_chain: args.emitter_chain,
+            emitter_address: args.emitter_address,
+            sequence: args.sequence,
+            consistency_level: args.consistency_level,
+            payload: args.payload,
         }
-        .try_to_vec()?,
+    }
+}
+
+/// Post a VAA whose signatures reached quorum in `signature_set`. Posting
+/// the same VAA again is a no-op.
+pub fn post_vaa<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, PostVaa<'info>>,
+    args: PostVaaArgs,
+) -> Result<()> {
+    let ix = solana_program::instruction::Instruction {
+        program_id: ctx.program.key(),
+        accounts: post_vaa_metas(
+            ctx.accounts.payer.key(),
+            ctx.accounts.guardian_set.key(),
+            ctx.accounts.config.key(),
+            ctx.accounts.signature_set.key(),
+            ctx.accounts.posted_vaa.key(),
+        ),
+        data: Instruction::from(args).try_to_vec()?,
     };
 
     solana_program::program::invoke_signed(
@@ -79,3 +219,73 @@ pub fn post_message<'info>(
     )
     .map_err(Into::into)
 }
+
+/// `VerifySignatures` instruction, to follow a secp256k1 instruction. See
+/// [`verify_signatures`].
+pub fn verify_signatures_ix(
+    payer: Pubkey,
+    guardian_set: Pubkey,
+    signature_set: Pubkey,
+    signers: [i8; MAX_VERIFY_SIGNERS],
+) -> solana_program::instruction::Instruction {
+    solana_program::instruction::Instruction {
+        program_id: ID,
+        accounts: verify_signatures_metas(payer, guardian_set, signature_set),
+        data: Instruction::VerifySignatures { signers }
+            .try_to_vec()
+            .expect("instruction should serialize"),
+    }
+}
+
+/// `PostVAA` instruction. See [`post_vaa`].
+pub fn post_vaa_ix(
+    payer: Pubkey,
+    guardian_set: Pubkey,
+    signature_set: Pubkey,
+    posted_vaa: Pubkey,
+    args: PostVaaArgs,
+) -> solana_program::instruction::Instruction {
+    let (config, _) = Pubkey::find_program_address(&[BridgeData::SEED_PREFIX], &ID);
+
+    solana_program::instruction::Instruction {
+        program_id: ID,
+        accounts: post_vaa_metas(payer, guardian_set, config, signature_set, posted_vaa),
+        data: Instruction::from(args)
+            .try_to_vec()
+            .expect("instruction should serialize"),
+    }
+}
+
+fn verify_signatures_metas(
+    payer: Pubkey,
+    guardian_set: Pubkey,
+    signature_set: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(guardian_set, false),
+        AccountMeta::new(signature_set, true),
+        AccountMeta::new_readonly(solana_program::sysvar::instructions::ID, false),
+        AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        AccountMeta::new_readonly(solana_program::system_program::ID, false),
+    ]
+}
+
+fn post_vaa_metas(
+    payer: Pubkey,
+    guardian_set: Pubkey,
+    config: Pubkey,
+    signature_set: Pubkey,
+    posted_vaa: Pubkey,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(guardian_set, false),
+        AccountMeta::new_readonly(config, false),
+        AccountMeta::new_readonly(signature_set, false),
+        AccountMeta::new(posted_vaa, false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new_readonly(solana_program::sysvar::clock::ID, false),
+        AccountMeta::new_readonly(solana_program::sysvar::rent::ID, false),
+        AccountMeta::new_readonly(solana_program::system_program::ID, false),
+    ]
+}
@@ -1,12 +1,20 @@
 pub use accounts::*;
 pub use constants::*;
+pub use fee::*;
 pub use instructions::*;
 pub use message::*;
+pub use query::*;
+pub use secp256k1::*;
 pub use types::*;
+pub use vaa::*;
 
 pub mod accounts;
 pub mod constants;
+pub mod fee;
 pub mod instructions;
 pub mod message;
 pub mod program;
+pub mod query;
+pub mod secp256k1;
 pub mod types;
+pub mod vaa;
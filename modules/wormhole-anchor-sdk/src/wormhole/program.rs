@@ -1,5 +1,21 @@
 use anchor_lang::prelude::*;
 
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "solana-devnet",
+    feature = "tilt-devnet"
+)))]
+compile_error!("Enable one of the `mainnet`, `solana-devnet` or `tilt-devnet` features.");
+
+#[cfg(any(
+    all(feature = "mainnet", feature = "solana-devnet"),
+    all(feature = "mainnet", feature = "tilt-devnet"),
+    all(feature = "solana-devnet", feature = "tilt-devnet"),
+))]
+compile_error!(
+    "Only one of the `mainnet`, `solana-devnet` and `tilt-devnet` features can be enabled."
+);
+
 #[cfg(feature = "mainnet")]
 declare_id!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
 
@@ -0,0 +1,409 @@
+//! Wormhole Queries: guardian-signed responses to reads of other chains, e.g.
+//! an `eth_call` of a contract on an EVM chain.
+//!
+//! Guardians sign `keccak256(QUERY_RESPONSE_PREFIX || keccak256(response))`.
+//! On Solana the signatures are checked by secp256k1 precompile instructions,
+//! see [`query_signers`]. A quorum of signatures does not fit in one
+//! transaction with the response, so programs collect the signers of a
+//! response hash over several transactions first.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{keccak, secp256k1_program, sysvar::instructions},
+};
+use std::io;
+
+use super::{secp256k1::SECP_OFFSETS_SIZE, GUARDIAN_KEY_SIZE};
+
+/// Prefix of the message guardians sign for a query response.
+pub const QUERY_RESPONSE_PREFIX: &[u8; 35] = b"query_response_0000000000000000000|";
+
+/// Size of [`query_response_message`].
+pub const QUERY_RESPONSE_MESSAGE_SIZE: usize = QUERY_RESPONSE_PREFIX.len() + 32;
+
+/// Query type of an `eth_call` at a given block.
+pub const QUERY_TYPE_ETH_CALL: u8 = 1;
+
+/// `request_chain_id` of a request made off-chain. Its request ID is the
+/// requester's 65-byte signature, otherwise the 32-byte ID of the request on
+/// its chain.
+pub const QUERY_REQUEST_CHAIN_OFF_CHAIN: u16 = 0;
+
+/// Keccak256 of a wire encoded query response.
+pub fn query_response_hash(response: &[u8]) -> [u8; 32] {
+    keccak::hash(response).to_bytes()
+}
+
+/// Message of the secp256k1 precompile instruction verifying guardian
+/// signatures of the response hashing to `response_hash`. The precompile
+/// hashes it once more, giving the digest guardians sign.
+pub fn query_response_message(response_hash: &[u8; 32]) -> [u8; QUERY_RESPONSE_MESSAGE_SIZE] {
+    let mut message = [0u8; QUERY_RESPONSE_MESSAGE_SIZE];
+    message[..QUERY_RESPONSE_PREFIX.len()].copy_from_slice(QUERY_RESPONSE_PREFIX);
+    message[QUERY_RESPONSE_PREFIX.len()..].copy_from_slice(response_hash);
+    message
+}
+
+/// Keys of the guardians whose signatures of the response hashing to
+/// `response_hash` were verified by the secp256k1 instruction right before the
+/// current one.
+///
+/// Every offset of that instruction must point into its own data and every
+/// signature must cover [`query_response_message`]. Membership of the keys in
+/// a guardian set is up to the caller, e.g. with
+/// [`GuardianSet::key_index`](super::GuardianSet::key_index).
+pub fn query_signers(
+    instructions_sysvar: &AccountInfo,
+    response_hash: &[u8; 32],
+) -> Result<Vec<[u8; GUARDIAN_KEY_SIZE]>> {
+    require_keys_eq!(
+        *instructions_sysvar.key,
+        instructions::ID,
+        ErrorCode::AccountSysvarMismatch
+    );
+    let current = instructions::load_current_index_checked(instructions_sysvar)?;
+    require!(current > 0, ErrorCode::InstructionMissing);
+    let secp_index = current - 1;
+    let secp_ix =
+        instructions::load_instruction_at_checked(secp_index.into(), instructions_sysvar)?;
+    require_keys_eq!(
+        secp_ix.program_id,
+        secp256k1_program::ID,
+        ErrorCode::InstructionMissing
+    );
+
+    let data = &secp_ix.data;
+    let invalid = || error!(ErrorCode::InstructionDidNotDeserialize);
+    let count = *data.first().ok_or_else(invalid)? as usize;
+    require!(
+        data.len() > count * SECP_OFFSETS_SIZE,
+        ErrorCode::InstructionDidNotDeserialize
+    );
+
+    let message = query_response_message(response_hash);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let mut keys = Vec::with_capacity(count);
+    for i in 0..count {
+        let at = 1 + i * SECP_OFFSETS_SIZE;
+        let key_offset = read_u16(at + 3);
+        let message_offset = read_u16(at + 6);
+        let message_size = read_u16(at + 8);
+        require!(
+            [data[at + 2], data[at + 5], data[at + 10]]
+                .iter()
+                .all(|index| u16::from(*index) == secp_index),
+            ErrorCode::InstructionDidNotDeserialize
+        );
+        require!(
+            message_size == message.len()
+                && data.get(message_offset..message_offset + message_size) == Some(&message[..]),
+            ErrorCode::InstructionDidNotDeserialize
+        );
+        let key = data
+            .get(key_offset..key_offset + GUARDIAN_KEY_SIZE)
+            .ok_or_else(invalid)?;
+        keys.push(key.try_into().unwrap());
+    }
+    Ok(keys)
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// A query request, as signed by its requester.
+pub struct QueryRequest {
+    pub version: u8,
+    pub nonce: u32,
+    pub queries: Vec<PerChainQuery>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// Query of a single chain. `query` is encoded according to `query_type`,
+/// e.g. as an [`EthCallQuery`].
+pub struct PerChainQuery {
+    pub chain_id: u16,
+    pub query_type: u8,
+    pub query: Vec<u8>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// A query response: the request and one response per queried chain, in the
+/// request's order.
+pub struct QueryResponse {
+    pub version: u8,
+    pub request_chain_id: u16,
+    pub request_id: Vec<u8>,
+    pub request: QueryRequest,
+    pub responses: Vec<PerChainQueryResponse>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// Response of a single chain. `response` is encoded according to
+/// `query_type`, e.g. as an [`EthCallQueryResponse`].
+pub struct PerChainQueryResponse {
+    pub chain_id: u16,
+    pub query_type: u8,
+    pub response: Vec<u8>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// `eth_call` query: calls made at the block `block_id` (a block number as
+/// hex string or a block hash).
+pub struct EthCallQuery {
+    pub block_id: Vec<u8>,
+    pub calls: Vec<EthCallData>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// A single call of an [`EthCallQuery`].
+pub struct EthCallData {
+    /// Called contract.
+    pub to: [u8; 20],
+    /// ABI encoded call data.
+    pub data: Vec<u8>,
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// `eth_call` response: the block the calls were made at and their ABI
+/// encoded results, in the query's order.
+pub struct EthCallQueryResponse {
+    pub block_number: u64,
+    pub block_hash: [u8; 32],
+    /// Block time in microseconds.
+    pub block_time: u64,
+    pub results: Vec<Vec<u8>>,
+}
+
+impl QueryRequest {
+    /// Parse a wire encoded request. Trailing bytes are rejected.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader(bytes);
+        let version = reader.u8()?;
+        if version != 1 {
+            return Err(invalid_data("unsupported query request version"));
+        }
+        let nonce = reader.u32()?;
+        let queries = (0..reader.u8()?)
+            .map(|_| {
+                Ok(PerChainQuery {
+                    chain_id: reader.u16()?,
+                    query_type: reader.u8()?,
+                    query: reader.bytes()?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        reader.finish()?;
+
+        Ok(Self {
+            version,
+            nonce,
+            queries,
+        })
+    }
+
+    /// Wire encoding of the request (all integers big-endian).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.version];
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.push(self.queries.len() as u8);
+        for query in &self.queries {
+            out.extend_from_slice(&query.chain_id.to_be_bytes());
+            out.push(query.query_type);
+            put_bytes(&mut out, &query.query);
+        }
+        out
+    }
+}
+
+impl QueryResponse {
+    /// Parse a wire encoded response. Responses must match the request's
+    /// queries and trailing bytes are rejected.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader(bytes);
+        let version = reader.u8()?;
+        if version != 1 {
+            return Err(invalid_data("unsupported query response version"));
+        }
+        let request_chain_id = reader.u16()?;
+        let request_id = if request_chain_id == QUERY_REQUEST_CHAIN_OFF_CHAIN {
+            reader.take_slice(65)?
+        } else {
+            reader.take_slice(32)?
+        }
+        .to_vec();
+        let request = QueryRequest::parse(&reader.bytes()?)?;
+        let responses = (0..reader.u8()?)
+            .map(|_| {
+                Ok(PerChainQueryResponse {
+                    chain_id: reader.u16()?,
+                    query_type: reader.u8()?,
+                    response: reader.bytes()?,
+                })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        reader.finish()?;
+
+        let matches_request = responses.len() == request.queries.len()
+            && responses
+                .iter()
+                .zip(&request.queries)
+                .all(|(response, query)| {
+                    response.chain_id == query.chain_id && response.query_type == query.query_type
+                });
+        if !matches_request {
+            return Err(invalid_data("query responses do not match the request"));
+        }
+
+        Ok(Self {
+            version,
+            request_chain_id,
+            request_id,
+            request,
+            responses,
+        })
+    }
+
+    /// Wire encoding of the response (all integers big-endian).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.version];
+        out.extend_from_slice(&self.request_chain_id.to_be_bytes());
+        out.extend_from_slice(&self.request_id);
+        put_bytes(&mut out, &self.request.to_bytes());
+        out.push(self.responses.len() as u8);
+        for response in &self.responses {
+            out.extend_from_slice(&response.chain_id.to_be_bytes());
+            out.push(response.query_type);
+            put_bytes(&mut out, &response.response);
+        }
+        out
+    }
+
+    /// Each query paired with its response.
+    pub fn pairs(&self) -> impl Iterator<Item = (&PerChainQuery, &PerChainQueryResponse)> {
+        self.request.queries.iter().zip(&self.responses)
+    }
+}
+
+impl EthCallQuery {
+    /// Parse a wire encoded `eth_call` query. Trailing bytes are rejected.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader(bytes);
+        let block_id = reader.bytes()?;
+        let calls = (0..reader.u8()?)
+            .map(|_| {
+                Ok(EthCallData {
+                    to: reader.take()?,
+                    data: reader.bytes()?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+        reader.finish()?;
+
+        Ok(Self { block_id, calls })
+    }
+
+    /// Wire encoding of the query (all integers big-endian).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        put_bytes(&mut out, &self.block_id);
+        out.push(self.calls.len() as u8);
+        for call in &self.calls {
+            out.extend_from_slice(&call.to);
+            put_bytes(&mut out, &call.data);
+        }
+        out
+    }
+}
+
+impl EthCallQueryResponse {
+    /// Parse a wire encoded `eth_call` response. Trailing bytes are rejected.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader(bytes);
+        let block_number = reader.u64()?;
+        let block_hash = reader.take()?;
+        let block_time = reader.u64()?;
+        let results = (0..reader.u8()?)
+            .map(|_| reader.bytes())
+            .collect::<io::Result<_>>()?;
+        reader.finish()?;
+
+        Ok(Self {
+            block_number,
+            block_hash,
+            block_time,
+            results,
+        })
+    }
+
+    /// Wire encoding of the response (all integers big-endian).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&self.block_number.to_be_bytes());
+        out.extend_from_slice(&self.block_hash);
+        out.extend_from_slice(&self.block_time.to_be_bytes());
+        out.push(self.results.len() as u8);
+        for result in &self.results {
+            put_bytes(&mut out, result);
+        }
+        out
+    }
+
+    /// Block time in seconds.
+    pub fn block_time_seconds(&self) -> i64 {
+        (self.block_time / 1_000_000) as i64
+    }
+}
+
+fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take_slice(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(invalid_data("query is too short"));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn take<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        Ok(self.take_slice(N)?.try_into().unwrap())
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(u8::from_be_bytes(self.take()?))
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        Ok(u16::from_be_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        Ok(u32::from_be_bytes(self.take()?))
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_be_bytes(self.take()?))
+    }
+
+    /// Bytes prefixed with their u32 length.
+    fn bytes(&mut self) -> io::Result<Vec<u8>> {
+        let len = self.u32()? as usize;
+        Ok(self.take_slice(len)?.to_vec())
+    }
+
+    fn finish(&self) -> io::Result<()> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_data("query has trailing bytes"))
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,70 @@
+//! Off-chain builders for the secp256k1 precompile instruction that
+//! `VerifySignatures` checks guardian signatures against.
+
+use anchor_lang::solana_program::{instruction::Instruction, secp256k1_program};
+
+use super::MAX_VERIFY_SIGNERS;
+
+/// Size of an Ethereum address as stored in a guardian set.
+pub const GUARDIAN_KEY_SIZE: usize = 20;
+
+/// Size of a recoverable secp256k1 signature (64 bytes + recovery ID).
+pub const GUARDIAN_SIGNATURE_SIZE: usize = 65;
+
+/// Size of a single `SecpSignatureOffsets` entry in the precompile's data.
+pub(crate) const SECP_OFFSETS_SIZE: usize = 11;
+
+/// Build a secp256k1 precompile instruction verifying `signatures` (pairs of
+/// guardian key and signature) over `message`: the VAA body hash, or the
+/// [`query_response_message`](super::query_response_message) of a query
+/// response.
+///
+/// The core bridge requires every offset to point into this very instruction,
+/// so `instruction_index` must be this instruction's position in the
+/// transaction. `message` is stored once and shared by all signatures.
+pub fn secp256k1_instruction(
+    signatures: &[([u8; GUARDIAN_KEY_SIZE], [u8; GUARDIAN_SIGNATURE_SIZE])],
+    message: &[u8],
+    instruction_index: u8,
+) -> Instruction {
+    let count = signatures.len();
+    let data_start = 1 + count * SECP_OFFSETS_SIZE;
+    let entry_size = GUARDIAN_KEY_SIZE + GUARDIAN_SIGNATURE_SIZE;
+    let message_offset = data_start + count * entry_size;
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.push(count as u8);
+    for i in 0..count {
+        let key_offset = data_start + i * entry_size;
+        let signature_offset = key_offset + GUARDIAN_KEY_SIZE;
+
+        data.extend_from_slice(&(signature_offset as u16).to_le_bytes());
+        data.push(instruction_index);
+        data.extend_from_slice(&(key_offset as u16).to_le_bytes());
+        data.push(instruction_index);
+        data.extend_from_slice(&(message_offset as u16).to_le_bytes());
+        data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+        data.push(instruction_index);
+    }
+    for (key, signature) in signatures {
+        data.extend_from_slice(key);
+        data.extend_from_slice(signature);
+    }
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: secp256k1_program::id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// `signers` argument of `VerifySignatures` for a secp256k1 instruction that
+/// holds the signatures of `guardian_indices`, in that order.
+pub fn verify_signers(guardian_indices: &[usize]) -> [i8; MAX_VERIFY_SIGNERS] {
+    let mut signers = [-1i8; MAX_VERIFY_SIGNERS];
+    for (position, &index) in guardian_indices.iter().enumerate() {
+        signers[index] = position as i8;
+    }
+    signers
+}
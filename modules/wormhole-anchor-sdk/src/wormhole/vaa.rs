@@ -0,0 +1,244 @@
+use anchor_lang::{prelude::*, solana_program::keccak};
+use std::io;
+
+use super::{
+    message::PostedVaaMeta, program::ID, PostVaaArgs, GUARDIAN_SIGNATURE_SIZE,
+    SEED_PREFIX_POSTED_VAA,
+};
+
+/// Size of a VAA body without its payload.
+pub const VAA_BODY_HEADER_SIZE: usize = 51;
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// The signed part of a VAA. Guardians sign the keccak256 digest of
+/// [`VaaBody::body_hash`].
+pub struct VaaBody {
+    pub timestamp: u32,
+    pub nonce: u32,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub consistency_level: u8,
+    pub payload: Vec<u8>,
+}
+
+impl VaaBody {
+    /// Build a body whose payload is the serialized `payload`.
+    pub fn new<P: AnchorSerialize>(
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        payload: &P,
+    ) -> Self {
+        let mut encoded = Vec::new();
+        payload
+            .serialize(&mut encoded)
+            .expect("payload should serialize");
+
+        Self {
+            emitter_chain,
+            emitter_address,
+            sequence,
+            payload: encoded,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_timestamp(mut self, timestamp: u32) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: u32) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn with_consistency_level(mut self, consistency_level: u8) -> Self {
+        self.consistency_level = consistency_level;
+        self
+    }
+
+    /// Body of a posted VAA, from its meta and raw payload.
+    pub fn from_posted(meta: &PostedVaaMeta, payload: &[u8]) -> Self {
+        Self {
+            timestamp: meta.timestamp,
+            nonce: meta.batch_id,
+            emitter_chain: meta.emitter_chain,
+            emitter_address: meta.emitter_address,
+            sequence: meta.sequence,
+            consistency_level: meta.finality,
+            payload: payload.to_vec(),
+        }
+    }
+
+    /// Parse a wire encoded body. The payload is the rest of `bytes`.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader(bytes);
+        Ok(Self {
+            timestamp: u32::from_be_bytes(reader.take()?),
+            nonce: u32::from_be_bytes(reader.take()?),
+            emitter_chain: u16::from_be_bytes(reader.take()?),
+            emitter_address: reader.take()?,
+            sequence: u64::from_be_bytes(reader.take()?),
+            consistency_level: u8::from_be_bytes(reader.take()?),
+            payload: reader.0.to_vec(),
+        })
+    }
+
+    /// Wire encoding of the body (all integers big-endian).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(VAA_BODY_HEADER_SIZE + self.payload.len());
+        out.extend_from_slice(&self.timestamp.to_be_bytes());
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.emitter_chain.to_be_bytes());
+        out.extend_from_slice(&self.emitter_address);
+        out.extend_from_slice(&self.sequence.to_be_bytes());
+        out.push(self.consistency_level);
+        out.extend_from_slice(&self.payload);
+        out
+    }
+
+    /// Keccak256 of the body. This is the hash the core bridge uses to derive
+    /// the `PostedVAA` account and the message the secp256k1 precompile checks.
+    pub fn body_hash(&self) -> [u8; 32] {
+        body_hash(
+            self.timestamp,
+            self.nonce,
+            self.emitter_chain,
+            &self.emitter_address,
+            self.sequence,
+            self.consistency_level,
+            &self.payload,
+        )
+    }
+
+    /// Address of the `PostedVAA` account the core bridge creates for this
+    /// body.
+    pub fn posted_vaa_address(&self) -> Pubkey {
+        posted_vaa_address(self)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// A guardian's signature of a VAA body.
+pub struct GuardianSignature {
+    /// Index of the guardian in its guardian set.
+    pub index: u8,
+    /// Recoverable secp256k1 signature (64 bytes + recovery ID).
+    pub signature: [u8; GUARDIAN_SIGNATURE_SIZE],
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+/// A signed VAA, as guardians publish it.
+pub struct Vaa {
+    pub version: u8,
+    pub guardian_set_index: u32,
+    pub signatures: Vec<GuardianSignature>,
+    pub body: VaaBody,
+}
+
+impl Vaa {
+    /// Parse a wire encoded VAA.
+    pub fn parse(bytes: &[u8]) -> io::Result<Self> {
+        let mut reader = Reader(bytes);
+        let version = u8::from_be_bytes(reader.take()?);
+        if version != 1 {
+            return Err(invalid_data("unsupported VAA version"));
+        }
+        let guardian_set_index = u32::from_be_bytes(reader.take()?);
+        let num_signatures = u8::from_be_bytes(reader.take()?);
+        let signatures = (0..num_signatures)
+            .map(|_| {
+                Ok(GuardianSignature {
+                    index: u8::from_be_bytes(reader.take()?),
+                    signature: reader.take()?,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self {
+            version,
+            guardian_set_index,
+            signatures,
+            body: VaaBody::parse(reader.0)?,
+        })
+    }
+
+    /// Wire encoding of the VAA.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![self.version];
+        out.extend_from_slice(&self.guardian_set_index.to_be_bytes());
+        out.push(self.signatures.len() as u8);
+        for signature in &self.signatures {
+            out.push(signature.index);
+            out.extend_from_slice(&signature.signature);
+        }
+        out.extend(self.body.to_bytes());
+        out
+    }
+
+    /// See [`VaaBody::body_hash`].
+    pub fn body_hash(&self) -> [u8; 32] {
+        self.body.body_hash()
+    }
+
+    /// Arguments of the `PostVAA` instruction posting this VAA.
+    pub fn post_vaa_args(&self) -> PostVaaArgs {
+        PostVaaArgs {
+            version: self.version,
+            guardian_set_index: self.guardian_set_index,
+            timestamp: self.body.timestamp,
+            nonce: self.body.nonce,
+            emitter_chain: self.body.emitter_chain,
+            emitter_address: self.body.emitter_address,
+            sequence: self.body.sequence,
+            consistency_level: self.body.consistency_level,
+            payload: self.body.payload.clone(),
+        }
+    }
+}
+
+/// Keccak256 of a VAA body given its fields, without copying the payload.
+pub fn body_hash(
+    timestamp: u32,
+    nonce: u32,
+    emitter_chain: u16,
+    emitter_address: &[u8; 32],
+    sequence: u64,
+    consistency_level: u8,
+    payload: &[u8],
+) -> [u8; 32] {
+    keccak::hashv(&[
+        &timestamp.to_be_bytes(),
+        &nonce.to_be_bytes(),
+        &emitter_chain.to_be_bytes(),
+        emitter_address,
+        &sequence.to_be_bytes(),
+        &[consistency_level],
+        payload,
+    ])
+    .to_bytes()
+}
+
+/// Address of the `PostedVAA` account the core bridge creates for `body`.
+pub fn posted_vaa_address(body: &VaaBody) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_POSTED_VAA, &body.body_hash()], &ID).0
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        if self.0.len() < N {
+            return Err(invalid_data("VAA is too short"));
+        }
+        let (bytes, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(bytes.try_into().unwrap())
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,800 @@
+//! Native stand-in for the legacy Wormhole core bridge.
+//!
+//! It implements the instructions a CAT deployment touches (`initialize`,
+//! `post_message`, `post_message_unreliable`, `verify_signatures`, `post_vaa` and the guardian set
+//! upgrade) with the same account layouts, PDA seeds and checks as the
+//! deployed program, so `solana-program-test` can run without a validator.
+//! When a `wormhole_core.so` fixture is available and SBF is preferred
+//! (`cargo test-sbf`), the real program is loaded instead.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        self, entrypoint::ProgramResult, instruction::Instruction, program::invoke_signed,
+        program_error::ProgramError, secp256k1_program, system_instruction, sysvar,
+    },
+};
+use std::io;
+use wormhole_anchor_sdk::wormhole::{
+    self, program::ID as CORE_BRIDGE_ID, BridgeConfig, BridgeData, FeeCollector, Finality,
+    PostedVaaData, PostedVaaMeta, SequenceTracker, SignatureSetData,
+};
+
+use crate::vaa::{
+    GOVERNANCE_ACTION_UPGRADE_GUARDIAN_SET, GOVERNANCE_CHAIN, GOVERNANCE_EMITTER,
+    GOVERNANCE_MODULE_CORE,
+};
+
+/// Maximum number of guardians the legacy `VerifySignatures` can address.
+pub const MAX_GUARDIANS: usize = wormhole::MAX_VERIFY_SIGNERS;
+
+/// Consistency level written for [`Finality::Confirmed`] messages.
+pub const CONSISTENCY_LEVEL_CONFIRMED: u8 = 1;
+
+/// Consistency level written for [`Finality::Finalized`] messages.
+pub const CONSISTENCY_LEVEL_FINALIZED: u8 = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Errors raised by the stand-in, surfaced as `ProgramError::Custom`.
+pub enum CoreBridgeError {
+    InvalidGuardianSet = 0x100,
+    GuardianSetExpired,
+    GuardianSetMismatch,
+    InvalidSecpInstruction,
+    InvalidGuardianKey,
+    InvalidHash,
+    NoQuorum,
+    InsufficientFees,
+    InvalidGovernanceEmitter,
+    InvalidGovernanceAction,
+    InvalidGuardianSetUpgrade,
+    AlreadyClaimed,
+    InvalidDerivedAccount,
+    MissingSigner,
+    EmitterChanged,
+    InvalidPayloadLength,
+}
+
+impl From<CoreBridgeError> for ProgramError {
+    fn from(err: CoreBridgeError) -> Self {
+        ProgramError::Custom(err as u32)
+    }
+}
+
+/// Guardian set account data.
+pub use wormhole::GuardianSet as GuardianSetData;
+
+#[derive(Default, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+/// Arguments of the core bridge `initialize` instruction.
+pub struct InitializeData {
+    pub guardian_set_expiration_time: u32,
+    pub fee: u64,
+    pub initial_guardians: Vec<[u8; 20]>,
+}
+
+/// Governance claim PDA, marking a governance VAA as consumed.
+pub fn claim_address(emitter_chain: u16, emitter_address: &[u8; 32], sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            emitter_address,
+            &emitter_chain.to_be_bytes(),
+            &sequence.to_be_bytes(),
+        ],
+        &CORE_BRIDGE_ID,
+    )
+    .0
+}
+
+pub fn bridge_address() -> Pubkey {
+    Pubkey::find_program_address(&[BridgeData::SEED_PREFIX], &CORE_BRIDGE_ID).0
+}
+
+pub fn fee_collector_address() -> Pubkey {
+    Pubkey::find_program_address(&[FeeCollector::SEED_PREFIX], &CORE_BRIDGE_ID).0
+}
+
+pub fn sequence_address(emitter: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SequenceTracker::SEED_PREFIX, emitter.as_ref()],
+        &CORE_BRIDGE_ID,
+    )
+    .0
+}
+
+/// Entrypoint registered with `ProgramTest::add_program`.
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let mut data = data;
+    match wormhole::Instruction::deserialize(&mut data)? {
+        wormhole::Instruction::Initialize => initialize(
+            program_id,
+            accounts,
+            InitializeData::deserialize(&mut data)?,
+        ),
+        wormhole::Instruction::PostMessage {
+            batch_id,
+            payload,
+            finality,
+        } => post_message(program_id, accounts, batch_id, payload, finality, false),
+        wormhole::Instruction::PostMessageUnreliable {
+            batch_id,
+            payload,
+            finality,
+        } => post_message(program_id, accounts, batch_id, payload, finality, true),
+        wormhole::Instruction::VerifySignatures { signers } => {
+            verify_signatures(program_id, accounts, signers)
+        }
+        wormhole::Instruction::PostVAA {
+            version,
+            guardian_set_index,
+            timestamp,
+            nonce,
+            emitter_chain,
+            emitter_address,
+            sequence,
+            consistency_level,
+            payload,
+        } => {
+            let meta = PostedVaaMeta {
+                version,
+                finality: consistency_level,
+                timestamp,
+                signature_set: Pubkey::default(),
+                posted_timestamp: 0,
+                batch_id: nonce,
+                sequence,
+                emitter_chain,
+                emitter_address,
+            };
+            post_vaa(program_id, accounts, guardian_set_index, meta, payload)
+        }
+        wormhole::Instruction::UpgradeGuardianSet => upgrade_guardian_set(program_id, accounts),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: InitializeData,
+) -> ProgramResult {
+    let [bridge, guardian_set, fee_collector, payer, _clock, _rent, system_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if data.initial_guardians.is_empty() || data.initial_guardians.len() > MAX_GUARDIANS {
+        return Err(CoreBridgeError::InvalidGuardianSet.into());
+    }
+    let now = Clock::get()?.unix_timestamp as u32;
+
+    let bridge_bump = check_pda(bridge, &[BridgeData::SEED_PREFIX], program_id)?;
+    let guardian_set_bump = check_pda(
+        guardian_set,
+        &[GuardianSetData::SEED_PREFIX, &0u32.to_be_bytes()],
+        program_id,
+    )?;
+    check_pda(fee_collector, &[FeeCollector::SEED_PREFIX], program_id)?;
+
+    let guardian_set_data = GuardianSetData {
+        index: 0,
+        keys: data.initial_guardians,
+        creation_time: now,
+        expiration_time: 0,
+    };
+    create_and_write(
+        payer,
+        guardian_set,
+        system_program,
+        program_id,
+        &guardian_set_data.try_to_vec()?,
+        &[
+            GuardianSetData::SEED_PREFIX,
+            &0u32.to_be_bytes(),
+            &[guardian_set_bump],
+        ],
+    )?;
+
+    // The fee collector is a system account. Fund it to rent exemption so
+    // `last_lamports` starts from a stable balance.
+    invoke_signed(
+        &system_instruction::transfer(
+            payer.key,
+            fee_collector.key,
+            Rent::get()?.minimum_balance(0),
+        ),
+        &[payer.clone(), fee_collector.clone(), system_program.clone()],
+        &[],
+    )?;
+
+    let bridge_data = BridgeData {
+        guardian_set_index: 0,
+        last_lamports: fee_collector.lamports(),
+        config: BridgeConfig {
+            guardian_set_expiration_time: data.guardian_set_expiration_time,
+            fee: data.fee,
+        },
+    };
+    create_and_write(
+        payer,
+        bridge,
+        system_program,
+        program_id,
+        &bridge_data.try_to_vec()?,
+        &[BridgeData::SEED_PREFIX, &[bridge_bump]],
+    )
+}
+
+fn post_message(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    batch_id: u32,
+    payload: Vec<u8>,
+    finality: Finality,
+    unreliable: bool,
+) -> ProgramResult {
+    let [bridge, message, emitter, sequence, payer, fee_collector, _clock, _rent, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    if !message.is_signer || !emitter.is_signer {
+        return Err(CoreBridgeError::MissingSigner.into());
+    }
+    check_pda(bridge, &[BridgeData::SEED_PREFIX], program_id)?;
+    check_pda(fee_collector, &[FeeCollector::SEED_PREFIX], program_id)?;
+    let sequence_bump = check_pda(
+        sequence,
+        &[SequenceTracker::SEED_PREFIX, emitter.key.as_ref()],
+        program_id,
+    )?;
+
+    // Fees must have been transferred to the collector before this call.
+    let mut bridge_data = BridgeData::deserialize(&mut &bridge.data.borrow()[..])?;
+    if fee_collector.lamports() < bridge_data.last_lamports + bridge_data.fee() {
+        return Err(CoreBridgeError::InsufficientFees.into());
+    }
+    bridge_data.last_lamports = fee_collector.lamports();
+    write(bridge, &bridge_data.try_to_vec()?)?;
+
+    let mut tracker = if sequence.data_is_empty() {
+        SequenceTracker::default()
+    } else {
+        SequenceTracker::deserialize(&mut &sequence.data.borrow()[..])?
+    };
+    let meta = PostedVaaMeta {
+        version: 0,
+        finality: match finality {
+            Finality::Confirmed => CONSISTENCY_LEVEL_CONFIRMED,
+            Finality::Finalized => CONSISTENCY_LEVEL_FINALIZED,
+        },
+        timestamp: 0,
+        signature_set: Pubkey::default(),
+        posted_timestamp: Clock::get()?.unix_timestamp as u32,
+        batch_id,
+        sequence: tracker.sequence,
+        emitter_chain: wormhole::CHAIN_ID_SOLANA,
+        emitter_address: emitter.key.to_bytes(),
+    };
+    tracker.sequence += 1;
+
+    if sequence.data_is_empty() {
+        create_and_write(
+            payer,
+            sequence,
+            system_program,
+            program_id,
+            &tracker.try_to_vec()?,
+            &[
+                SequenceTracker::SEED_PREFIX,
+                emitter.key.as_ref(),
+                &[sequence_bump],
+            ],
+        )?;
+    } else {
+        write(sequence, &tracker.try_to_vec()?)?;
+    }
+
+    if !unreliable {
+        return create_and_write(
+            payer,
+            message,
+            system_program,
+            program_id,
+            &encode_posted(b"msg", &meta, &payload)?,
+            &[],
+        );
+    }
+
+    // Unreliable messages may be posted again to the same account, by the same
+    // emitter and with the same payload size.
+    let data = encode_posted(b"msu", &meta, &payload)?;
+    if message.data_is_empty() {
+        return create_and_write(payer, message, system_program, program_id, &data, &[]);
+    }
+    if message.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let previous = PostedVaaData::deserialize(&mut &message.data.borrow()[..])?;
+    if previous.meta.emitter_address != meta.emitter_address {
+        return Err(CoreBridgeError::EmitterChanged.into());
+    }
+    if previous.payload.len() != payload.len() {
+        return Err(CoreBridgeError::InvalidPayloadLength.into());
+    }
+    write(message, &data)
+}
+
+fn verify_signatures(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    signers: [i8; MAX_GUARDIANS],
+) -> ProgramResult {
+    let [payer, guardian_set, signature_set, instructions, _rent, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let guardian_set_data = load_guardian_set(program_id, guardian_set)?;
+
+    // The secp256k1 precompile instruction must immediately precede this one,
+    // and every offset in it must point into its own data.
+    if !sysvar::instructions::check_id(instructions.key) {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let current = sysvar::instructions::load_current_index_checked(instructions)?;
+    if current == 0 {
+        return Err(CoreBridgeError::InvalidSecpInstruction.into());
+    }
+    let secp_index = current - 1;
+    let secp_ix =
+        sysvar::instructions::load_instruction_at_checked(secp_index as usize, instructions)?;
+    if secp_ix.program_id != secp256k1_program::id() {
+        return Err(CoreBridgeError::InvalidSecpInstruction.into());
+    }
+    let entries = parse_secp_instruction(&secp_ix.data, secp_index as u8)?;
+
+    let mut message = [0u8; 32];
+    message.copy_from_slice(&secp_ix.data[entries.message_offset..entries.message_offset + 32]);
+
+    let mut signature_set_data = if signature_set.data_is_empty() {
+        SignatureSetData {
+            signatures: vec![false; guardian_set_data.keys.len()],
+            hash: message,
+            guardian_set_index: guardian_set_data.index,
+        }
+    } else {
+        let existing = SignatureSetData::deserialize(&mut &signature_set.data.borrow()[..])?;
+        if existing.guardian_set_index != guardian_set_data.index {
+            return Err(CoreBridgeError::GuardianSetMismatch.into());
+        }
+        if existing.hash != message {
+            return Err(CoreBridgeError::InvalidHash.into());
+        }
+        existing
+    };
+
+    let mut used = 0;
+    for (guardian_index, position) in signers.iter().enumerate() {
+        if *position < 0 {
+            continue;
+        }
+        let key = entries
+            .keys
+            .get(*position as usize)
+            .ok_or(CoreBridgeError::InvalidSecpInstruction)?;
+        if guardian_set_data.keys.get(guardian_index) != Some(key) {
+            return Err(CoreBridgeError::InvalidGuardianKey.into());
+        }
+        signature_set_data.signatures[guardian_index] = true;
+        used += 1;
+    }
+    if used != entries.keys.len() {
+        return Err(CoreBridgeError::InvalidSecpInstruction.into());
+    }
+
+    if signature_set.data_is_empty() {
+        if !signature_set.is_signer {
+            return Err(CoreBridgeError::MissingSigner.into());
+        }
+        create_and_write(
+            payer,
+            signature_set,
+            system_program,
+            program_id,
+            &signature_set_data.try_to_vec()?,
+            &[],
+        )
+    } else {
+        write(signature_set, &signature_set_data.try_to_vec()?)
+    }
+}
+
+fn post_vaa(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    guardian_set_index: u32,
+    mut meta: PostedVaaMeta,
+    payload: Vec<u8>,
+) -> ProgramResult {
+    let [guardian_set, bridge, signature_set, message, payer, _clock, _rent, system_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let now = Clock::get()?.unix_timestamp;
+    check_pda(bridge, &[BridgeData::SEED_PREFIX], program_id)?;
+
+    let guardian_set_data = load_guardian_set(program_id, guardian_set)?;
+    if guardian_set_data.index != guardian_set_index {
+        return Err(CoreBridgeError::GuardianSetMismatch.into());
+    }
+    if !guardian_set_data.is_active(now) {
+        return Err(CoreBridgeError::GuardianSetExpired.into());
+    }
+
+    if signature_set.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let signature_set_data = SignatureSetData::deserialize(&mut &signature_set.data.borrow()[..])?;
+    if signature_set_data.guardian_set_index != guardian_set_data.index {
+        return Err(CoreBridgeError::GuardianSetMismatch.into());
+    }
+
+    let hash = body_hash(&meta, &payload);
+    if signature_set_data.hash != hash {
+        return Err(CoreBridgeError::InvalidHash.into());
+    }
+    if !guardian_set_data.has_quorum(&signature_set_data) {
+        return Err(CoreBridgeError::NoQuorum.into());
+    }
+
+    let bump = check_pda(
+        message,
+        &[wormhole::SEED_PREFIX_POSTED_VAA, &hash],
+        program_id,
+    )?;
+    // Posting the same VAA twice is a no-op.
+    if !message.data_is_empty() {
+        return Ok(());
+    }
+
+    meta.signature_set = *signature_set.key;
+    meta.posted_timestamp = now as u32;
+    create_and_write(
+        payer,
+        message,
+        system_program,
+        program_id,
+        &encode_posted(b"vaa", &meta, &payload)?,
+        &[wormhole::SEED_PREFIX_POSTED_VAA, &hash, &[bump]],
+    )
+}
+
+fn upgrade_guardian_set(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer, bridge, vaa, claim, guardian_set_old, guardian_set_new, system_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let now = Clock::get()?.unix_timestamp as u32;
+    check_pda(bridge, &[BridgeData::SEED_PREFIX], program_id)?;
+
+    if vaa.owner != program_id {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let posted = PostedVaaData::deserialize(&mut &vaa.data.borrow()[..])?;
+    if posted.emitter_chain() != GOVERNANCE_CHAIN || *posted.emitter_address() != GOVERNANCE_EMITTER
+    {
+        return Err(CoreBridgeError::InvalidGovernanceEmitter.into());
+    }
+
+    // Payload: module (32), action (1), chain (2), new index (4), count (1),
+    // keys (20 * count).
+    let payload = &posted.payload;
+    if payload.len() < 40
+        || payload[..32] != GOVERNANCE_MODULE_CORE
+        || payload[32] != GOVERNANCE_ACTION_UPGRADE_GUARDIAN_SET
+    {
+        return Err(CoreBridgeError::InvalidGovernanceAction.into());
+    }
+    let target_chain = u16::from_be_bytes([payload[33], payload[34]]);
+    if target_chain != 0 && target_chain != wormhole::CHAIN_ID_SOLANA {
+        return Err(CoreBridgeError::InvalidGovernanceAction.into());
+    }
+    let new_index = u32::from_be_bytes([payload[35], payload[36], payload[37], payload[38]]);
+    let count = payload[39] as usize;
+    if count == 0 || count > MAX_GUARDIANS || payload.len() != 40 + 20 * count {
+        return Err(CoreBridgeError::InvalidGovernanceAction.into());
+    }
+    let keys = payload[40..]
+        .chunks_exact(20)
+        .map(|chunk| {
+            let mut key = [0u8; 20];
+            key.copy_from_slice(chunk);
+            key
+        })
+        .collect();
+
+    let mut bridge_data = BridgeData::deserialize(&mut &bridge.data.borrow()[..])?;
+    let mut old = load_guardian_set(program_id, guardian_set_old)?;
+    if old.index != bridge_data.guardian_set_index || new_index != old.index + 1 {
+        return Err(CoreBridgeError::InvalidGuardianSetUpgrade.into());
+    }
+
+    // Governance VAAs can only be consumed once.
+    let claim_bump = check_pda(
+        claim,
+        &[
+            posted.emitter_address(),
+            &posted.emitter_chain().to_be_bytes(),
+            &posted.sequence().to_be_bytes(),
+        ],
+        program_id,
+    )?;
+    if !claim.data_is_empty() {
+        return Err(CoreBridgeError::AlreadyClaimed.into());
+    }
+    create_and_write(
+        payer,
+        claim,
+        system_program,
+        program_id,
+        &[1],
+        &[
+            posted.emitter_address(),
+            &posted.emitter_chain().to_be_bytes(),
+            &posted.sequence().to_be_bytes(),
+            &[claim_bump],
+        ],
+    )?;
+
+    // Old set stays valid for the configured grace period.
+    old.expiration_time = now + bridge_data.guardian_set_expiration_time();
+    write(guardian_set_old, &old.try_to_vec()?)?;
+
+    let new_bump = check_pda(
+        guardian_set_new,
+        &[GuardianSetData::SEED_PREFIX, &new_index.to_be_bytes()],
+        program_id,
+    )?;
+    let new = GuardianSetData {
+        index: new_index,
+        keys,
+        creation_time: now,
+        expiration_time: 0,
+    };
+    create_and_write(
+        payer,
+        guardian_set_new,
+        system_program,
+        program_id,
+        &new.try_to_vec()?,
+        &[
+            GuardianSetData::SEED_PREFIX,
+            &new_index.to_be_bytes(),
+            &[new_bump],
+        ],
+    )?;
+
+    bridge_data.guardian_set_index = new_index;
+    write(bridge, &bridge_data.try_to_vec()?)
+}
+
+/// Keccak256 of the VAA body described by `meta` and `payload`.
+pub fn body_hash(meta: &PostedVaaMeta, payload: &[u8]) -> [u8; 32] {
+    wormhole::body_hash(
+        meta.timestamp,
+        meta.batch_id,
+        meta.emitter_chain,
+        &meta.emitter_address,
+        meta.sequence,
+        meta.finality,
+        payload,
+    )
+}
+
+struct SecpEntries {
+    keys: Vec<[u8; 20]>,
+    message_offset: usize,
+}
+
+fn parse_secp_instruction(
+    data: &[u8],
+    secp_index: u8,
+) -> std::result::Result<SecpEntries, ProgramError> {
+    let invalid = || ProgramError::from(CoreBridgeError::InvalidSecpInstruction);
+    let count = *data.first().ok_or_else(invalid)? as usize;
+    if count == 0 || data.len() < 1 + count * 11 {
+        return Err(invalid());
+    }
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let mut keys = Vec::with_capacity(count);
+    let mut message = None;
+    for i in 0..count {
+        let at = 1 + i * 11;
+        let key_offset = read_u16(at + 3);
+        let message_offset = read_u16(at + 6);
+        let message_size = read_u16(at + 8);
+        if data[at + 2] != secp_index || data[at + 5] != secp_index || data[at + 10] != secp_index {
+            return Err(invalid());
+        }
+        // All signatures must cover the same 32-byte hash.
+        if message_size != 32 || *message.get_or_insert(message_offset) != message_offset {
+            return Err(invalid());
+        }
+        let key = data.get(key_offset..key_offset + 20).ok_or_else(invalid)?;
+        keys.push(key.try_into().unwrap());
+    }
+
+    let message_offset = message.ok_or_else(invalid)?;
+    if data.len() < message_offset + 32 {
+        return Err(invalid());
+    }
+    Ok(SecpEntries {
+        keys,
+        message_offset,
+    })
+}
+
+fn load_guardian_set(
+    program_id: &Pubkey,
+    guardian_set: &AccountInfo,
+) -> std::result::Result<GuardianSetData, ProgramError> {
+    if guardian_set.owner != program_id {
+        return Err(CoreBridgeError::InvalidGuardianSet.into());
+    }
+    let data = GuardianSetData::deserialize(&mut &guardian_set.data.borrow()[..])?;
+    check_pda(
+        guardian_set,
+        &[GuardianSetData::SEED_PREFIX, &data.index.to_be_bytes()],
+        program_id,
+    )?;
+    Ok(data)
+}
+
+fn encode_posted(magic: &[u8; 3], meta: &PostedVaaMeta, payload: &[u8]) -> io::Result<Vec<u8>> {
+    let mut data = magic.to_vec();
+    meta.serialize(&mut data)?;
+    payload.to_vec().serialize(&mut data)?;
+    Ok(data)
+}
+
+pub(crate) fn check_pda(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+) -> std::result::Result<u8, ProgramError> {
+    let (address, bump) = Pubkey::find_program_address(seeds, program_id);
+    if address != *account.key {
+        return Err(CoreBridgeError::InvalidDerivedAccount.into());
+    }
+    Ok(bump)
+}
+
+pub(crate) fn create_and_write<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    data: &[u8],
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(data.len());
+    let signers: &[&[&[u8]]] = if signer_seeds.is_empty() {
+        &[]
+    } else {
+        &[signer_seeds]
+    };
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            account.key,
+            lamports,
+            data.len() as u64,
+            program_id,
+        ),
+        &[payer.clone(), account.clone(), system_program.clone()],
+        signers,
+    )?;
+    write(account, data)
+}
+
+pub(crate) fn write(account: &AccountInfo, data: &[u8]) -> ProgramResult {
+    account.data.borrow_mut()[..data.len()].copy_from_slice(data);
+    Ok(())
+}
+
+/// Core bridge `initialize`.
+pub fn initialize_ix(payer: &Pubkey, data: InitializeData) -> Instruction {
+    let mut ix_data = wormhole::Instruction::Initialize.try_to_vec().unwrap();
+    ix_data.extend(data.try_to_vec().unwrap());
+
+    Instruction {
+        program_id: CORE_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(bridge_address(), false),
+            AccountMeta::new(GuardianSetData::address(0), false),
+            AccountMeta::new(fee_collector_address(), false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: ix_data,
+    }
+}
+
+/// Core bridge `verify_signatures`. `signers[guardian_index]` is the position
+/// of that guardian's signature in the preceding secp256k1 instruction, or
+/// `-1` if it did not sign.
+pub fn verify_signatures_ix(
+    payer: &Pubkey,
+    guardian_set_index: u32,
+    signature_set: &Pubkey,
+    signers: [i8; MAX_GUARDIANS],
+) -> Instruction {
+    wormhole::verify_signatures_ix(
+        *payer,
+        GuardianSetData::address(guardian_set_index),
+        *signature_set,
+        signers,
+    )
+}
+
+/// Core bridge `post_vaa` for a body whose signatures were collected in
+/// `signature_set`.
+pub fn post_vaa_ix(
+    payer: &Pubkey,
+    guardian_set_index: u32,
+    signature_set: &Pubkey,
+    body: &crate::vaa::VaaBody,
+) -> Instruction {
+    wormhole::post_vaa_ix(
+        *payer,
+        GuardianSetData::address(guardian_set_index),
+        *signature_set,
+        body.posted_vaa_address(),
+        wormhole::PostVaaArgs {
+            version: 1,
+            guardian_set_index,
+            timestamp: body.timestamp,
+            nonce: body.nonce,
+            emitter_chain: body.emitter_chain,
+            emitter_address: body.emitter_address,
+            sequence: body.sequence,
+            consistency_level: body.consistency_level,
+            payload: body.payload.clone(),
+        },
+    )
+}
+
+/// Core bridge `upgrade_guardian_set`, consuming a posted governance VAA.
+pub fn upgrade_guardian_set_ix(
+    payer: &Pubkey,
+    governance: &crate::vaa::VaaBody,
+    old_index: u32,
+) -> Instruction {
+    Instruction {
+        program_id: CORE_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(bridge_address(), false),
+            AccountMeta::new_readonly(governance.posted_vaa_address(), false),
+            AccountMeta::new(
+                claim_address(
+                    governance.emitter_chain,
+                    &governance.emitter_address,
+                    governance.sequence,
+                ),
+                false,
+            ),
+            AccountMeta::new(GuardianSetData::address(old_index), false),
+            AccountMeta::new(GuardianSetData::address(old_index + 1), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: wormhole::Instruction::UpgradeGuardianSet
+            .try_to_vec()
+            .unwrap(),
+    }
+}
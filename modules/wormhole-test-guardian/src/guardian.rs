@@ -0,0 +1,159 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::Instruction, keccak},
+};
+use libsecp256k1::{Message, PublicKey, SecretKey};
+
+use wormhole_anchor_sdk::wormhole::{
+    self, GuardianSignature, Vaa, VaaBody, GUARDIAN_KEY_SIZE, GUARDIAN_SIGNATURE_SIZE,
+};
+
+use crate::core_bridge::GuardianSetData;
+
+/// A guardian holding a secp256k1 signing key.
+pub struct Guardian {
+    secret_key: SecretKey,
+}
+
+impl Guardian {
+    /// Deterministic guardian derived from `seed`, so that keys are stable
+    /// across test runs.
+    pub fn from_seed(seed: &[u8]) -> Self {
+        let mut digest = keccak::hash(seed).to_bytes();
+        loop {
+            // A keccak256 digest is almost always a valid scalar, but rehash
+            // just in case.
+            if let Ok(secret_key) = SecretKey::parse(&digest) {
+                return Self { secret_key };
+            }
+            digest = keccak::hash(&digest).to_bytes();
+        }
+    }
+
+    /// Ethereum address of this guardian, i.e. what the core bridge stores in
+    /// its guardian set.
+    pub fn key(&self) -> [u8; GUARDIAN_KEY_SIZE] {
+        let public_key = PublicKey::from_secret_key(&self.secret_key);
+        let hash = keccak::hash(&public_key.serialize()[1..]).to_bytes();
+
+        let mut key = [0u8; GUARDIAN_KEY_SIZE];
+        key.copy_from_slice(&hash[12..]);
+        key
+    }
+
+    /// Sign a VAA body hash. The secp256k1 precompile hashes its message
+    /// again before recovery, so the signed digest is `keccak256(body_hash)`.
+    pub fn sign(&self, body_hash: &[u8; 32]) -> [u8; GUARDIAN_SIGNATURE_SIZE] {
+        self.sign_message(body_hash)
+    }
+
+    /// Sign a query response. The signed digest is
+    /// `keccak256(query_response_message(keccak256(response)))`.
+    pub fn sign_query_response(&self, response: &[u8]) -> [u8; GUARDIAN_SIGNATURE_SIZE] {
+        self.sign_message(&query_message(response))
+    }
+
+    /// Sign `keccak256(message)`, what the secp256k1 precompile checks for a
+    /// `message`.
+    fn sign_message(&self, message: &[u8]) -> [u8; GUARDIAN_SIGNATURE_SIZE] {
+        let digest = keccak::hash(message).to_bytes();
+        let (signature, recovery_id) =
+            libsecp256k1::sign(&Message::parse(&digest), &self.secret_key);
+
+        let mut out = [0u8; GUARDIAN_SIGNATURE_SIZE];
+        out[..64].copy_from_slice(&signature.serialize());
+        out[64] = recovery_id.serialize();
+        out
+    }
+}
+
+/// An ordered set of guardians sharing a guardian set index.
+pub struct GuardianSet {
+    pub index: u32,
+    pub guardians: Vec<Guardian>,
+}
+
+impl GuardianSet {
+    /// Create `num_guardians` deterministic guardians for `index`.
+    pub fn new(index: u32, num_guardians: usize) -> Self {
+        let guardians = (0..num_guardians)
+            .map(|i| {
+                Guardian::from_seed(
+                    &[&index.to_be_bytes()[..], &(i as u32).to_be_bytes()[..]].concat(),
+                )
+            })
+            .collect();
+
+        Self { index, guardians }
+    }
+
+    pub fn len(&self) -> usize {
+        self.guardians.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.guardians.is_empty()
+    }
+
+    pub fn keys(&self) -> Vec<[u8; GUARDIAN_KEY_SIZE]> {
+        self.guardians.iter().map(Guardian::key).collect()
+    }
+
+    /// Minimum number of signatures the core bridge accepts for this set.
+    pub fn quorum(&self) -> usize {
+        wormhole::quorum(self.len())
+    }
+
+    /// Indices of every guardian in the set.
+    pub fn all(&self) -> Vec<usize> {
+        (0..self.len()).collect()
+    }
+
+    /// Signed VAA of `body`, with the signatures of the guardians at
+    /// `signers`.
+    pub fn sign_vaa(&self, body: &VaaBody, signers: &[usize]) -> Vaa {
+        let hash = body.body_hash();
+        Vaa {
+            version: 1,
+            guardian_set_index: self.index,
+            signatures: signers
+                .iter()
+                .map(|&index| GuardianSignature {
+                    index: index as u8,
+                    signature: self.guardians[index].sign(&hash),
+                })
+                .collect(),
+            body: body.clone(),
+        }
+    }
+
+    /// secp256k1 precompile instruction with the signatures of `response` by
+    /// the guardians at `signers`, for an instruction checking them with
+    /// [`wormhole::query_signers`]. It must be at `instruction_index` in its
+    /// transaction, right before that instruction.
+    pub fn sign_query_response(
+        &self,
+        response: &[u8],
+        signers: &[usize],
+        instruction_index: u8,
+    ) -> Instruction {
+        let signatures = signers
+            .iter()
+            .map(|&index| {
+                let guardian = &self.guardians[index];
+                (guardian.key(), guardian.sign_query_response(response))
+            })
+            .collect::<Vec<_>>();
+
+        wormhole::secp256k1_instruction(&signatures, &query_message(response), instruction_index)
+    }
+
+    /// Address of the core bridge's `GuardianSet` account for this set.
+    pub fn address(&self) -> Pubkey {
+        GuardianSetData::address(self.index)
+    }
+}
+
+fn query_message(response: &[u8]) -> [u8; wormhole::QUERY_RESPONSE_MESSAGE_SIZE] {
+    wormhole::query_response_message(&wormhole::query_response_hash(response))
+}
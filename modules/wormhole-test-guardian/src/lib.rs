@@ -0,0 +1,27 @@
+//! Local guardian tooling for `solana-program-test`.
+//!
+//! [`GuardianSet`] holds secp256k1 guardian keys and signs [`VaaBody`]s built
+//! from CAT payloads. [`LocalWormhole`] drives `verify_signatures` and
+//! `post_vaa` against a locally loaded core bridge, including guardian set
+//! rotation, so integration tests exercise the real signature path offline.
+//! [`LocalTokenBridge`] adds a Token Bridge for programs that route tokens
+//! through Portal.
+//! [`eth_call_response`] builds Wormhole Queries responses for
+//! [`GuardianSet::sign_query_response`] to sign.
+//! [`capture_log_data`] makes Anchor events visible in the transaction logs.
+
+pub use core_bridge::{CoreBridgeError, GuardianSetData};
+pub use guardian::*;
+pub use local::*;
+pub use log_data::*;
+pub use query::*;
+pub use token_bridge::TokenBridgeError;
+pub use vaa::*;
+
+pub mod core_bridge;
+pub mod guardian;
+pub mod local;
+pub mod log_data;
+pub mod query;
+pub mod token_bridge;
+pub mod vaa;
@@ -0,0 +1,298 @@
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use wormhole_anchor_sdk::{
+    token_bridge::{program::ID as TOKEN_BRIDGE_ID, AssetMeta, WrappedMint},
+    wormhole::{program::ID as CORE_BRIDGE_ID, secp256k1_instruction, verify_signers, BridgeData},
+};
+
+use crate::{
+    core_bridge::{self, GuardianSetData, InitializeData},
+    guardian::GuardianSet,
+    token_bridge,
+    vaa::{guardian_set_upgrade, register_chain, VaaBody},
+};
+
+/// Number of signatures verified per transaction. Seven keeps the secp256k1
+/// instruction plus `verify_signatures` under the packet size limit.
+pub const SIGNATURES_PER_TRANSACTION: usize = 7;
+
+/// Program name used to look up a `wormhole_core.so` fixture.
+pub const CORE_BRIDGE_PROGRAM_NAME: &str = "wormhole_core";
+
+/// Program name used to look up a `wormhole_token_bridge.so` fixture.
+pub const TOKEN_BRIDGE_PROGRAM_NAME: &str = "wormhole_token_bridge";
+
+/// A locally run Wormhole core bridge together with the guardian set that
+/// currently signs for it.
+pub struct LocalWormhole {
+    pub guardian_set: GuardianSet,
+    /// Seconds an old guardian set stays valid after a rotation.
+    pub guardian_set_expiration_time: u32,
+    /// Message fee in lamports.
+    pub fee: u64,
+    governance_sequence: u64,
+}
+
+impl LocalWormhole {
+    pub fn new(guardian_set: GuardianSet) -> Self {
+        Self {
+            guardian_set,
+            guardian_set_expiration_time: 86_400,
+            fee: 0,
+            governance_sequence: 0,
+        }
+    }
+
+    pub fn with_fee(mut self, fee: u64) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    pub fn with_guardian_set_expiration_time(mut self, seconds: u32) -> Self {
+        self.guardian_set_expiration_time = seconds;
+        self
+    }
+
+    /// Register the core bridge with `program_test` under its network ID.
+    pub fn add_program(program_test: &mut ProgramTest) {
+        program_test.add_program(
+            CORE_BRIDGE_PROGRAM_NAME,
+            CORE_BRIDGE_ID,
+            processor!(core_bridge::process_instruction),
+        );
+    }
+
+    /// Initialize the core bridge with the current guardian set.
+    pub async fn initialize(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> Result<(), BanksClientError> {
+        let ix = core_bridge::initialize_ix(
+            &context.payer.pubkey(),
+            InitializeData {
+                guardian_set_expiration_time: self.guardian_set_expiration_time,
+                fee: self.fee,
+                initial_guardians: self.guardian_set.keys(),
+            },
+        );
+        process(context, &[ix], &[]).await
+    }
+
+    /// Sign `body` with every current guardian and post it. Returns the
+    /// `PostedVAA` account address.
+    pub async fn post_vaa(
+        &self,
+        context: &mut ProgramTestContext,
+        body: &VaaBody,
+    ) -> Result<Pubkey, BanksClientError> {
+        self.post_vaa_with_signers(context, &self.guardian_set, body, &self.guardian_set.all())
+            .await
+    }
+
+    /// Sign `body` with the guardians of `guardian_set` at `signers` and post
+    /// it. Use this to post with a stale set or without quorum.
+    pub async fn post_vaa_with_signers(
+        &self,
+        context: &mut ProgramTestContext,
+        guardian_set: &GuardianSet,
+        body: &VaaBody,
+        signers: &[usize],
+    ) -> Result<Pubkey, BanksClientError> {
+        let signature_set = Keypair::new();
+        self.verify_signatures(context, guardian_set, body, signers, &signature_set)
+            .await?;
+
+        let ix = core_bridge::post_vaa_ix(
+            &context.payer.pubkey(),
+            guardian_set.index,
+            &signature_set.pubkey(),
+            body,
+        );
+        process(context, &[ix], &[]).await?;
+
+        Ok(body.posted_vaa_address())
+    }
+
+    /// Run `verify_signatures` for `signers`, batching signatures into as
+    /// many transactions as needed.
+    pub async fn verify_signatures(
+        &self,
+        context: &mut ProgramTestContext,
+        guardian_set: &GuardianSet,
+        body: &VaaBody,
+        signers: &[usize],
+        signature_set: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let hash = body.body_hash();
+        for batch in signers.chunks(SIGNATURES_PER_TRANSACTION) {
+            let signatures = batch
+                .iter()
+                .map(|&index| {
+                    let guardian = &guardian_set.guardians[index];
+                    (guardian.key(), guardian.sign(&hash))
+                })
+                .collect::<Vec<_>>();
+
+            let ixs = [
+                secp256k1_instruction(&signatures, &hash, 0),
+                core_bridge::verify_signatures_ix(
+                    &context.payer.pubkey(),
+                    guardian_set.index,
+                    &signature_set.pubkey(),
+                    verify_signers(batch),
+                ),
+            ];
+            process(context, &ixs, &[signature_set]).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Rotate the core bridge to `new_set` via a governance VAA signed by the
+    /// current set. The previous set expires after
+    /// [`guardian_set_expiration_time`](Self::guardian_set_expiration_time).
+    /// Returns the replaced set.
+    pub async fn upgrade_guardian_set(
+        &mut self,
+        context: &mut ProgramTestContext,
+        new_set: GuardianSet,
+    ) -> Result<GuardianSet, BanksClientError> {
+        let governance = guardian_set_upgrade(self.next_governance_sequence(), &new_set);
+        self.post_vaa(context, &governance).await?;
+
+        let ix = core_bridge::upgrade_guardian_set_ix(
+            &context.payer.pubkey(),
+            &governance,
+            self.guardian_set.index,
+        );
+        process(context, &[ix], &[]).await?;
+
+        Ok(std::mem::replace(&mut self.guardian_set, new_set))
+    }
+
+    /// Sequence of the next governance VAA. Governance VAAs are claimed by
+    /// sequence, so each one needs a fresh one.
+    pub fn next_governance_sequence(&mut self) -> u64 {
+        self.governance_sequence += 1;
+        self.governance_sequence - 1
+    }
+
+    pub async fn bridge_data(&self, context: &mut ProgramTestContext) -> BridgeData {
+        let account = context
+            .banks_client
+            .get_account(core_bridge::bridge_address())
+            .await
+            .unwrap()
+            .expect("core bridge is not initialized");
+        BridgeData::deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn guardian_set_data(
+        &self,
+        context: &mut ProgramTestContext,
+        index: u32,
+    ) -> GuardianSetData {
+        let account = context
+            .banks_client
+            .get_account(GuardianSetData::address(index))
+            .await
+            .unwrap()
+            .expect("guardian set does not exist");
+        GuardianSetData::deserialize(&mut &account.data[..]).unwrap()
+    }
+}
+
+/// A locally run Token Bridge, posting and verifying through a
+/// [`LocalWormhole`].
+pub struct LocalTokenBridge;
+
+impl LocalTokenBridge {
+    /// Register the Token Bridge with `program_test` under its network ID.
+    pub fn add_program(program_test: &mut ProgramTest) {
+        program_test.add_program(
+            TOKEN_BRIDGE_PROGRAM_NAME,
+            TOKEN_BRIDGE_ID,
+            processor!(token_bridge::process_instruction),
+        );
+    }
+
+    /// Initialize the Token Bridge against the local core bridge.
+    pub async fn initialize(context: &mut ProgramTestContext) -> Result<(), BanksClientError> {
+        let ix = token_bridge::initialize_ix(&context.payer.pubkey());
+        process(context, &[ix], &[]).await
+    }
+
+    /// Register the Token Bridge at `emitter_address` on `emitter_chain` via
+    /// a governance VAA signed by `wormhole`'s guardians.
+    pub async fn register_chain(
+        context: &mut ProgramTestContext,
+        wormhole: &mut LocalWormhole,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+    ) -> Result<(), BanksClientError> {
+        let governance = register_chain(
+            wormhole.next_governance_sequence(),
+            emitter_chain,
+            emitter_address,
+        );
+        wormhole.post_vaa(context, &governance).await?;
+
+        let ix = token_bridge::register_chain_ix(&context.payer.pubkey(), &governance);
+        process(context, &[ix], &[]).await
+    }
+
+    /// Attest native `mint`. Returns the address of the posted message.
+    pub async fn attest_token(
+        context: &mut ProgramTestContext,
+        mint: &Pubkey,
+    ) -> Result<Pubkey, BanksClientError> {
+        let message = Keypair::new();
+        let ix = token_bridge::attest_token_ix(&context.payer.pubkey(), mint, &message.pubkey(), 0);
+        process(context, &[ix], &[&message]).await?;
+
+        Ok(message.pubkey())
+    }
+
+    /// Post `attestation`, an `AssetMeta` message of a foreign Token Bridge,
+    /// and create its wrapped mint. Returns the wrapped mint address.
+    pub async fn create_wrapped(
+        context: &mut ProgramTestContext,
+        wormhole: &LocalWormhole,
+        attestation: &VaaBody,
+    ) -> Result<Pubkey, BanksClientError> {
+        wormhole.post_vaa(context, attestation).await?;
+
+        let ix = token_bridge::create_wrapped_ix(&context.payer.pubkey(), attestation);
+        process(context, &[ix], &[]).await?;
+
+        let asset_meta = AssetMeta::deserialize(&mut &attestation.payload[..]).unwrap();
+        Ok(WrappedMint::address(
+            asset_meta.token_chain,
+            &asset_meta.token_address,
+        ))
+    }
+}
+
+/// Sign `ixs` with the context payer and `signers`, and process them with a
+/// fresh blockhash so identical transactions are not deduplicated.
+pub async fn process(
+    context: &mut ProgramTestContext,
+    ixs: &[solana_sdk::instruction::Instruction],
+    signers: &[&Keypair],
+) -> Result<(), BanksClientError> {
+    let blockhash = context.get_new_latest_blockhash().await?;
+
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&context.payer.pubkey()),
+        &all_signers,
+        blockhash,
+    );
+    context.banks_client.process_transaction(tx).await
+}
@@ -0,0 +1,131 @@
+//! `sol_log_data` for builtin programs.
+//!
+//! `solana-program-test` runs programs natively and its syscall stubs print
+//! `sol_log_data` to stdout instead of the transaction logs, so Anchor events
+//! never reach the client. [`capture_log_data`] wraps the stubs to log the
+//! data like the runtime does, and [`program_data`] reads it back.
+
+use std::sync::Once;
+
+use anchor_lang::solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::Instruction,
+    program_stubs::{self, SyscallStubs},
+    pubkey::Pubkey,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+struct LogDataStubs {
+    inner: Box<dyn SyscallStubs>,
+}
+
+impl SyscallStubs for LogDataStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+
+    fn sol_log_compute_units(&self) {
+        self.inner.sol_log_compute_units()
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+
+    unsafe fn sol_memcpy(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memcpy(dst, src, n)
+    }
+
+    unsafe fn sol_memmove(&self, dst: *mut u8, src: *const u8, n: usize) {
+        self.inner.sol_memmove(dst, src, n)
+    }
+
+    unsafe fn sol_memcmp(&self, s1: *const u8, s2: *const u8, n: usize, result: *mut i32) {
+        self.inner.sol_memcmp(s1, s2, n, result)
+    }
+
+    unsafe fn sol_memset(&self, s: *mut u8, c: u8, n: usize) {
+        self.inner.sol_memset(s, c, n)
+    }
+
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        self.inner
+            .sol_log(&format!("{PROGRAM_DATA}{}", fields.join(" ")))
+    }
+
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner.sol_get_processed_sibling_instruction(index)
+    }
+
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner.sol_get_stack_height()
+    }
+}
+
+/// Route `sol_log_data` into the transaction logs. Call after the first
+/// `ProgramTest` has started, since starting installs the stubs this wraps.
+pub fn capture_log_data() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let inner = program_stubs::set_syscall_stubs(Box::new(LogDataStubs {
+            inner: Box::new(NoStubs),
+        }));
+        program_stubs::set_syscall_stubs(Box::new(LogDataStubs { inner }));
+    });
+}
+
+/// Placeholder while the real stubs are being swapped.
+struct NoStubs;
+
+impl SyscallStubs for NoStubs {}
+
+/// Data logged with `sol_log_data` (e.g. Anchor events) in `logs`, one entry
+/// per call, fields concatenated.
+pub fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| {
+            log.strip_prefix("Program log: ")
+                .unwrap_or(log)
+                .strip_prefix(PROGRAM_DATA)
+        })
+        .map(|data| {
+            data.split(' ')
+                .flat_map(|field| STANDARD.decode(field).unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
@@ -0,0 +1,46 @@
+use wormhole_anchor_sdk::wormhole::{
+    EthCallData, EthCallQuery, EthCallQueryResponse, PerChainQuery, PerChainQueryResponse,
+    QueryRequest, QueryResponse, QUERY_REQUEST_CHAIN_OFF_CHAIN, QUERY_TYPE_ETH_CALL,
+};
+
+/// Response to an off-chain request of `eth_call`s on `chain_id` at
+/// `block_number`, made at `block_time` (in seconds). `calls` pairs each call
+/// with its ABI encoded result.
+pub fn eth_call_response(
+    chain_id: u16,
+    block_number: u64,
+    block_time: u64,
+    calls: &[(EthCallData, Vec<u8>)],
+) -> QueryResponse {
+    let query = EthCallQuery {
+        block_id: format!("0x{block_number:x}").into_bytes(),
+        calls: calls.iter().map(|(call, _)| call.clone()).collect(),
+    };
+    let response = EthCallQueryResponse {
+        block_number,
+        block_hash: [0xbb; 32],
+        block_time: block_time * 1_000_000,
+        results: calls.iter().map(|(_, result)| result.clone()).collect(),
+    };
+
+    QueryResponse {
+        version: 1,
+        request_chain_id: QUERY_REQUEST_CHAIN_OFF_CHAIN,
+        // Guardians check the requester's signature, not consumers.
+        request_id: vec![0; 65],
+        request: QueryRequest {
+            version: 1,
+            nonce: block_number as u32,
+            queries: vec![PerChainQuery {
+                chain_id,
+                query_type: QUERY_TYPE_ETH_CALL,
+                query: query.to_bytes(),
+            }],
+        },
+        responses: vec![PerChainQueryResponse {
+            chain_id,
+            query_type: QUERY_TYPE_ETH_CALL,
+            response: response.to_bytes(),
+        }],
+    }
+}
@@ -0,0 +1,760 @@
+//! Native stand-in for the Wormhole Token Bridge (Portal).
+//!
+//! It implements `initialize`, the `register_chain` governance action,
+//! `attest_token`, `create_wrapped` and the native transfers with payload
+//! (`transfer_native_with_payload` and `complete_native_with_payload`) with
+//! the same account layouts, PDA seeds and payload encoding as the deployed
+//! program. Messages are posted through the core bridge stand-in. Metaplex
+//! metadata is not modelled: attestations carry an empty symbol and name, and
+//! `create_wrapped` only creates the wrapped mint and its `WrappedMeta`.
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        self, entrypoint::ProgramResult, instruction::Instruction, program::invoke_signed,
+        program_error::ProgramError, program_pack::Pack, system_instruction, sysvar,
+    },
+};
+use anchor_spl::token::spl_token;
+use wormhole_anchor_sdk::{
+    token_bridge::{
+        self, program::ID as TOKEN_BRIDGE_ID, AssetMeta, Config, EndpointRegistration,
+        TransferWithMeta, TransferWithPayload, WrappedMeta, WrappedMint,
+    },
+    wormhole::{self, program::ID as CORE_BRIDGE_ID, BridgeData, Finality, PostedVaaData},
+};
+
+use crate::{
+    core_bridge::{self, check_pda, create_and_write},
+    vaa::{
+        VaaBody, GOVERNANCE_ACTION_REGISTER_CHAIN, GOVERNANCE_CHAIN, GOVERNANCE_EMITTER,
+        GOVERNANCE_MODULE_TOKEN_BRIDGE,
+    },
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Errors raised by the stand-in, surfaced as `ProgramError::Custom`.
+pub enum TokenBridgeError {
+    InvalidConfig = 0x200,
+    InvalidGovernanceEmitter,
+    InvalidGovernanceAction,
+    AlreadyClaimed,
+    InvalidEndpoint,
+    InvalidChain,
+    InvalidMint,
+    InvalidAmount,
+    InvalidSender,
+    InvalidRedeemer,
+    InvalidRecipient,
+    InvalidPayload,
+    WrappedAsset,
+}
+
+impl From<TokenBridgeError> for ProgramError {
+    fn from(err: TokenBridgeError) -> Self {
+        ProgramError::Custom(err as u32)
+    }
+}
+
+/// Entrypoint registered with `ProgramTest::add_program`.
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let mut data = data;
+    match token_bridge::Instruction::deserialize(&mut data)? {
+        // The deployed program takes the core bridge address as the argument.
+        token_bridge::Instruction::Initialize => {
+            initialize(program_id, accounts, Pubkey::deserialize(&mut data)?)
+        }
+        token_bridge::Instruction::RegisterChain => register_chain(program_id, accounts),
+        token_bridge::Instruction::AttestToken { batch_id } => {
+            attest_token(program_id, accounts, batch_id)
+        }
+        token_bridge::Instruction::CreateWrapped {} => create_wrapped(program_id, accounts),
+        token_bridge::Instruction::TransferNativeWithPayload {
+            batch_id,
+            amount,
+            recipient_address,
+            recipient_chain,
+            payload,
+            cpi_program_id,
+        } => transfer_native_with_payload(
+            program_id,
+            accounts,
+            batch_id,
+            amount,
+            recipient_address,
+            recipient_chain,
+            payload,
+            cpi_program_id,
+        ),
+        token_bridge::Instruction::CompleteNativeWithPayload {} => {
+            complete_native_with_payload(program_id, accounts)
+        }
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wormhole_bridge: Pubkey,
+) -> ProgramResult {
+    let [payer, config, _rent, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let bump = check_pda(config, &[Config::SEED_PREFIX], program_id)?;
+    if !config.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_and_write(
+        payer,
+        config,
+        system_program,
+        program_id,
+        &Config { wormhole_bridge }.try_to_vec()?,
+        &[Config::SEED_PREFIX, &[bump]],
+    )
+}
+
+fn register_chain(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer, config, endpoint, vaa, claim, _rent, system_program] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_config(program_id, config)?;
+
+    let posted = load_posted(vaa)?;
+    if posted.emitter_chain() != GOVERNANCE_CHAIN || *posted.emitter_address() != GOVERNANCE_EMITTER
+    {
+        return Err(TokenBridgeError::InvalidGovernanceEmitter.into());
+    }
+
+    // Payload: module (32), action (1), chain (2), emitter chain (2), emitter
+    // address (32).
+    let payload = &posted.payload;
+    if payload.len() != 69
+        || payload[..32] != GOVERNANCE_MODULE_TOKEN_BRIDGE
+        || payload[32] != GOVERNANCE_ACTION_REGISTER_CHAIN
+    {
+        return Err(TokenBridgeError::InvalidGovernanceAction.into());
+    }
+    let target_chain = u16::from_be_bytes([payload[33], payload[34]]);
+    if target_chain != 0 && target_chain != wormhole::CHAIN_ID_SOLANA {
+        return Err(TokenBridgeError::InvalidGovernanceAction.into());
+    }
+    let registration = EndpointRegistration {
+        emitter_chain: u16::from_be_bytes([payload[35], payload[36]]),
+        emitter_address: payload[37..69].try_into().unwrap(),
+    };
+
+    claim_vaa(program_id, payer, claim, system_program, &posted)?;
+
+    let bump = check_pda(
+        endpoint,
+        &[
+            &registration.emitter_chain.to_be_bytes(),
+            &registration.emitter_address,
+        ],
+        program_id,
+    )?;
+    if !endpoint.data_is_empty() {
+        return Err(TokenBridgeError::InvalidEndpoint.into());
+    }
+    create_and_write(
+        payer,
+        endpoint,
+        system_program,
+        program_id,
+        &registration.try_to_vec()?,
+        &[
+            &registration.emitter_chain.to_be_bytes(),
+            &registration.emitter_address,
+            &[bump],
+        ],
+    )
+}
+
+fn attest_token(program_id: &Pubkey, accounts: &[AccountInfo], batch_id: u32) -> ProgramResult {
+    let [payer, config, mint, wrapped_meta, _spl_metadata, bridge, message, emitter, sequence, fee_collector, clock, rent, system_program, wormhole_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let config_data = load_config(program_id, config)?;
+    if *bridge.key != config_data.wormhole_bridge {
+        return Err(TokenBridgeError::InvalidConfig.into());
+    }
+    let mint_data = load_mint(mint)?;
+
+    // Wrapped mints have a `WrappedMeta` and cannot be attested.
+    check_pda(
+        wrapped_meta,
+        &[WrappedMeta::SEED_PREFIX, mint.key.as_ref()],
+        program_id,
+    )?;
+    if !wrapped_meta.data_is_empty() {
+        return Err(TokenBridgeError::WrappedAsset.into());
+    }
+    let emitter_bump = check_pda(emitter, &[token_bridge::SEED_PREFIX_EMITTER], program_id)?;
+
+    let asset_meta = AssetMeta {
+        token_address: mint.key.to_bytes(),
+        token_chain: wormhole::CHAIN_ID_SOLANA,
+        decimals: mint_data.decimals,
+        ..Default::default()
+    };
+    post_message(
+        [
+            payer,
+            bridge,
+            message,
+            emitter,
+            sequence,
+            fee_collector,
+            clock,
+            rent,
+            system_program,
+            wormhole_program,
+        ],
+        batch_id,
+        asset_meta.try_to_vec()?,
+        emitter_bump,
+    )
+}
+
+fn create_wrapped(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer, config, endpoint, vaa, claim, mint, wrapped_meta, _spl_metadata, mint_authority, _rent, system_program, token_program, _spl_metadata_program, _wormhole_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_config(program_id, config)?;
+
+    let posted = load_posted(vaa)?;
+    let asset_meta = AssetMeta::deserialize(&mut &posted.payload[..])
+        .map_err(|_| TokenBridgeError::InvalidPayload)?;
+    check_endpoint(program_id, endpoint, &posted)?;
+    if asset_meta.token_chain == wormhole::CHAIN_ID_SOLANA {
+        return Err(TokenBridgeError::InvalidChain.into());
+    }
+
+    let mint_seeds = [
+        &WrappedMint::SEED_PREFIX[..],
+        &asset_meta.token_chain.to_be_bytes(),
+        &asset_meta.token_address,
+    ];
+    let mint_bump = check_pda(mint, &mint_seeds, program_id)?;
+    let meta_bump = check_pda(
+        wrapped_meta,
+        &[WrappedMeta::SEED_PREFIX, mint.key.as_ref()],
+        program_id,
+    )?;
+    check_pda(
+        mint_authority,
+        &[token_bridge::SEED_PREFIX_MINT_AUTHORITY],
+        program_id,
+    )?;
+
+    claim_vaa(program_id, payer, claim, system_program, &posted)?;
+
+    // A later attestation of the same token only updates the Metaplex
+    // metadata, which is not modelled.
+    if !mint.data_is_empty() {
+        return Ok(());
+    }
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            mint.key,
+            Rent::get()?.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        &[payer.clone(), mint.clone(), system_program.clone()],
+        &[&[mint_seeds[0], mint_seeds[1], mint_seeds[2], &[mint_bump]]],
+    )?;
+    invoke_signed(
+        &spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            mint.key,
+            mint_authority.key,
+            None,
+            asset_meta
+                .decimals
+                .min(token_bridge::MAX_WRAPPED_ASSET_DECIMALS),
+        )?,
+        &[mint.clone(), token_program.clone()],
+        &[],
+    )?;
+
+    create_and_write(
+        payer,
+        wrapped_meta,
+        system_program,
+        program_id,
+        &WrappedMeta {
+            chain: asset_meta.token_chain,
+            token_address: asset_meta.token_address,
+            original_decimals: asset_meta.decimals,
+        }
+        .try_to_vec()?,
+        &[WrappedMeta::SEED_PREFIX, mint.key.as_ref(), &[meta_bump]],
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn transfer_native_with_payload(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    batch_id: u32,
+    amount: u64,
+    recipient_address: [u8; 32],
+    recipient_chain: u16,
+    payload: Vec<u8>,
+    cpi_program_id: Option<Pubkey>,
+) -> ProgramResult {
+    let [payer, config, from, mint, custody, authority_signer, custody_signer, bridge, message, emitter, sequence, fee_collector, clock, sender, rent, system_program, wormhole_program, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    let config_data = load_config(program_id, config)?;
+    if *bridge.key != config_data.wormhole_bridge {
+        return Err(TokenBridgeError::InvalidConfig.into());
+    }
+    if recipient_chain == wormhole::CHAIN_ID_SOLANA {
+        return Err(TokenBridgeError::InvalidChain.into());
+    }
+    let mint_data = load_mint(mint)?;
+
+    let custody_bump = check_pda(custody, &[mint.key.as_ref()], program_id)?;
+    let authority_bump = check_pda(
+        authority_signer,
+        &[token_bridge::SEED_PREFIX_AUTHORITY_SIGNER],
+        program_id,
+    )?;
+    check_pda(
+        custody_signer,
+        &[token_bridge::SEED_PREFIX_CUSTODY_SIGNER],
+        program_id,
+    )?;
+    let emitter_bump = check_pda(emitter, &[token_bridge::SEED_PREFIX_EMITTER], program_id)?;
+
+    // A program sending through its sender PDA is recorded as the sender, so
+    // the recipient can tell which program sent the transfer.
+    if !sender.is_signer {
+        return Err(TokenBridgeError::InvalidSender.into());
+    }
+    let from_address = match cpi_program_id {
+        Some(cpi_program_id) => {
+            if *sender.key != token_bridge::sender_address(&cpi_program_id) {
+                return Err(TokenBridgeError::InvalidSender.into());
+            }
+            cpi_program_id.to_bytes()
+        }
+        None => sender.key.to_bytes(),
+    };
+
+    let normalized = token_bridge::normalize_amount(amount, mint_data.decimals);
+    if normalized == 0 {
+        return Err(TokenBridgeError::InvalidAmount.into());
+    }
+
+    if custody.data_is_empty() {
+        invoke_signed(
+            &system_instruction::create_account(
+                payer.key,
+                custody.key,
+                Rent::get()?.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::id(),
+            ),
+            &[payer.clone(), custody.clone(), system_program.clone()],
+            &[&[mint.key.as_ref(), &[custody_bump]]],
+        )?;
+        invoke_signed(
+            &spl_token::instruction::initialize_account3(
+                &spl_token::id(),
+                custody.key,
+                mint.key,
+                custody_signer.key,
+            )?,
+            &[custody.clone(), mint.clone(), token_program.clone()],
+            &[],
+        )?;
+    }
+
+    // Only the truncated amount is locked, so no dust is left in custody.
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            from.key,
+            custody.key,
+            authority_signer.key,
+            &[],
+            token_bridge::denormalize_amount(normalized, mint_data.decimals),
+        )?,
+        &[
+            from.clone(),
+            custody.clone(),
+            authority_signer.clone(),
+            token_program.clone(),
+        ],
+        &[&[
+            token_bridge::SEED_PREFIX_AUTHORITY_SIGNER,
+            &[authority_bump],
+        ]],
+    )?;
+
+    let transfer = TransferWithPayload::new(
+        &TransferWithMeta {
+            amount: normalized,
+            token_address: mint.key.to_bytes(),
+            token_chain: wormhole::CHAIN_ID_SOLANA,
+            to_address: recipient_address,
+            to_chain: recipient_chain,
+            from_address,
+        },
+        payload,
+    );
+    post_message(
+        [
+            payer,
+            bridge,
+            message,
+            emitter,
+            sequence,
+            fee_collector,
+            clock,
+            rent,
+            system_program,
+            wormhole_program,
+        ],
+        batch_id,
+        transfer.try_to_vec()?,
+        emitter_bump,
+    )
+}
+
+fn complete_native_with_payload(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let [payer, config, vaa, claim, endpoint, to, redeemer, _to_fees, custody, mint, custody_signer, _rent, system_program, _wormhole_program, token_program] =
+        accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+    load_config(program_id, config)?;
+
+    let posted = load_posted(vaa)?;
+    let transfer = TransferWithPayload::deserialize(&mut &posted.payload[..])
+        .map_err(|_| TokenBridgeError::InvalidPayload)?;
+
+    check_endpoint(program_id, endpoint, &posted)?;
+
+    if transfer.to_chain() != wormhole::CHAIN_ID_SOLANA {
+        return Err(TokenBridgeError::InvalidChain.into());
+    }
+    if transfer.token_chain() != wormhole::CHAIN_ID_SOLANA || transfer.mint() != *mint.key {
+        return Err(TokenBridgeError::InvalidMint.into());
+    }
+    let mint_data = load_mint(mint)?;
+
+    // The recipient signs, either itself or, for a program, through its
+    // redeemer PDA. The tokens go to a token account it owns.
+    let recipient = transfer.to();
+    if !redeemer.is_signer
+        || (*redeemer.key != recipient
+            && *redeemer.key != token_bridge::redeemer_address(&recipient))
+    {
+        return Err(TokenBridgeError::InvalidRedeemer.into());
+    }
+    if to.owner != &spl_token::id() {
+        return Err(TokenBridgeError::InvalidRecipient.into());
+    }
+    let to_data = spl_token::state::Account::unpack(&to.data.borrow())?;
+    if to_data.mint != *mint.key || to_data.owner != *redeemer.key {
+        return Err(TokenBridgeError::InvalidRecipient.into());
+    }
+
+    check_pda(custody, &[mint.key.as_ref()], program_id)?;
+    let custody_signer_bump = check_pda(
+        custody_signer,
+        &[token_bridge::SEED_PREFIX_CUSTODY_SIGNER],
+        program_id,
+    )?;
+
+    claim_vaa(program_id, payer, claim, system_program, &posted)?;
+
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            &spl_token::id(),
+            custody.key,
+            to.key,
+            custody_signer.key,
+            &[],
+            token_bridge::denormalize_amount(transfer.amount(), mint_data.decimals),
+        )?,
+        &[
+            custody.clone(),
+            to.clone(),
+            custody_signer.clone(),
+            token_program.clone(),
+        ],
+        &[&[
+            token_bridge::SEED_PREFIX_CUSTODY_SIGNER,
+            &[custody_signer_bump],
+        ]],
+    )
+}
+
+fn load_config(
+    program_id: &Pubkey,
+    config: &AccountInfo,
+) -> std::result::Result<Config, ProgramError> {
+    check_pda(config, &[Config::SEED_PREFIX], program_id)?;
+    if config.owner != program_id || config.data_is_empty() {
+        return Err(TokenBridgeError::InvalidConfig.into());
+    }
+    Ok(Config::deserialize(&mut &config.data.borrow()[..])?)
+}
+
+fn load_posted(vaa: &AccountInfo) -> std::result::Result<PostedVaaData, ProgramError> {
+    if *vaa.owner != CORE_BRIDGE_ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(PostedVaaData::deserialize(&mut &vaa.data.borrow()[..])?)
+}
+
+fn load_mint(mint: &AccountInfo) -> std::result::Result<spl_token::state::Mint, ProgramError> {
+    if mint.owner != &spl_token::id() {
+        return Err(TokenBridgeError::InvalidMint.into());
+    }
+    spl_token::state::Mint::unpack(&mint.data.borrow())
+}
+
+/// Only messages from a registered Token Bridge are consumed.
+fn check_endpoint(
+    program_id: &Pubkey,
+    endpoint: &AccountInfo,
+    posted: &PostedVaaData,
+) -> ProgramResult {
+    check_pda(
+        endpoint,
+        &[
+            &posted.emitter_chain().to_be_bytes(),
+            posted.emitter_address(),
+        ],
+        program_id,
+    )?;
+    if endpoint.owner != program_id || endpoint.data_is_empty() {
+        return Err(TokenBridgeError::InvalidEndpoint.into());
+    }
+    Ok(())
+}
+
+/// Pay the message fee and post `payload` from the Token Bridge emitter. The
+/// Token Bridge pays the fee itself.
+fn post_message(
+    [payer, bridge, message, emitter, sequence, fee_collector, clock, rent, system_program, wormhole_program]: [&AccountInfo; 10],
+    batch_id: u32,
+    payload: Vec<u8>,
+    emitter_bump: u8,
+) -> ProgramResult {
+    let fee = BridgeData::deserialize(&mut &bridge.data.borrow()[..])?.fee();
+    if fee > 0 {
+        invoke_signed(
+            &system_instruction::transfer(payer.key, fee_collector.key, fee),
+            &[payer.clone(), fee_collector.clone(), system_program.clone()],
+            &[],
+        )?;
+    }
+
+    let ix = Instruction {
+        program_id: CORE_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*bridge.key, false),
+            AccountMeta::new(*message.key, true),
+            AccountMeta::new_readonly(*emitter.key, true),
+            AccountMeta::new(*sequence.key, false),
+            AccountMeta::new(*payer.key, true),
+            AccountMeta::new(*fee_collector.key, false),
+            AccountMeta::new_readonly(*clock.key, false),
+            AccountMeta::new_readonly(*rent.key, false),
+            AccountMeta::new_readonly(*system_program.key, false),
+        ],
+        data: wormhole::Instruction::PostMessage {
+            batch_id,
+            payload,
+            finality: Finality::Finalized,
+        }
+        .try_to_vec()?,
+    };
+    invoke_signed(
+        &ix,
+        &[
+            bridge.clone(),
+            message.clone(),
+            emitter.clone(),
+            sequence.clone(),
+            payer.clone(),
+            fee_collector.clone(),
+            clock.clone(),
+            rent.clone(),
+            system_program.clone(),
+            wormhole_program.clone(),
+        ],
+        &[&[token_bridge::SEED_PREFIX_EMITTER, &[emitter_bump]]],
+    )
+}
+
+/// Mark `posted` as consumed. A VAA can only be claimed once.
+fn claim_vaa<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    claim: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    posted: &PostedVaaData,
+) -> ProgramResult {
+    let seeds = [
+        &posted.emitter_address()[..],
+        &posted.emitter_chain().to_be_bytes(),
+        &posted.sequence().to_be_bytes(),
+    ];
+    let bump = check_pda(claim, &seeds, program_id)?;
+    if !claim.data_is_empty() {
+        return Err(TokenBridgeError::AlreadyClaimed.into());
+    }
+    create_and_write(
+        payer,
+        claim,
+        system_program,
+        program_id,
+        &[1],
+        &[seeds[0], seeds[1], seeds[2], &[bump]],
+    )
+}
+
+/// Token Bridge `initialize`, posting through the local core bridge.
+pub fn initialize_ix(payer: &Pubkey) -> Instruction {
+    let mut data = token_bridge::Instruction::Initialize.try_to_vec().unwrap();
+    data.extend(core_bridge::bridge_address().try_to_vec().unwrap());
+
+    Instruction {
+        program_id: TOKEN_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(Config::address(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data,
+    }
+}
+
+/// Token Bridge `register_chain`, consuming a posted governance VAA.
+pub fn register_chain_ix(payer: &Pubkey, governance: &VaaBody) -> Instruction {
+    let emitter_chain = u16::from_be_bytes([governance.payload[35], governance.payload[36]]);
+    let emitter_address: [u8; 32] = governance.payload[37..69].try_into().unwrap();
+
+    Instruction {
+        program_id: TOKEN_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(Config::address(), false),
+            AccountMeta::new(
+                EndpointRegistration::address(emitter_chain, &emitter_address),
+                false,
+            ),
+            AccountMeta::new_readonly(governance.posted_vaa_address(), false),
+            AccountMeta::new(
+                token_bridge::claim_address(
+                    governance.emitter_chain,
+                    &governance.emitter_address,
+                    governance.sequence,
+                ),
+                false,
+            ),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+        data: token_bridge::Instruction::RegisterChain
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Token Bridge `attest_token` of native `mint`. `message` signs, as a new
+/// message account.
+pub fn attest_token_ix(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    message: &Pubkey,
+    batch_id: u32,
+) -> Instruction {
+    let emitter = token_bridge::emitter_address();
+
+    Instruction {
+        program_id: TOKEN_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(Config::address(), false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(WrappedMeta::address(mint), false),
+            AccountMeta::new_readonly(token_bridge::spl_metadata_address(mint), false),
+            AccountMeta::new(core_bridge::bridge_address(), false),
+            AccountMeta::new(*message, true),
+            AccountMeta::new_readonly(emitter, false),
+            AccountMeta::new(core_bridge::sequence_address(&emitter), false),
+            AccountMeta::new(core_bridge::fee_collector_address(), false),
+            AccountMeta::new_readonly(sysvar::clock::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(CORE_BRIDGE_ID, false),
+        ],
+        data: token_bridge::Instruction::AttestToken { batch_id }
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Token Bridge `create_wrapped`, consuming a posted `AssetMeta` VAA.
+pub fn create_wrapped_ix(payer: &Pubkey, body: &VaaBody) -> Instruction {
+    let asset_meta = AssetMeta::deserialize(&mut &body.payload[..]).unwrap();
+    let mint = WrappedMint::address(asset_meta.token_chain, &asset_meta.token_address);
+
+    Instruction {
+        program_id: TOKEN_BRIDGE_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(Config::address(), false),
+            AccountMeta::new_readonly(
+                EndpointRegistration::address(body.emitter_chain, &body.emitter_address),
+                false,
+            ),
+            AccountMeta::new_readonly(body.posted_vaa_address(), false),
+            AccountMeta::new(
+                token_bridge::claim_address(
+                    body.emitter_chain,
+                    &body.emitter_address,
+                    body.sequence,
+                ),
+                false,
+            ),
+            AccountMeta::new(mint, false),
+            AccountMeta::new(WrappedMeta::address(&mint), false),
+            AccountMeta::new(token_bridge::spl_metadata_address(&mint), false),
+            AccountMeta::new_readonly(token_bridge::mint_authority_address(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(token_bridge::program::spl_metadata::ID, false),
+            AccountMeta::new_readonly(CORE_BRIDGE_ID, false),
+        ],
+        data: token_bridge::Instruction::CreateWrapped {}
+            .try_to_vec()
+            .unwrap(),
+    }
+}
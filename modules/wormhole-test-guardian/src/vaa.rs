@@ -0,0 +1,82 @@
+pub use wormhole_anchor_sdk::wormhole::VaaBody;
+
+use crate::guardian::GuardianSet;
+
+/// Chain ID of the core bridge governance emitter.
+pub const GOVERNANCE_CHAIN: u16 = 1;
+
+/// Address of the core bridge governance emitter.
+pub const GOVERNANCE_EMITTER: [u8; 32] = {
+    let mut address = [0u8; 32];
+    address[31] = 4;
+    address
+};
+
+/// Core bridge governance module, AKA `"Core"` left-padded to 32 bytes.
+pub const GOVERNANCE_MODULE_CORE: [u8; 32] = {
+    let mut module = [0u8; 32];
+    module[28] = b'C';
+    module[29] = b'o';
+    module[30] = b'r';
+    module[31] = b'e';
+    module
+};
+
+/// Governance action that rotates the guardian set.
+pub const GOVERNANCE_ACTION_UPGRADE_GUARDIAN_SET: u8 = 2;
+
+/// Governance message rotating the core bridge to `new_set`.
+pub fn guardian_set_upgrade(sequence: u64, new_set: &GuardianSet) -> VaaBody {
+    let mut payload = Vec::with_capacity(32 + 1 + 2 + 4 + 1 + 20 * new_set.len());
+    payload.extend_from_slice(&GOVERNANCE_MODULE_CORE);
+    payload.push(GOVERNANCE_ACTION_UPGRADE_GUARDIAN_SET);
+    payload.extend_from_slice(&0u16.to_be_bytes());
+    payload.extend_from_slice(&new_set.index.to_be_bytes());
+    payload.push(new_set.len() as u8);
+    for key in new_set.keys() {
+        payload.extend_from_slice(&key);
+    }
+
+    VaaBody {
+        emitter_chain: GOVERNANCE_CHAIN,
+        emitter_address: GOVERNANCE_EMITTER,
+        sequence,
+        payload,
+        ..Default::default()
+    }
+}
+
+/// Token Bridge governance module, AKA `"TokenBridge"` left-padded to 32
+/// bytes.
+pub const GOVERNANCE_MODULE_TOKEN_BRIDGE: [u8; 32] = {
+    let name = b"TokenBridge";
+    let mut module = [0u8; 32];
+    let mut i = 0;
+    while i < name.len() {
+        module[32 - name.len() + i] = name[i];
+        i += 1;
+    }
+    module
+};
+
+/// Governance action that registers a foreign Token Bridge.
+pub const GOVERNANCE_ACTION_REGISTER_CHAIN: u8 = 1;
+
+/// Governance message registering the Token Bridge at `emitter_address` on
+/// `emitter_chain`.
+pub fn register_chain(sequence: u64, emitter_chain: u16, emitter_address: [u8; 32]) -> VaaBody {
+    let mut payload = Vec::with_capacity(32 + 1 + 2 + 2 + 32);
+    payload.extend_from_slice(&GOVERNANCE_MODULE_TOKEN_BRIDGE);
+    payload.push(GOVERNANCE_ACTION_REGISTER_CHAIN);
+    payload.extend_from_slice(&0u16.to_be_bytes());
+    payload.extend_from_slice(&emitter_chain.to_be_bytes());
+    payload.extend_from_slice(&emitter_address);
+
+    VaaBody {
+        emitter_chain: GOVERNANCE_CHAIN,
+        emitter_address: GOVERNANCE_EMITTER,
+        sequence,
+        payload,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,293 @@
+use anchor_lang::prelude::*;
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    transaction::TransactionError,
+};
+use wormhole_anchor_sdk::wormhole::{
+    posted_vaa_address, PostedVaa, PostedVaaData, SignatureSetData, Vaa,
+};
+use wormhole_test_guardian::{
+    CoreBridgeError, GuardianSet, GuardianSetData, LocalWormhole, VaaBody,
+};
+
+async fn start(wormhole: &LocalWormhole) -> ProgramTestContext {
+    let mut program_test = ProgramTest::default();
+    LocalWormhole::add_program(&mut program_test);
+
+    let mut context = program_test.start_with_context().await;
+    wormhole.initialize(&mut context).await.unwrap();
+    context
+}
+
+fn message(sequence: u64) -> VaaBody {
+    VaaBody::new(2, [0xab; 32], sequence, &b"cat payload".to_vec())
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+fn assert_core_error(
+    result: std::result::Result<Pubkey, BanksClientError>,
+    expected: CoreBridgeError,
+) {
+    match result.unwrap_err() {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected as u32),
+        err => panic!("unexpected error: {err:?}"),
+    }
+}
+
+async fn posted(context: &mut ProgramTestContext, address: Pubkey) -> PostedVaaData {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("VAA was not posted");
+    PostedVaaData::deserialize(&mut &account.data[..]).unwrap()
+}
+
+async fn signature_set_data(context: &mut ProgramTestContext, address: Pubkey) -> SignatureSetData {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("signature set does not exist");
+    SignatureSetData::deserialize(&mut &account.data[..]).unwrap()
+}
+
+#[tokio::test]
+async fn posts_vaa_signed_by_every_guardian() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 19));
+    let mut context = start(&wormhole).await;
+
+    let body = message(7);
+    let address = wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    let posted = posted(&mut context, address).await;
+    assert_eq!(posted.emitter_chain(), 2);
+    assert_eq!(*posted.emitter_address(), [0xab; 32]);
+    assert_eq!(posted.sequence(), 7);
+    assert_eq!(posted.timestamp(), 1_700_000_000);
+    assert_eq!(posted.finality(), 1);
+    assert_eq!(posted.payload, body.payload);
+}
+
+#[tokio::test]
+async fn signed_vaa_round_trips_and_hashes_like_the_core_bridge() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 19));
+    let mut context = start(&wormhole).await;
+
+    let body = message(3).with_nonce(42);
+    let vaa = wormhole.guardian_set.sign_vaa(&body, &[0, 4, 18]);
+    let bytes = vaa.to_bytes();
+    assert_eq!(Vaa::parse(&bytes).unwrap(), vaa);
+    assert!(Vaa::parse(&bytes[..bytes.len() - body.payload.len() - 1]).is_err());
+
+    let address = wormhole.post_vaa(&mut context, &body).await.unwrap();
+    assert_eq!(posted_vaa_address(&vaa.body), address);
+    let posted = posted(&mut context, address).await;
+    assert_eq!(posted.body_hash(), vaa.body_hash());
+    assert_eq!(
+        VaaBody::from_posted(&posted.meta, &posted.payload),
+        vaa.body
+    );
+}
+
+#[tokio::test]
+async fn posted_vaa_rejects_malformed_accounts() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 19));
+    let mut context = start(&wormhole).await;
+
+    let address = wormhole.post_vaa(&mut context, &message(1)).await.unwrap();
+    let data = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let posted = PostedVaa::<Vec<u8>>::deserialize(&mut &data[..]).unwrap();
+    assert_eq!(posted.data(), b"cat payload");
+    assert_eq!(
+        PostedVaa::<Vec<u8>>::try_payload(&data).unwrap(),
+        b"cat payload"
+    );
+
+    let error = |data: &[u8]| {
+        PostedVaa::<Vec<u8>>::deserialize(&mut &data[..])
+            .err()
+            .expect("malformed account was accepted")
+            .to_string()
+    };
+
+    let mut message = data.clone();
+    message[..3].copy_from_slice(b"msg");
+    assert!(error(&message).contains("discriminator"));
+    assert!(error(&data[..50]).contains("too short"));
+    assert!(error(&data[..data.len() - 1]).contains("payload size"));
+    assert!(error(&[&data[..], &[0]].concat()).contains("payload size"));
+
+    // Decoding must consume the whole payload.
+    let trailing = PostedVaa::<u32>::try_payload(&data).unwrap_err();
+    assert!(trailing.to_string().contains("trailing bytes"));
+    assert!(PostedVaa::<u32>::deserialize(&mut &data[..]).is_err());
+}
+
+#[tokio::test]
+async fn posts_vaa_at_exact_quorum() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 19));
+    let mut context = start(&wormhole).await;
+
+    let signers = (19 - wormhole.guardian_set.quorum()..19).collect::<Vec<_>>();
+    let result = wormhole
+        .post_vaa_with_signers(&mut context, &wormhole.guardian_set, &message(1), &signers)
+        .await;
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn rejects_vaa_without_quorum() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 19));
+    let mut context = start(&wormhole).await;
+
+    let signers = (0..wormhole.guardian_set.quorum() - 1).collect::<Vec<_>>();
+    let result = wormhole
+        .post_vaa_with_signers(&mut context, &wormhole.guardian_set, &message(1), &signers)
+        .await;
+    assert_core_error(result, CoreBridgeError::NoQuorum);
+}
+
+#[tokio::test]
+async fn signature_set_reaches_quorum_of_its_guardian_set() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 19));
+    let mut context = start(&wormhole).await;
+    let guardian_set = wormhole.guardian_set_data(&mut context, 0).await;
+    assert_eq!(guardian_set.quorum(), wormhole.guardian_set.quorum());
+
+    let body = message(1);
+    let signature_set = Keypair::new();
+    let quorum = guardian_set.quorum();
+    wormhole
+        .verify_signatures(
+            &mut context,
+            &wormhole.guardian_set,
+            &body,
+            &(0..quorum - 1).collect::<Vec<_>>(),
+            &signature_set,
+        )
+        .await
+        .unwrap();
+    let signatures = signature_set_data(&mut context, signature_set.pubkey()).await;
+    assert_eq!(signatures.num_verified(), quorum - 1);
+    assert!(!guardian_set.has_quorum(&signatures));
+
+    wormhole
+        .verify_signatures(
+            &mut context,
+            &wormhole.guardian_set,
+            &body,
+            &[quorum - 1],
+            &signature_set,
+        )
+        .await
+        .unwrap();
+    let signatures = signature_set_data(&mut context, signature_set.pubkey()).await;
+    assert!(guardian_set.has_quorum(&signatures));
+    assert!(!guardian_set.has_signers(&signatures, 19));
+
+    // Signatures only count for the set that produced them.
+    let next_set = GuardianSetData {
+        index: 1,
+        ..guardian_set
+    };
+    assert!(!next_set.has_quorum(&signatures));
+}
+
+#[tokio::test]
+async fn rejects_signatures_from_unknown_guardians() {
+    let wormhole = LocalWormhole::new(GuardianSet::new(0, 5));
+    let mut context = start(&wormhole).await;
+
+    // Same index and size as the current set, but different keys.
+    let impostors = GuardianSet {
+        index: 0,
+        guardians: GuardianSet::new(9, 5).guardians,
+    };
+    let result = wormhole
+        .post_vaa_with_signers(&mut context, &impostors, &message(1), &impostors.all())
+        .await;
+    assert_core_error(result, CoreBridgeError::InvalidGuardianKey);
+}
+
+#[tokio::test]
+async fn rotates_guardian_set() {
+    let mut wormhole =
+        LocalWormhole::new(GuardianSet::new(0, 13)).with_guardian_set_expiration_time(600);
+    let mut context = start(&wormhole).await;
+
+    let old_set = wormhole
+        .upgrade_guardian_set(&mut context, GuardianSet::new(1, 19))
+        .await
+        .unwrap();
+    assert_eq!(
+        wormhole.bridge_data(&mut context).await.guardian_set_index,
+        1
+    );
+    assert_eq!(
+        wormhole.guardian_set_data(&mut context, 1).await.keys,
+        wormhole.guardian_set.keys()
+    );
+    assert_ne!(
+        wormhole
+            .guardian_set_data(&mut context, 0)
+            .await
+            .expiration_time,
+        0
+    );
+
+    // New set signs from now on.
+    assert!(wormhole.post_vaa(&mut context, &message(1)).await.is_ok());
+
+    // Old set is still honoured during the grace period...
+    let result = wormhole
+        .post_vaa_with_signers(&mut context, &old_set, &message(2), &old_set.all())
+        .await;
+    assert!(result.is_ok());
+
+    // ...but not after it.
+    let mut clock: Clock = context.banks_client.get_sysvar().await.unwrap();
+    clock.unix_timestamp += 601;
+    context.set_sysvar(&clock);
+
+    let result = wormhole
+        .post_vaa_with_signers(&mut context, &old_set, &message(3), &old_set.all())
+        .await;
+    assert_core_error(result, CoreBridgeError::GuardianSetExpired);
+}
+
+#[tokio::test]
+async fn rejects_guardian_set_upgrade_replay() {
+    let mut wormhole = LocalWormhole::new(GuardianSet::new(0, 1));
+    let mut context = start(&wormhole).await;
+
+    let governance = wormhole_test_guardian::guardian_set_upgrade(0, &GuardianSet::new(1, 1));
+    wormhole
+        .upgrade_guardian_set(&mut context, GuardianSet::new(1, 1))
+        .await
+        .unwrap();
+
+    let ix = wormhole_test_guardian::core_bridge::upgrade_guardian_set_ix(
+        &context.payer.pubkey(),
+        &governance,
+        0,
+    );
+    let result = wormhole_test_guardian::process(&mut context, &[ix], &[])
+        .await
+        .map(|_| Pubkey::default());
+    assert!(result.is_err());
+}
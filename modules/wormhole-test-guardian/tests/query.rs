@@ -0,0 +1,80 @@
+use wormhole_anchor_sdk::wormhole::{
+    self, EthCallData, EthCallQuery, EthCallQueryResponse, QueryResponse, QUERY_RESPONSE_PREFIX,
+};
+use wormhole_test_guardian::{eth_call_response, GuardianSet, GuardianSetData};
+
+const TOTAL_SUPPLY: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+
+fn total_supply_response(block_time: u64) -> QueryResponse {
+    let mut supply = vec![0u8; 32];
+    supply[24..].copy_from_slice(&1_000u64.to_be_bytes());
+    eth_call_response(
+        2,
+        42,
+        block_time,
+        &[(
+            EthCallData {
+                to: [0xee; 20],
+                data: TOTAL_SUPPLY.to_vec(),
+            },
+            supply,
+        )],
+    )
+}
+
+#[test]
+fn eth_call_response_round_trips() {
+    let response = total_supply_response(1_700_000_000);
+    let bytes = response.to_bytes();
+    assert_eq!(QueryResponse::parse(&bytes).unwrap(), response);
+
+    let (query, result) = response.pairs().next().unwrap();
+    let query = EthCallQuery::parse(&query.query).unwrap();
+    assert_eq!(query.block_id, b"0x2a");
+    assert_eq!(query.calls[0].to, [0xee; 20]);
+    assert_eq!(query.calls[0].data, TOTAL_SUPPLY);
+
+    let result = EthCallQueryResponse::parse(&result.response).unwrap();
+    assert_eq!(result.block_number, 42);
+    assert_eq!(result.block_time_seconds(), 1_700_000_000);
+    assert_eq!(result.results[0][24..], 1_000u64.to_be_bytes());
+
+    // Truncated or padded responses are rejected.
+    assert!(QueryResponse::parse(&bytes[..bytes.len() - 1]).is_err());
+    assert!(QueryResponse::parse(&[&bytes[..], &[0]].concat()).is_err());
+}
+
+#[test]
+fn responses_must_match_the_request() {
+    let mut response = total_supply_response(1_700_000_000);
+    response.responses[0].chain_id = 4;
+    assert!(QueryResponse::parse(&response.to_bytes()).is_err());
+
+    let mut response = total_supply_response(1_700_000_000);
+    response.responses.clear();
+    assert!(QueryResponse::parse(&response.to_bytes()).is_err());
+}
+
+#[test]
+fn signs_the_prefixed_response_hash() {
+    let guardian_set = GuardianSet::new(0, 4);
+    let bytes = total_supply_response(1_700_000_000).to_bytes();
+
+    let message = wormhole::query_response_message(&wormhole::query_response_hash(&bytes));
+    assert_eq!(
+        message[..QUERY_RESPONSE_PREFIX.len()],
+        QUERY_RESPONSE_PREFIX[..]
+    );
+
+    let ix = guardian_set.sign_query_response(&bytes, &[0, 2, 3], 0);
+    assert_eq!(ix.data[0], 3);
+    assert!(ix.data.ends_with(&message));
+
+    let data = GuardianSetData {
+        index: 0,
+        keys: guardian_set.keys(),
+        ..Default::default()
+    };
+    assert_eq!(data.key_index(&guardian_set.keys()[2]), Some(2));
+    assert_eq!(data.key_index(&[0xff; 20]), None);
+}
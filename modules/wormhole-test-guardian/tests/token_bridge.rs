@@ -0,0 +1,181 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::{self, solana_program::program_pack::Pack};
+use solana_program_test::{BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    system_instruction,
+    transaction::TransactionError,
+};
+use wormhole_anchor_sdk::{
+    token_bridge::{self, AssetMeta, WrappedMeta, WrappedMint},
+    wormhole::{PostedVaaData, CHAIN_ID_SOLANA},
+};
+use wormhole_test_guardian::{
+    process, GuardianSet, LocalTokenBridge, LocalWormhole, TokenBridgeError, VaaBody,
+};
+
+const FOREIGN_CHAIN: u16 = 2;
+const FOREIGN_TOKEN_BRIDGE: [u8; 32] = [0xb7; 32];
+const FOREIGN_TOKEN: [u8; 32] = [0xcc; 32];
+
+async fn start(wormhole: &mut LocalWormhole) -> ProgramTestContext {
+    let mut program_test = ProgramTest::default();
+    LocalWormhole::add_program(&mut program_test);
+    LocalTokenBridge::add_program(&mut program_test);
+
+    let mut context = program_test.start_with_context().await;
+    wormhole.initialize(&mut context).await.unwrap();
+    LocalTokenBridge::initialize(&mut context).await.unwrap();
+    LocalTokenBridge::register_chain(&mut context, wormhole, FOREIGN_CHAIN, FOREIGN_TOKEN_BRIDGE)
+        .await
+        .unwrap();
+    context
+}
+
+async fn create_mint(context: &mut ProgramTestContext, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::id(),
+        ),
+        spl_token::instruction::initialize_mint2(
+            &spl_token::id(),
+            &mint.pubkey(),
+            &payer,
+            None,
+            decimals,
+        )
+        .unwrap(),
+    ];
+    process(context, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+fn attestation(sequence: u64, emitter_address: [u8; 32]) -> VaaBody {
+    let mut symbol = [0u8; 32];
+    symbol[..3].copy_from_slice(b"CAT");
+    let mut name = [0u8; 32];
+    name[..9].copy_from_slice(b"CAT Token");
+
+    VaaBody::new(
+        FOREIGN_CHAIN,
+        emitter_address,
+        sequence,
+        &AssetMeta {
+            token_address: FOREIGN_TOKEN,
+            token_chain: FOREIGN_CHAIN,
+            decimals: 18,
+            symbol,
+            name,
+        },
+    )
+}
+
+async fn account_data(context: &mut ProgramTestContext, address: Pubkey) -> Vec<u8> {
+    context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account does not exist")
+        .data
+}
+
+fn assert_token_bridge_error<T: std::fmt::Debug>(
+    result: std::result::Result<T, BanksClientError>,
+    expected: TokenBridgeError,
+) {
+    match result.unwrap_err() {
+        BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(code),
+        )) => assert_eq!(code, expected as u32),
+        err => panic!("unexpected error: {err:?}"),
+    }
+}
+
+#[tokio::test]
+async fn attests_native_mint() {
+    let mut wormhole = LocalWormhole::new(GuardianSet::new(0, 1));
+    let mut context = start(&mut wormhole).await;
+    let mint = create_mint(&mut context, 9).await;
+
+    let message = LocalTokenBridge::attest_token(&mut context, &mint)
+        .await
+        .unwrap();
+
+    let posted =
+        PostedVaaData::deserialize(&mut &account_data(&mut context, message).await[..]).unwrap();
+    assert_eq!(
+        *posted.emitter_address(),
+        token_bridge::emitter_address().to_bytes()
+    );
+    let asset_meta = AssetMeta::deserialize(&mut &posted.payload[..]).unwrap();
+    assert_eq!(asset_meta.token_address, mint.to_bytes());
+    assert_eq!(asset_meta.token_chain, CHAIN_ID_SOLANA);
+    assert_eq!(asset_meta.decimals, 9);
+}
+
+#[tokio::test]
+async fn creates_wrapped_asset_once_from_registered_token_bridge() {
+    let mut wormhole = LocalWormhole::new(GuardianSet::new(0, 1));
+    let mut context = start(&mut wormhole).await;
+
+    let body = attestation(0, FOREIGN_TOKEN_BRIDGE);
+    let mint = LocalTokenBridge::create_wrapped(&mut context, &wormhole, &body)
+        .await
+        .unwrap();
+    assert_eq!(mint, WrappedMint::address(FOREIGN_CHAIN, &FOREIGN_TOKEN));
+
+    // Wrapped assets have at most 8 decimals and are minted by the Token Bridge.
+    let mint_data =
+        spl_token::state::Mint::unpack(&account_data(&mut context, mint).await).unwrap();
+    assert_eq!(mint_data.decimals, 8);
+    assert_eq!(
+        mint_data.mint_authority,
+        Some(token_bridge::mint_authority_address()).into()
+    );
+
+    let meta = WrappedMeta::deserialize(
+        &mut &account_data(&mut context, WrappedMeta::address(&mint)).await[..],
+    )
+    .unwrap();
+    assert_eq!(meta.original_decimals, 18);
+    assert_eq!(meta.wrapped_mint(), mint);
+    assert!(meta.is_wrapped_asset(&mint, FOREIGN_CHAIN, &FOREIGN_TOKEN));
+    assert!(!meta.is_wrapped_asset(&mint, FOREIGN_CHAIN, &[0xdd; 32]));
+    assert!(!meta.is_wrapped_asset(&Pubkey::new_unique(), FOREIGN_CHAIN, &FOREIGN_TOKEN));
+
+    let ix =
+        wormhole_test_guardian::token_bridge::create_wrapped_ix(&context.payer.pubkey(), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_token_bridge_error(result, TokenBridgeError::AlreadyClaimed);
+
+    // Wrapped mints cannot be attested back.
+    let result = LocalTokenBridge::attest_token(&mut context, &mint).await;
+    assert_token_bridge_error(result, TokenBridgeError::WrappedAsset);
+
+    let result =
+        LocalTokenBridge::create_wrapped(&mut context, &wormhole, &attestation(0, [0x11; 32]))
+            .await;
+    assert_token_bridge_error(result, TokenBridgeError::InvalidEndpoint);
+}
+
+#[test]
+fn asset_meta_round_trips() {
+    let body = attestation(0, FOREIGN_TOKEN_BRIDGE);
+    assert_eq!(body.payload.len(), token_bridge::ASSET_META_SIZE);
+    assert_eq!(body.payload[0], token_bridge::PAYLOAD_ID_ASSET_META);
+
+    let asset_meta = AssetMeta::deserialize(&mut &body.payload[..]).unwrap();
+    assert_eq!(asset_meta.symbol(), "CAT");
+    assert_eq!(asset_meta.name(), "CAT Token");
+    assert!(AssetMeta::deserialize(&mut &body.payload[..99]).is_err());
+}
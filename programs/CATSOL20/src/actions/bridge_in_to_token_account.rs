@@ -0,0 +1,186 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::token::{mint_to, MintTo, Mint, Token, TokenAccount};
+
+use crate::{
+    actions::BridgeInParams,
+    constants::*,
+    utils_cat::*,
+    error::ErrorFactory,
+    events::Redeemed,
+    cat_struct::{CATSOLStructs, RecipientType},
+    state::{Config, ForeignEmitter, Received, ReceivedBitmap}
+};
+
+#[derive(Accounts)]
+#[instruction(params: BridgeInParams)]
+pub struct BridgeInToTokenAccount<'info> {
+    /// Owner will initialize an account that tracks his own payloads
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Token Mint. The token that is bridged in.
+    #[account(
+        mut,
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    // Token Account. The `dest_user_address` of the transfer. It can have any
+    // owner, but must exist: closed accounts fail to load.
+    #[account(
+        mut,
+        constraint = token_account.mint == token_mint.key() @ ErrorFactory::InvalidTokenAccountMint,
+        constraint = !token_account.is_frozen() @ ErrorFactory::InvalidATAAccount,
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Verified Wormhole message account. The Wormhole program verified
+    /// signatures and posted the account data here. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            ReceivedBitmap::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &ReceivedBitmap::index(posted.sequence()).to_le_bytes()[..]
+        ],
+        bump,
+        space = ReceivedBitmap::MAXIMUM_SIZE,
+        constraint = !received_bitmap.is_received(posted.sequence()) @ ErrorFactory::AlreadyReceived
+    )]
+    /// Received bitmap covering the message's sequence. The message's bit is
+    /// set on redemption, preventing replay.
+    pub received_bitmap: Box<Account<'info, ReceivedBitmap>>,
+
+    #[account(
+        seeds = [
+            Received::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &posted.sequence().to_le_bytes()[..]
+        ],
+        bump,
+        constraint = received.data_is_empty() @ ErrorFactory::AlreadyReceived
+    )]
+    /// CHECK: Received account of the message. Only messages redeemed before
+    /// the received bitmap have one, and those cannot be redeemed again.
+    pub received: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            // Emitters are registered under a u64 chain ID.
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        constraint = foreign_emitter.verify(posted.emitter_address()) @ ErrorFactory::InvalidForeignEmitter
+    )]
+    /// Foreign emitter account. The posted message's `emitter_address` must
+    /// agree with the one we have registered for this message's `emitter_chain`
+    /// (chain ID). Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+
+impl BridgeInToTokenAccount<'_> {
+    pub fn bridge_in_to_token_account(ctx: Context<BridgeInToTokenAccount>, params: BridgeInParams) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorFactory::MintingPaused);
+        let posted_message = &ctx.accounts.posted;
+        ctx.accounts.foreign_emitter.check_policy(posted_message)?;
+        require!(
+            wormhole::posted_vaa_hash(&posted_message.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        let payload = match posted_message.data() {
+            CATSOLStructs::Transfer {
+                payload,
+                recipient_type: RecipientType::TokenAccount,
+            } => payload,
+            CATSOLStructs::CrossChainPayload { .. } | CATSOLStructs::Transfer { .. } => {
+                return Err(ErrorFactory::InvalidRecipientType.into())
+            }
+            _ => return Err(ErrorFactory::InvalidMessage.into()),
+        };
+
+        let dest_chain: u64 = payload.dest_token_chain.into();
+        require!(
+            dest_chain == CONVENTIONAL_SOLANA_ID,
+            ErrorFactory::InvalidDestinationChain
+        );
+
+        // The payload names the token account itself
+        require_keys_eq!(
+            Pubkey::from(payload.dest_user_address),
+            ctx.accounts.token_account.key(),
+            ErrorFactory::MisMatchdATAAddress
+        );
+
+        // Normalize the amount by converting it back from the standard 8 decimals to the token's decimals
+        let amount_u64: u64 = payload.amount.into();
+        let decimals = ctx.accounts.token_mint.decimals;
+        let normalized_amount = denormalize_amount(amount_u64, decimals);
+
+        // Mint the tokens
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.token_account.to_account_info(),
+            authority: ctx.accounts.token_mint.to_account_info(),
+        };
+        let bump = ctx.bumps.token_mint;
+
+        let cpi_signer_seeds = &[
+            b"spl_cat_token".as_ref(),
+            &[bump],
+        ];
+        let cpi_signer = &[&cpi_signer_seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, cpi_signer);
+
+        mint_to(cpi_ctx, normalized_amount)?;
+
+        // Mark the message as redeemed.
+        ctx.accounts.received_bitmap.mark_received(
+            ctx.accounts.posted.emitter_chain(),
+            ctx.accounts.posted.sequence(),
+        )?;
+        emit!(Redeemed {
+            emitter_chain: posted_message.emitter_chain(),
+            sequence: posted_message.sequence(),
+            vaa_hash: params.vaa_hash,
+            amount: normalized_amount,
+            recipient: ctx.accounts.token_account.key(),
+        });
+
+        // Done
+        Ok(())
+    }
+}
@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
 use wormhole_anchor_sdk::wormhole;
 use anchor_spl::{
     associated_token::AssociatedToken,
@@ -7,16 +6,18 @@ use anchor_spl::{
 
 };
 use crate::{
+    chains::{emitter_chain_key, wormhole_chain_id},
     constants::*,
     utils_cat::*,
     error::ErrorFactory,
     cat_struct::{CATSOLStructs, CrossChainStruct, U256},
-    state::{Config, ForeignEmitter, WormholeEmitter}
+    state::{Config, ForeignEmitter, OutboundStatus, OutboundTransfer, WormholeEmitter}
 };
 
 #[derive(Clone, AnchorDeserialize, AnchorSerialize)]
 pub struct BridgeOutParams {
     pub amount: u64,
+    /// CAT chain ID of the destination, see [`crate::CHAINS`].
     pub recipient_chain: u64,
     pub recipient: [u8; 32],
     pub recipient_contract: [u8; 32],
@@ -110,15 +111,29 @@ pub struct BridgeOut<'info> {
     /// account be mutable.
     pub wormhole_message: UncheckedAccount<'info>,
 
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            OutboundTransfer::SEED_PREFIX,
+            &wormhole_sequence.value().to_le_bytes()[..]
+        ],
+        bump,
+        space = OutboundTransfer::MAXIMUM_SIZE
+    )]
+    /// Outbound transfer account of the message's sequence.
+    pub outbound_transfer: Box<Account<'info, OutboundTransfer>>,
+
     #[account(
         seeds = [
             ForeignEmitter::SEED_PREFIX,
-            &params.recipient_chain.to_le_bytes()[..]
+            &emitter_chain_key(params.recipient_chain)[..]
         ],
         bump,
-        constraint = foreign_emitter.chain == params.recipient_chain
+        constraint = wormhole_chain_id(params.recipient_chain).map(u64::from) == Some(foreign_emitter.chain)
     )]
-    /// Foreign Emitter account should exist for the recipient chain. Read-only.
+    /// Foreign Emitter account should exist for the recipient chain, under
+    /// its Wormhole chain ID. Read-only.
     /// We're just checking if the account exists and is initialized.
     pub foreign_emitter: Account<'info, ForeignEmitter>,
 
@@ -133,19 +148,47 @@ pub struct BridgeOut<'info> {
 }
 
 impl BridgeOut<'_> {
-    pub fn bridge_out(ctx: Context<BridgeOut>, params: BridgeOutParams ) -> Result<()> {
+    pub fn bridge_out(ctx: Context<BridgeOut>, params: BridgeOutParams) -> Result<()> {
+        Self::send(ctx, params, None)
+    }
+
+    /// Same as [`BridgeOut::bridge_out`], but `data` is delivered to the
+    /// recipient program along with the tokens.
+    pub fn bridge_out_with_payload(
+        ctx: Context<BridgeOut>,
+        params: BridgeOutParams,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        Self::send(ctx, params, Some(data))
+    }
+
+    /// Lamports `bridge_out` costs the owner on top of the transaction fee:
+    /// the Wormhole fee and the rent of the message and outbound transfer
+    /// accounts. With `data`, the cost of `bridge_out_with_payload`. Add
+    /// [`wormhole::MessageCost::with_new_token_account`] if the transaction
+    /// also creates the owner's ATA.
+    pub fn estimate_cost(
+        bridge: &wormhole::BridgeData,
+        fee_collector_lamports: u64,
+        rent: &Rent,
+        data: Option<&[u8]>,
+    ) -> wormhole::MessageCost {
+        let payload_size = match data {
+            Some(data) => 1 + CrossChainStruct::LEN + 4 + data.len(),
+            None => CrossChainStruct::LEN,
+        };
+        wormhole::MessageCost::estimate(bridge, fee_collector_lamports, rent, payload_size)
+            .with_new_account(rent, OutboundTransfer::MAXIMUM_SIZE)
+    }
+
+    fn send(ctx: Context<BridgeOut>, params: BridgeOutParams, data: Option<Vec<u8>>) -> Result<()> {
         // Pay the Fee
-        let fee = ctx.accounts.wormhole_bridge.fee();
-        if fee > 0 {
-            solana_program::program::invoke(
-                &solana_program::system_instruction::transfer(
-                    &ctx.accounts.owner.key(),
-                    &ctx.accounts.wormhole_fee_collector.key(),
-                    fee,
-                ),
-                &ctx.accounts.to_account_infos(),
-            )?;
-        }
+        wormhole::pay_message_fee(
+            &ctx.accounts.wormhole_bridge,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.wormhole_fee_collector.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
 
         // Burn the tokens
         let cpi_program = ctx.accounts.token_program.to_account_info();
@@ -184,10 +227,24 @@ impl BridgeOut<'_> {
         };
 
         // Serialize the payload
-        let cat_sol_struct = CATSOLStructs::CrossChainPayload { payload };
+        let cat_sol_struct = match data {
+            Some(data) => CATSOLStructs::TransferWithPayload { payload, data },
+            None => CATSOLStructs::CrossChainPayload { payload },
+        };
         let mut encoded_payload: Vec<u8> = Vec::new();
         cat_sol_struct.serialize(&mut encoded_payload)?;
 
+        // Record the transfer under its sequence.
+        let outbound = &mut ctx.accounts.outbound_transfer;
+        outbound.sender = ctx.accounts.ata_authority.key();
+        outbound.status = OutboundStatus::Pending;
+        outbound.sequence = ctx.accounts.wormhole_sequence.value();
+        outbound.amount = params.amount;
+        outbound.recipient_chain = params.recipient_chain;
+        outbound.recipient = params.recipient;
+        outbound.timestamp = ctx.accounts.clock.unix_timestamp;
+        outbound.payer = ctx.accounts.owner.key();
+
         let wormhole_emitter = &ctx.accounts.wormhole_emitter;
         let config = &ctx.accounts.config;
 
@@ -216,7 +273,9 @@ impl BridgeOut<'_> {
             ),
             config.batch_id,
             encoded_payload,
-            config.finality.into(),
+            config
+                .message_finality(&ctx.accounts.foreign_emitter, params.amount)
+                .into(),
         )?;
 
         // Done.
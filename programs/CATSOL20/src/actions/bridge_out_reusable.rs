@@ -0,0 +1,238 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+
+use crate::{
+    chains::{emitter_chain_key, wormhole_chain_id},
+    constants::*,
+    utils_cat::*,
+    error::ErrorFactory,
+    cat_struct::{CATSOLStructs, CrossChainStruct, U256},
+    state::{Config, ForeignEmitter, OutboundStatus, OutboundTransfer, WormholeEmitter},
+    BridgeOutParams
+};
+
+#[derive(Accounts)]
+#[instruction(params: BridgeOutParams)]
+/// Context used to bridge out through the owner's reusable message account.
+/// Same as [`crate::BridgeOut`], without a new message account.
+pub struct BridgeOutReusable<'info> {
+    #[account(mut)]
+    /// Owner will pay Wormhole fee to post a message, and the reusable message
+    /// account on first use.
+    pub owner: Signer<'info>,
+
+    /// ATA Authority. The authority of the ATA that holds the bridged tokens.
+    /// CHECK: This is the authority of the ATA
+    pub ata_authority: UncheckedAccount<'info>,
+
+    /// Token Mint. The token that is bridged out.
+    #[account(
+        mut,
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    // Token Account. Its an Associated Token Account that holds the tokens
+    // that are bridged out.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = ata_authority,
+    )]
+    pub token_user_ata: Account<'info, TokenAccount>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Wormhole PDAs specified in the config are checked
+    /// against the Wormhole accounts in this context. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    /// Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ ErrorFactory::InvalidWormholeConfig
+    )]
+    /// Wormhole bridge data. [`wormhole::post_message_unreliable`] requires
+    /// this account be mutable.
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ ErrorFactory::InvalidWormholeFeeCollector
+    )]
+    /// Wormhole fee collector. [`wormhole::post_message_unreliable`] requires
+    /// this account be mutable.
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump,
+    )]
+    /// Program's emitter account. Read-only.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ ErrorFactory::InvalidWormholeSequence
+    )]
+    /// Emitter's sequence account. [`wormhole::post_message_unreliable`]
+    /// requires this account be mutable.
+    pub wormhole_sequence: Account<'info, wormhole::SequenceTracker>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_PREFIX_REUSABLE,
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    /// CHECK: Owner's reusable Wormhole Message. Holds the owner's previous
+    /// transfer, if any. [`wormhole::post_message_unreliable`] requires this
+    /// account be mutable.
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            OutboundTransfer::SEED_PREFIX,
+            &wormhole_sequence.value().to_le_bytes()[..]
+        ],
+        bump,
+        space = OutboundTransfer::MAXIMUM_SIZE
+    )]
+    /// Outbound transfer account of the message's sequence.
+    pub outbound_transfer: Box<Account<'info, OutboundTransfer>>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &emitter_chain_key(params.recipient_chain)[..]
+        ],
+        bump,
+        constraint = wormhole_chain_id(params.recipient_chain).map(u64::from) == Some(foreign_emitter.chain)
+    )]
+    /// Foreign Emitter account should exist for the recipient chain, under
+    /// its Wormhole chain ID. Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl BridgeOutReusable<'_> {
+    pub fn bridge_out_reusable(ctx: Context<BridgeOutReusable>, params: BridgeOutParams) -> Result<()> {
+        // The previous message is overwritten, give guardians time to observe
+        // it first.
+        let message = &ctx.accounts.wormhole_message;
+        if !message.data_is_empty() {
+            let previous = wormhole::PostedVaaData::try_deserialize(&mut &message.data.borrow()[..])?;
+            require!(
+                ctx.accounts.clock.unix_timestamp
+                    >= i64::from(previous.posted_timestamp()) + REUSABLE_MESSAGE_DELAY,
+                ErrorFactory::ReusableMessageInUse
+            );
+        }
+
+        // Pay the Fee
+        wormhole::pay_message_fee(
+            &ctx.accounts.wormhole_bridge,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.wormhole_fee_collector.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // Burn the tokens
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.token_user_ata.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        );
+        burn(cpi_ctx, params.amount)?;
+
+        // Normalize the amount to a Standard 8 decimals
+        let decimals = ctx.accounts.token_mint.decimals;
+        let foreign_amount = normalize_amount(params.amount, decimals);
+
+        // Plain transfers all have the same size, so the account can be
+        // posted to again.
+        let payload = CrossChainStruct {
+            amount: U256::from(foreign_amount),
+            token_decimals: decimals,
+            source_token_address: ctx.accounts.wormhole_emitter.key().to_bytes(),
+            source_user_address: ctx.accounts.ata_authority.key().to_bytes(),
+            source_token_chain: U256::from(CONVENTIONAL_SOLANA_ID), // Solana's Chain ID
+            dest_token_address: params.recipient_contract,
+            dest_user_address: params.recipient,
+            dest_token_chain: U256::from(params.recipient_chain)
+        };
+        let mut encoded_payload: Vec<u8> = Vec::new();
+        CATSOLStructs::CrossChainPayload { payload }.serialize(&mut encoded_payload)?;
+
+        // Record the transfer under its sequence.
+        let outbound = &mut ctx.accounts.outbound_transfer;
+        outbound.sender = ctx.accounts.ata_authority.key();
+        outbound.status = OutboundStatus::Pending;
+        outbound.sequence = ctx.accounts.wormhole_sequence.value();
+        outbound.amount = params.amount;
+        outbound.recipient_chain = params.recipient_chain;
+        outbound.recipient = params.recipient;
+        outbound.timestamp = ctx.accounts.clock.unix_timestamp;
+        outbound.payer = ctx.accounts.owner.key();
+
+        let wormhole_emitter = &ctx.accounts.wormhole_emitter;
+        let config = &ctx.accounts.config;
+
+        wormhole::post_message_unreliable(
+            CpiContext::new_with_signer(
+                ctx.accounts.wormhole_program.to_account_info(),
+                wormhole::PostMessage {
+                    config: ctx.accounts.wormhole_bridge.to_account_info(),
+                    message: ctx.accounts.wormhole_message.to_account_info(),
+                    emitter: wormhole_emitter.to_account_info(),
+                    sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+                    payer: ctx.accounts.owner.to_account_info(),
+                    fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[
+                    &[
+                        SEED_PREFIX_REUSABLE,
+                        ctx.accounts.owner.key().as_ref(),
+                        &[ctx.bumps.wormhole_message],
+                    ],
+                    &[wormhole::SEED_PREFIX_EMITTER, &[wormhole_emitter.bump]],
+                ],
+            ),
+            config.batch_id,
+            encoded_payload,
+            config
+                .message_finality(&ctx.accounts.foreign_emitter, params.amount)
+                .into(),
+        )?;
+
+        // Done.
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorFactory,
+    state::{Config, OutboundStatus, OutboundTransfer},
+};
+
+#[derive(Accounts)]
+pub struct CloseOutboundTransfer<'info> {
+    #[account(
+        constraint = signer.key() == outbound_transfer.payer
+            || signer.key() == config.owner @ ErrorFactory::PayerOrOwnerOnly
+    )]
+    /// Payer of the outbound transfer account, or the owner of the program.
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Receives the rent of the closed account. Must be the account
+    /// that paid for it when sending the transfer.
+    #[account(
+        mut,
+        constraint = rent_recipient.key() == outbound_transfer.payer @ ErrorFactory::InvalidRentRecipient
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = rent_recipient
+    )]
+    /// Outbound transfer account to close.
+    pub outbound_transfer: Account<'info, OutboundTransfer>,
+}
+
+
+impl CloseOutboundTransfer<'_> {
+    pub fn close_outbound_transfer(ctx: Context<CloseOutboundTransfer>) -> Result<()> {
+        // A refunded transfer is settled. A pending one may still come back
+        // as a refund, which is then no longer checked against the record,
+        // so it is kept for the retention period.
+        let outbound = &ctx.accounts.outbound_transfer;
+        let age = Clock::get()?.unix_timestamp - outbound.timestamp;
+        require!(
+            outbound.status == OutboundStatus::Refunded
+                || age >= OutboundTransfer::RETENTION_PERIOD,
+            ErrorFactory::OutboundTransferNotSettled
+        );
+
+        // Done. Anchor closes `outbound_transfer` on exit.
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    cat_struct::CATSOLStructs,
+    error::ErrorFactory,
+    state::{Config, Received},
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CloseReceivedParams {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: CloseReceivedParams)]
+pub struct CloseReceived<'info> {
+    #[account(
+        constraint = signer.key() == received.payer
+            || signer.key() == config.owner @ ErrorFactory::PayerOrOwnerOnly
+    )]
+    /// Payer of the received account, or the owner of the program.
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Receives the rent of the closed account. Must be the account
+    /// that paid for it when redeeming the message.
+    #[account(
+        mut,
+        constraint = rent_recipient.key() == received.payer @ ErrorFactory::InvalidRentRecipient
+    )]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        seeds = [
+            Received::SEED_PREFIX,
+            &params.emitter_chain.to_le_bytes()[..],
+            &params.sequence.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// Received account to close.
+    pub received: Account<'info, Received>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &received.wormhole_message_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// The redeemed Wormhole message. Its timestamp starts the retention
+    /// period. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+}
+
+
+impl CloseReceived<'_> {
+    pub fn close_received(ctx: Context<CloseReceived>, _params: &CloseReceivedParams) -> Result<()> {
+        let age = Clock::get()?.unix_timestamp - ctx.accounts.posted.timestamp() as i64;
+        require!(
+            age >= Received::RETENTION_PERIOD,
+            ErrorFactory::RetentionPeriodNotOver
+        );
+
+        // Done. The message's bit is already set in its received bitmap, and
+        // Anchor closes `received` on exit.
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     state::{Config, WormholeEmitter}
 };
 
-use anchor_lang::solana_program::{self, program::invoke_signed};
+use anchor_lang::solana_program::program::invoke_signed;
 use mpl_token_metadata::instruction::create_metadata_accounts_v3;
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -190,6 +190,9 @@ impl Initialize<'_> {
         // so this value is stored as u8.
         config.finality = wormhole::Finality::Confirmed as u8;
 
+        // Zero means no amount forces Finalized.
+        config.finalized_threshold = 0;
+
 
         // Create Metadata for the tokens.
         {
@@ -241,27 +244,18 @@ impl Initialize<'_> {
         // by posting a message to the Wormhole program.
         {
             // Pay the Fee
-            let fee = ctx.accounts.wormhole_bridge.fee();
-            if fee > 0 {
-                solana_program::program::invoke(
-                    &solana_program::system_instruction::transfer(
-                        &ctx.accounts.owner.key(),
-                        &ctx.accounts.wormhole_fee_collector.key(),
-                        fee,
-                    ),
-                    &ctx.accounts.to_account_infos(),
-                )?;
-            }
+            wormhole::pay_message_fee(
+                &ctx.accounts.wormhole_bridge,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.wormhole_fee_collector.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
             let wormhole_emitter = &ctx.accounts.wormhole_emitter;
             let config = &ctx.accounts.config;
 
             let mut payload: Vec<u8> = Vec::new();
-            CATSOLStructs::serialize(
-                &&CATSOLStructs::Alive {
-                    program_id: *ctx.program_id,
-                },
-                &mut payload,
-            )?;
+            CATSOLStructs::hello(&wormhole_emitter.key(), ctx.accounts.token_mint.decimals)
+                .serialize(&mut payload)?;
 
             wormhole::post_message(
                 CpiContext::new_with_signer(
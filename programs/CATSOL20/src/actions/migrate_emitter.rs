@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    chains::is_emitter_chain,
+    error::ErrorFactory,
+    state::{Config, ForeignEmitter},
+    utils_cat::*
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateEmitterParams {
+    /// Chain the emitter is registered under.
+    pub chain: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: MigrateEmitterParams)]
+/// Context used to grow a [`ForeignEmitter`] account created by an earlier
+/// version of the program to the current layout. Fields added since then are
+/// zeroed. The emitter's chain is listed in the config, which earlier versions
+/// did not do.
+pub struct MigrateEmitter<'info> {
+    #[account(mut)]
+    /// Owner of the program set in the [`Config`] account. Pays for the
+    /// larger account.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    /// Config account. Must be migrated first.
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &params.chain.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// CHECK: Foreign emitter account. It only deserializes once grown, which
+    /// the handler checks.
+    pub foreign_emitter: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateEmitter<'_> {
+    pub fn migrate_emitter(ctx: Context<MigrateEmitter>, params: &MigrateEmitterParams) -> Result<()> {
+        let foreign_emitter = ctx.accounts.foreign_emitter.to_account_info();
+        grow_account(
+            &foreign_emitter,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ForeignEmitter::MAXIMUM_SIZE,
+        )?;
+
+        let data = foreign_emitter.try_borrow_data()?;
+        ForeignEmitter::try_deserialize(&mut &data[..])?;
+
+        // Emitters registered under a CAT chain ID are not listed. They have
+        // to be registered again.
+        if is_emitter_chain(params.chain) {
+            ctx.accounts.config.add_emitter_chain(params.chain)?;
+        }
+
+        // Done
+        Ok(())
+    }
+}
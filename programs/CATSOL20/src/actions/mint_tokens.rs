@@ -16,6 +16,7 @@ pub struct MintTokens<'info> {
     pub owner: Signer<'info>,
 
     #[account(
+        mut,
         has_one = owner @ ErrorFactory::OwnerOnly,
         seeds = [Config::SEED_PREFIX],
         bump
@@ -51,6 +52,7 @@ impl MintTokens<'_> {
 
     pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
         let config = &mut ctx.accounts.config;
+        require!(!config.paused, ErrorFactory::MintingPaused);
 
         // Check if the amount doesn't exceed the max supply
         if amount + config.minted_supply > config.max_supply {
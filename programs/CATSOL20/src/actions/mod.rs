@@ -13,5 +13,65 @@ pub use register_emitter::*;
 pub mod bridge_out;
 pub use bridge_out::*;
 
+pub mod bridge_out_reusable;
+pub use bridge_out_reusable::*;
+
 pub mod bridge_in;
-pub use bridge_in::*;
\ No newline at end of file
+pub use bridge_in::*;
+
+pub mod bridge_in_with_payload;
+pub use bridge_in_with_payload::*;
+
+pub mod bridge_in_to_token_account;
+pub use bridge_in_to_token_account::*;
+
+pub mod refund;
+pub use refund::*;
+
+pub mod close_received;
+pub use close_received::*;
+
+pub mod close_legacy_received;
+pub use close_legacy_received::*;
+
+pub mod close_outbound_transfer;
+pub use close_outbound_transfer::*;
+
+pub mod migrate_config;
+pub use migrate_config::*;
+
+pub mod migrate_emitter;
+pub use migrate_emitter::*;
+
+pub mod set_message_params;
+pub use set_message_params::*;
+
+pub mod set_emitter_policy;
+pub use set_emitter_policy::*;
+
+pub mod update_metadata;
+pub use update_metadata::*;
+
+pub mod attest_token;
+pub use attest_token::*;
+
+pub mod receive_attestation;
+pub use receive_attestation::*;
+
+pub mod approve_emitter;
+pub use approve_emitter::*;
+
+pub mod post_hello;
+pub use post_hello::*;
+
+pub mod receive_hello;
+pub use receive_hello::*;
+
+pub mod verify_query_signatures;
+pub use verify_query_signatures::*;
+
+pub mod record_remote_supply;
+pub use record_remote_supply::*;
+
+pub mod reconcile_supply;
+pub use reconcile_supply::*;
\ No newline at end of file
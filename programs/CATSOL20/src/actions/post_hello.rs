@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::token::Mint;
+
+use crate::{
+    constants::*,
+    error::ErrorFactory,
+    cat_struct::CATSOLStructs,
+    state::{Config, WormholeEmitter}
+};
+
+#[derive(Accounts)]
+/// Context used to post this deployment's `Hello` again, e.g. for a chain
+/// deployed after `initialize`.
+pub struct PostHello<'info> {
+    #[account(mut)]
+    /// Owner of the program. Pays the Wormhole fee and the message account.
+    pub owner: Signer<'info>,
+
+    /// Token Mint. Its decimals are in the `Hello`.
+    #[account(
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Wormhole PDAs specified in the config are checked
+    /// against the Wormhole accounts in this context. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    /// Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ ErrorFactory::InvalidWormholeConfig
+    )]
+    /// Wormhole bridge data. [`wormhole::post_message`] requires this account
+    /// be mutable.
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ ErrorFactory::InvalidWormholeFeeCollector
+    )]
+    /// Wormhole fee collector. [`wormhole::post_message`] requires this
+    /// account be mutable.
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump,
+    )]
+    /// Program's emitter account. Read-only.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ ErrorFactory::InvalidWormholeSequence
+    )]
+    /// Emitter's sequence account. [`wormhole::post_message`] requires this
+    /// account be mutable.
+    pub wormhole_sequence: Account<'info, wormhole::SequenceTracker>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_PREFIX_SENT,
+            &wormhole_sequence.next_value().to_le_bytes()[..]
+        ],
+        bump,
+    )]
+    /// CHECK: Wormhole Message. [`wormhole::post_message`] requires this
+    /// account be mutable.
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl PostHello<'_> {
+    pub fn post_hello(ctx: Context<PostHello>) -> Result<()> {
+        // Pay the Fee
+        wormhole::pay_message_fee(
+            &ctx.accounts.wormhole_bridge,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.wormhole_fee_collector.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        let wormhole_emitter = &ctx.accounts.wormhole_emitter;
+        let mut encoded_payload: Vec<u8> = Vec::new();
+        CATSOLStructs::hello(&wormhole_emitter.key(), ctx.accounts.token_mint.decimals)
+            .serialize(&mut encoded_payload)?;
+
+        let config = &ctx.accounts.config;
+
+        wormhole::post_message(
+            CpiContext::new_with_signer(
+                ctx.accounts.wormhole_program.to_account_info(),
+                wormhole::PostMessage {
+                    config: ctx.accounts.wormhole_bridge.to_account_info(),
+                    message: ctx.accounts.wormhole_message.to_account_info(),
+                    emitter: wormhole_emitter.to_account_info(),
+                    sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+                    payer: ctx.accounts.owner.to_account_info(),
+                    fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[
+                    &[
+                        SEED_PREFIX_SENT,
+                        &ctx.accounts.wormhole_sequence.next_value().to_le_bytes()[..],
+                        &[ctx.bumps.wormhole_message],
+                    ],
+                    &[wormhole::SEED_PREFIX_EMITTER, &[wormhole_emitter.bump]],
+                ],
+            ),
+            config.batch_id,
+            encoded_payload,
+            config.finality.into(),
+        )?;
+
+        // Done.
+        Ok(())
+    }
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::token::Mint;
+
+use crate::{
+    constants::*,
+    utils_cat::*,
+    error::ErrorFactory,
+    cat_struct::CATSOLStructs,
+    state::{ForeignAttestation, ForeignEmitter}
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReceiveAttestationParams {
+    pub vaa_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(params: ReceiveAttestationParams)]
+/// Context used to check a foreign deployment's attestation against this
+/// token and record it.
+pub struct ReceiveAttestation<'info> {
+    #[account(mut)]
+    /// Payer of the foreign attestation account.
+    pub payer: Signer<'info>,
+
+    /// Token Mint.
+    #[account(
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Metadata account for the token. Read-only.
+    #[account(
+        seeds = [
+            b"metadata",
+            mpl_token_metadata::id().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = mpl_token_metadata::id()
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Verified Wormhole message account. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            // Emitters are registered under a u64 chain ID.
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        constraint = foreign_emitter.verify(posted.emitter_address()) @ ErrorFactory::InvalidForeignEmitter
+    )]
+    /// Foreign emitter account. The attestation must come from the emitter
+    /// registered for its chain. Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            ForeignAttestation::SEED_PREFIX,
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        space = ForeignAttestation::MAXIMUM_SIZE
+    )]
+    /// Latest attestation of the emitter chain.
+    pub foreign_attestation: Box<Account<'info, ForeignAttestation>>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl ReceiveAttestation<'_> {
+    pub fn receive_attestation(ctx: Context<ReceiveAttestation>, params: &ReceiveAttestationParams) -> Result<()> {
+        let posted_message = &ctx.accounts.posted;
+        ctx.accounts.foreign_emitter.check_policy(posted_message)?;
+        require!(
+            wormhole::posted_vaa_hash(&posted_message.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        if let CATSOLStructs::Attest { token_address, decimals, symbol, name, .. } = posted_message.data() {
+            let attestation = &mut ctx.accounts.foreign_attestation;
+            // A fresh account has chain zero, registered chains never do.
+            require!(
+                attestation.chain == 0 || posted_message.sequence() > attestation.sequence,
+                ErrorFactory::StaleAttestation
+            );
+
+            // Decimals may differ between chains, amounts are normalized.
+            let (local_symbol, local_name) = metadata_attest_bytes(&ctx.accounts.metadata_account.to_account_info())?;
+            require!(
+                *symbol == local_symbol && *name == local_name,
+                ErrorFactory::AttestationMismatch
+            );
+
+            attestation.chain = ctx.accounts.foreign_emitter.chain;
+            attestation.sequence = posted_message.sequence();
+            attestation.token_address = *token_address;
+            attestation.decimals = *decimals;
+            attestation.symbol = *symbol;
+            attestation.name = *name;
+
+            // Done
+            Ok(())
+        } else {
+            Err(ErrorFactory::InvalidMessage.into())
+        }
+    }
+}
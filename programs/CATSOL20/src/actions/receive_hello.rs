@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    chains::cat_chain_id,
+    error::ErrorFactory,
+    cat_struct::{CATSOLStructs, U256},
+    state::{ApprovedEmitter, Config, ForeignEmitter}
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ReceiveHelloParams {
+    pub vaa_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(params: ReceiveHelloParams)]
+/// Context used to register an approved emitter from its `Hello`.
+pub struct ReceiveHello<'info> {
+    #[account(mut)]
+    /// Payer of the foreign emitter account.
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Lists the emitter's chain.
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Owner of the program. Receives the rent of the approval.
+    #[account(
+        mut,
+        address = config.owner @ ErrorFactory::OwnerOnly
+    )]
+    pub owner: UncheckedAccount<'info>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Verified Wormhole message account. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            ApprovedEmitter::SEED_PREFIX,
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        constraint = approved_emitter.address == *posted.emitter_address() @ ErrorFactory::EmitterNotApproved
+    )]
+    /// The owner's approval of the emitter that posted the `Hello`.
+    pub approved_emitter: Account<'info, ApprovedEmitter>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        space = ForeignEmitter::MAXIMUM_SIZE,
+    )]
+    /// Foreign emitter account of the `Hello`'s chain. Registered, or
+    /// replaced, with the approved emitter.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl ReceiveHello<'_> {
+    pub fn receive_hello(ctx: Context<ReceiveHello>, params: &ReceiveHelloParams) -> Result<()> {
+        let posted_message = &ctx.accounts.posted;
+        require!(
+            wormhole::posted_vaa_hash(&posted_message.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        if let CATSOLStructs::Hello { emitter, chain, .. } = posted_message.data() {
+            require!(
+                emitter == posted_message.emitter_address()
+                    && cat_chain_id(posted_message.emitter_chain()).map(U256::from) == Some(*chain),
+                ErrorFactory::InvalidHello
+            );
+
+            // Save the emitter info into the ForeignEmitter account.
+            let foreign_emitter = &mut ctx.accounts.foreign_emitter;
+            foreign_emitter.chain = ctx.accounts.approved_emitter.chain;
+            foreign_emitter.address = ctx.accounts.approved_emitter.address;
+            ctx.accounts.config.add_emitter_chain(foreign_emitter.chain)?;
+
+            // Done
+            Ok(())
+        } else {
+            Err(ErrorFactory::InvalidMessage.into())
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    constants::*,
+    error::ErrorFactory,
+    events::SupplyExceeded,
+    state::{Config, RemoteSupply}
+};
+
+#[derive(Accounts)]
+/// Context used to check the supply across chains against the max supply.
+/// The remote supply accounts of every registered emitter chain are passed as
+/// remaining accounts.
+pub struct ReconcileSupply<'info> {
+    /// Owner of the program set in the [`Config`] account.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    /// Config account. Paused or unpaused by the reconciliation.
+    pub config: Box<Account<'info, Config>>,
+
+    /// Token Mint. Its supply is the local supply.
+    #[account(
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+}
+
+impl<'info> ReconcileSupply<'info> {
+    pub fn reconcile_supply(ctx: Context<'_, '_, 'info, 'info, ReconcileSupply<'info>>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // Exactly one supply per registered emitter chain, so a partial set
+        // can neither pause nor resume minting.
+        let emitter_chains = &ctx.accounts.config.emitter_chains;
+        require!(
+            ctx.remaining_accounts.len() == emitter_chains.len(),
+            ErrorFactory::RemoteSupplyMismatch
+        );
+
+        let mut chains = Vec::with_capacity(ctx.remaining_accounts.len());
+        let mut remote_supply: u64 = 0;
+        for account in ctx.remaining_accounts {
+            let remote = Account::<RemoteSupply>::try_from(account)?;
+            require!(!chains.contains(&remote.chain), ErrorFactory::DuplicateRemoteSupply);
+            require!(emitter_chains.contains(&remote.chain), ErrorFactory::RemoteSupplyMismatch);
+            require!(now - remote.block_time <= REMOTE_SUPPLY_MAX_AGE, ErrorFactory::StaleRemoteSupply);
+
+            chains.push(remote.chain);
+            remote_supply = remote_supply.saturating_add(remote.supply);
+        }
+
+        let config = &mut ctx.accounts.config;
+        let local_supply = ctx.accounts.token_mint.supply;
+        // Minting resumes once the supply is back under the max supply.
+        config.paused = local_supply.saturating_add(remote_supply) > config.max_supply;
+        if config.paused {
+            emit!(SupplyExceeded {
+                local_supply,
+                remote_supply,
+                max_supply: config.max_supply,
+            });
+        }
+
+        // Done
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::token::Mint;
+
+use crate::{
+    constants::*,
+    utils_cat::*,
+    error::ErrorFactory,
+    state::{ForeignEmitter, QuerySignatures, RemoteSupply}
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RecordRemoteSupplyParams {
+    /// Chain of the queried deployment.
+    pub chain: u16,
+    /// Wire encoded query response.
+    pub response: Vec<u8>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RecordRemoteSupplyParams)]
+/// Context used to record a remote deployment's total supply from a query
+/// response signed by a quorum of guardians, as collected by
+/// `verify_query_signatures`.
+pub struct RecordRemoteSupply<'info> {
+    #[account(mut)]
+    /// Payer of the remote supply account.
+    pub payer: Signer<'info>,
+
+    /// Token Mint. Remote supplies are recorded in its decimals.
+    #[account(
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        mut,
+        has_one = payer,
+        close = payer,
+        seeds = [
+            QuerySignatures::SEED_PREFIX,
+            &wormhole::query_response_hash(&params.response)[..],
+            payer.key().as_ref()
+        ],
+        bump
+    )]
+    /// Signers of the response. Closed once the response is recorded.
+    pub query_signatures: Box<Account<'info, QuerySignatures>>,
+
+    #[account(
+        seeds = [
+            wormhole::GuardianSet::SEED_PREFIX,
+            &query_signatures.guardian_set_index.to_be_bytes()
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Guardian set that signed the response. Read-only.
+    pub guardian_set: Account<'info, wormhole::GuardianSet>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &(params.chain as u64).to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// Foreign emitter account. The queried contract must be the emitter
+    /// registered for the chain. Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            RemoteSupply::SEED_PREFIX,
+            &(params.chain as u64).to_le_bytes()[..]
+        ],
+        bump,
+        space = RemoteSupply::MAXIMUM_SIZE
+    )]
+    /// Latest remote supply of the chain.
+    pub remote_supply: Box<Account<'info, RemoteSupply>>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl RecordRemoteSupply<'_> {
+    pub fn record_remote_supply(ctx: Context<RecordRemoteSupply>, params: &RecordRemoteSupplyParams) -> Result<()> {
+        let guardian_set = &ctx.accounts.guardian_set;
+        require!(
+            guardian_set.is_active(Clock::get()?.unix_timestamp),
+            ErrorFactory::GuardianSetExpired
+        );
+        require!(
+            ctx.accounts.query_signatures.num_verified() >= guardian_set.quorum(),
+            ErrorFactory::QueryQuorumNotReached
+        );
+
+        let (supply, decimals, block_number, block_time) =
+            parse_supply_response(&params.response, params.chain, &ctx.accounts.foreign_emitter.address)
+                .ok_or(ErrorFactory::InvalidQueryResponse)?;
+
+        let remote_supply = &mut ctx.accounts.remote_supply;
+        require!(block_time > remote_supply.block_time, ErrorFactory::StaleQueryResponse);
+
+        remote_supply.chain = ctx.accounts.foreign_emitter.chain;
+        remote_supply.supply = scale_supply(&supply, decimals, ctx.accounts.token_mint.decimals);
+        remote_supply.decimals = decimals;
+        remote_supply.block_number = block_number;
+        remote_supply.block_time = block_time;
+
+        // Done
+        Ok(())
+    }
+}
+
+/// `(supply, decimals, block_number, block_time)` of a response to a single
+/// `eth_call` query of `totalSupply()` and `decimals()` on `chain`, both
+/// called on `emitter`.
+fn parse_supply_response(response: &[u8], chain: u16, emitter: &[u8; 32]) -> Option<([u8; 32], u8, u64, i64)> {
+    let response = wormhole::QueryResponse::parse(response).ok()?;
+    let [(query, result)] = response.pairs().collect::<Vec<_>>()[..] else {
+        return None;
+    };
+    if query.chain_id != chain || query.query_type != wormhole::QUERY_TYPE_ETH_CALL {
+        return None;
+    }
+
+    // EVM emitters are left-padded 20-byte addresses.
+    let (padding, contract) = emitter.split_at(12);
+    let query = wormhole::EthCallQuery::parse(&query.query).ok()?;
+    let calls_emitter = |call: &wormhole::EthCallData, selector: &[u8; 4]| {
+        call.to[..] == *contract && call.data[..] == selector[..]
+    };
+    match &query.calls[..] {
+        [total_supply, decimals]
+            if padding.iter().all(|byte| *byte == 0)
+                && calls_emitter(total_supply, &TOTAL_SUPPLY_SELECTOR)
+                && calls_emitter(decimals, &DECIMALS_SELECTOR) => {}
+        _ => return None,
+    }
+
+    let result = wormhole::EthCallQueryResponse::parse(&result.response).ok()?;
+    let [supply, decimals] = &result.results[..] else {
+        return None;
+    };
+    let supply: [u8; 32] = supply[..].try_into().ok()?;
+    // uint8 is ABI encoded as a 32-byte word.
+    let decimals: [u8; 32] = decimals[..].try_into().ok()?;
+    if decimals[..31].iter().any(|byte| *byte != 0) {
+        return None;
+    }
+
+    Some((supply, decimals[31], result.block_number, result.block_time_seconds()))
+}
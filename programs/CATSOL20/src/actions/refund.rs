@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{mint_to, MintTo, Mint, Token, TokenAccount},
+};
+
+use crate::{
+    chains::wormhole_chain_id,
+    constants::*,
+    utils_cat::*,
+    error::ErrorFactory,
+    events::Redeemed,
+    cat_struct::CATSOLStructs,
+    state::{Config, ForeignEmitter, Received, OutboundStatus, OutboundTransfer, ReceivedBitmap, Refunded, WormholeEmitter}
+};
+
+#[derive(Clone, AnchorDeserialize, AnchorSerialize)]
+pub struct RefundParams {
+    pub vaa_hash: [u8; 32],
+    /// Sequence of the outbound transfer being refunded. Must match the
+    /// refund message.
+    pub sequence: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: RefundParams)]
+pub struct Refund<'info> {
+    /// Payer for the `received_bitmap`, `refunded` and token accounts.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: ATA Authority. The original sender of the refunded transfer.
+    #[account(mut)]
+    pub ata_authority: UncheckedAccount<'info>,
+
+    /// Token Mint. The token that is re-minted.
+    #[account(
+        mut,
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    // Token Account. The original sender's Associated Token Account.
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = token_mint,
+        associated_token::authority = ata_authority,
+    )]
+    pub token_user_ata: Account<'info, TokenAccount>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+    // Associated Token Program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump,
+    )]
+    /// Program's emitter account. The refunded transfer must have been sent
+    /// by it. Read-only.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Verified Wormhole refund message. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            ReceivedBitmap::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &ReceivedBitmap::index(posted.sequence()).to_le_bytes()[..]
+        ],
+        bump,
+        space = ReceivedBitmap::MAXIMUM_SIZE,
+        constraint = !received_bitmap.is_received(posted.sequence()) @ ErrorFactory::AlreadyReceived
+    )]
+    /// Received bitmap covering the message's sequence. The message's bit is
+    /// set on redemption, preventing replay.
+    pub received_bitmap: Box<Account<'info, ReceivedBitmap>>,
+
+    #[account(
+        seeds = [
+            Received::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &posted.sequence().to_le_bytes()[..]
+        ],
+        bump,
+        constraint = received.data_is_empty() @ ErrorFactory::AlreadyReceived
+    )]
+    /// CHECK: Received account of the message. Only messages redeemed before
+    /// the received bitmap have one, and those cannot be redeemed again.
+    pub received: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            Refunded::SEED_PREFIX,
+            &params.sequence.to_le_bytes()[..]
+        ],
+        bump,
+        space = Refunded::MAXIMUM_SIZE
+    )]
+    /// Refunded account. Cannot be overwritten, so each outbound transfer is
+    /// refunded at most once.
+    pub refunded: Account<'info, Refunded>,
+
+    #[account(
+        mut,
+        seeds = [
+            OutboundTransfer::SEED_PREFIX,
+            &params.sequence.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// CHECK: Outbound transfer account of the refunded transfer. If it
+    /// exists, it must match the refund, and is marked refunded. Transfers
+    /// sent before these accounts existed have none.
+    pub outbound_transfer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            // Emitters are registered under a u64 chain ID.
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        constraint = foreign_emitter.verify(posted.emitter_address()) @ ErrorFactory::InvalidForeignEmitter
+    )]
+    /// Foreign emitter account. The refund must come from the emitter
+    /// registered for its chain. Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+
+impl Refund<'_> {
+    pub fn refund(ctx: Context<Refund>, params: RefundParams) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorFactory::MintingPaused);
+        let posted_message = &ctx.accounts.posted;
+        ctx.accounts.foreign_emitter.check_policy(posted_message)?;
+        require!(
+            wormhole::posted_vaa_hash(&posted_message.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        if let CATSOLStructs::Refund { sequence, payload } = posted_message.data() {
+            require!(*sequence == params.sequence, ErrorFactory::InvalidRefund);
+
+            // The refunded transfer must have left this program for the chain
+            // that is now refunding it.
+            let source_chain: u64 = payload.source_token_chain.into();
+            let dest_chain: u64 = payload.dest_token_chain.into();
+            require!(
+                source_chain == CONVENTIONAL_SOLANA_ID
+                    && payload.source_token_address == ctx.accounts.wormhole_emitter.key().to_bytes()
+                    && wormhole_chain_id(dest_chain) == Some(posted_message.emitter_chain()),
+                ErrorFactory::InvalidRefund
+            );
+
+            let ata_address = associated_token::get_associated_token_address(
+                &Pubkey::from(payload.source_user_address),
+                &ctx.accounts.token_mint.key(),
+            );
+
+            // Refunds only go back to the original sender.
+            require_keys_eq!(
+                ata_address,
+                ctx.accounts.token_user_ata.key(),
+                ErrorFactory::MisMatchdATAAddress
+            );
+
+            // The payload carries the normalized amount, so dust burned on the
+            // way out is not refunded.
+            let amount_u64: u64 = payload.amount.into();
+            let decimals = ctx.accounts.token_mint.decimals;
+            let refund_amount = denormalize_amount(amount_u64, decimals);
+
+            // The transfer's record, if it has one, must be the refunded
+            // transfer, still pending. It is marked refunded.
+            if !ctx.accounts.outbound_transfer.data_is_empty() {
+                let mut data = ctx.accounts.outbound_transfer.try_borrow_mut_data()?;
+                let mut outbound = OutboundTransfer::try_deserialize(&mut &data[..])?;
+                require!(
+                    outbound.status == OutboundStatus::Pending,
+                    ErrorFactory::TransferNotPending
+                );
+                require!(
+                    outbound.sender == Pubkey::from(payload.source_user_address)
+                        && denormalize_amount(normalize_amount(outbound.amount, decimals), decimals) == refund_amount
+                        && outbound.recipient_chain == dest_chain,
+                    ErrorFactory::OutboundTransferMismatch
+                );
+                outbound.status = OutboundStatus::Refunded;
+                outbound.try_serialize(&mut &mut data[..])?;
+            }
+
+            // Mint the tokens back
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_accounts = MintTo {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.token_user_ata.to_account_info(),
+                authority: ctx.accounts.token_mint.to_account_info(),
+            };
+            let bump = ctx.bumps.token_mint;
+
+            let cpi_signer_seeds = &[
+                b"spl_cat_token".as_ref(),
+                &[bump],
+            ];
+            let cpi_signer = &[&cpi_signer_seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, cpi_signer);
+
+            mint_to(cpi_ctx, refund_amount)?;
+
+            // Mark the message as redeemed.
+            ctx.accounts.received_bitmap.mark_received(
+                ctx.accounts.posted.emitter_chain(),
+                ctx.accounts.posted.sequence(),
+            )?;
+            emit!(Redeemed {
+                emitter_chain: posted_message.emitter_chain(),
+                sequence: posted_message.sequence(),
+                vaa_hash: params.vaa_hash,
+                amount: refund_amount,
+                recipient: ctx.accounts.token_user_ata.key(),
+            });
+
+            // Save the keccak256 hash of the refund message.
+            ctx.accounts.refunded.wormhole_message_hash = params.vaa_hash;
+
+            // Done
+            Ok(())
+        } else {
+            Err(ErrorFactory::InvalidMessage.into())
+        }
+    }
+}
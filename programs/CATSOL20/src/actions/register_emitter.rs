@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use crate::{
+    chains::is_emitter_chain,
     error::ErrorFactory,
     state::{Config, ForeignEmitter},
 };
-use wormhole_anchor_sdk::wormhole;
 
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
@@ -19,6 +19,7 @@ pub struct RegisterEmitter<'info> {
     pub owner: Signer<'info>,
 
     #[account(
+        mut,
         has_one = owner @ ErrorFactory::OwnerOnly,
         seeds = [Config::SEED_PREFIX],
         bump
@@ -47,10 +48,11 @@ impl RegisterEmitter<'_> {
     ) -> Result<()> {
         let chain = params.chain;
         let address = params.address;
-        // Foreign emitter cannot share the same Wormhole Chain ID as the
-        // Solana Wormhole program's. And cannot register a zero address.
+        // Foreign emitter must be on a chain in the registry, and cannot share
+        // the same Wormhole Chain ID as the Solana Wormhole program's. And
+        // cannot register a zero address.
         require!(
-            chain > 0 && chain != wormhole::CHAIN_ID_SOLANA as u64 as u64 && !address.iter().all(|&x| x == 0),
+            is_emitter_chain(chain) && !address.iter().all(|&x| x == 0),
             ErrorFactory::InvalidForeignEmitter,
         );
 
@@ -59,6 +61,9 @@ impl RegisterEmitter<'_> {
         emitter.chain = chain;
         emitter.address = address;
 
+        // A new chain needs its remote supply reconciled.
+        ctx.accounts.config.add_emitter_chain(chain)?;
+
         // Done.
         Ok(())
     }
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorFactory,
+    state::{Config, ForeignEmitter},
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetEmitterPolicyParams {
+    pub chain: u64,
+    /// Seconds. Zero means no limit.
+    pub max_vaa_age: u32,
+    /// Consistency levels to accept. Empty accepts any.
+    pub consistency_levels: Vec<u8>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetEmitterPolicyParams)]
+pub struct SetEmitterPolicy<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &params.chain.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// Registered emitter of the source chain.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+}
+
+impl SetEmitterPolicy<'_> {
+    pub fn set_emitter_policy(
+        ctx: Context<SetEmitterPolicy>,
+        params: &SetEmitterPolicyParams,
+    ) -> Result<()> {
+        require!(
+            params.consistency_levels.len() <= ForeignEmitter::MAX_CONSISTENCY_LEVELS,
+            ErrorFactory::TooManyConsistencyLevels
+        );
+
+        let emitter = &mut ctx.accounts.foreign_emitter;
+        emitter.max_vaa_age = params.max_vaa_age;
+        emitter.consistency_levels = params.consistency_levels.clone();
+        Ok(())
+    }
+}
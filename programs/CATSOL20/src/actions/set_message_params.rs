@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::ErrorFactory,
+    state::{Config, ForeignEmitter},
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetMessageParamsParams {
+    pub batch_id: u32,
+    pub finality: u8,
+    pub finalized_threshold: u64,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct SetEmitterFinalityParams {
+    pub chain: u64,
+    /// `None` falls back to the config's finality.
+    pub finality: Option<u8>,
+}
+
+#[derive(Accounts)]
+pub struct SetMessageParams<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+#[derive(Accounts)]
+#[instruction(params: SetEmitterFinalityParams)]
+pub struct SetEmitterFinality<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &params.chain.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// Registered emitter of the destination chain.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+}
+
+fn check_finality(finality: u8) -> Result<()> {
+    require!(
+        finality <= wormhole::Finality::Finalized as u8,
+        ErrorFactory::InvalidFinality
+    );
+    Ok(())
+}
+
+impl SetMessageParams<'_> {
+    pub fn set_message_params(
+        ctx: Context<SetMessageParams>,
+        params: &SetMessageParamsParams,
+    ) -> Result<()> {
+        check_finality(params.finality)?;
+
+        let config = &mut ctx.accounts.config;
+        config.batch_id = params.batch_id;
+        config.finality = params.finality;
+        config.finalized_threshold = params.finalized_threshold;
+        Ok(())
+    }
+}
+
+impl SetEmitterFinality<'_> {
+    pub fn set_emitter_finality(
+        ctx: Context<SetEmitterFinality>,
+        params: &SetEmitterFinalityParams,
+    ) -> Result<()> {
+        if let Some(finality) = params.finality {
+            check_finality(finality)?;
+        }
+
+        ctx.accounts.foreign_emitter.finality = params.finality;
+        Ok(())
+    }
+}
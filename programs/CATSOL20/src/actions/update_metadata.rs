@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{metadata::Metadata, token::Mint};
+
+use crate::{
+    constants::*,
+    error::ErrorFactory,
+    state::Config,
+};
+
+use anchor_lang::solana_program::program::invoke_signed;
+use mpl_token_metadata::{
+    instruction::update_metadata_accounts_v2,
+    state::{Collection, Creator, DataV2},
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MetadataCreator {
+    pub address: Pubkey,
+    /// Percentage of royalties, all creators must add up to 100.
+    pub share: u8,
+}
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateMetadataParams {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    /// Creators are added unverified, they verify themselves with Metaplex.
+    pub creators: Option<Vec<MetadataCreator>>,
+    /// Collection mint, added unverified.
+    pub collection: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+/// Context used to update or freeze the token's Metaplex metadata. The mint
+/// PDA is the metadata's update authority.
+pub struct UpdateMetadata<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// Token Mint. Signs as the metadata's update authority.
+    #[account(
+        seeds = [SEED_PREFIX_MINT],
+        bump
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Metadata account for the token.
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            mpl_token_metadata::id().as_ref(),
+            token_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = mpl_token_metadata::id()
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// Metadata program.
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+impl UpdateMetadata<'_> {
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, params: &UpdateMetadataParams) -> Result<()> {
+        let data = DataV2 {
+            name: params.name.clone(),
+            symbol: params.symbol.clone(),
+            uri: params.uri.clone(),
+            seller_fee_basis_points: 0,
+            creators: params.creators.as_ref().map(|creators| {
+                creators
+                    .iter()
+                    .map(|creator| Creator {
+                        address: creator.address,
+                        verified: false,
+                        share: creator.share,
+                    })
+                    .collect()
+            }),
+            collection: params.collection.map(|key| Collection {
+                verified: false,
+                key,
+            }),
+            uses: None,
+        };
+
+        Self::invoke_update(&ctx, Some(data), None)
+    }
+
+    pub fn set_metadata_immutable(ctx: Context<UpdateMetadata>) -> Result<()> {
+        Self::invoke_update(&ctx, None, Some(false))
+    }
+
+    fn invoke_update(
+        ctx: &Context<UpdateMetadata>,
+        data: Option<DataV2>,
+        is_mutable: Option<bool>,
+    ) -> Result<()> {
+        let update_metadata_ix = update_metadata_accounts_v2(
+            ctx.accounts.metadata_program.key(),
+            ctx.accounts.metadata_account.key(),
+            ctx.accounts.token_mint.key(),
+            None,
+            data,
+            None,
+            is_mutable,
+        );
+
+        let bump = ctx.bumps.token_mint;
+
+        let metadata_signer_seeds = &[
+            b"spl_cat_token".as_ref(),
+            &[bump],
+        ];
+
+        invoke_signed(
+            &update_metadata_ix,
+            &[
+                ctx.accounts.metadata_account.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+                ctx.accounts.metadata_program.to_account_info(),
+            ],
+            &[metadata_signer_seeds],
+        )?;
+
+        Ok(())
+    }
+}
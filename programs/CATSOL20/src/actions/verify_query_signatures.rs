@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    error::ErrorFactory,
+    state::QuerySignatures
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct VerifyQuerySignaturesParams {
+    /// Index of the guardian set that signed the response.
+    pub guardian_set_index: u32,
+    /// Keccak256 of the wire encoded query response.
+    pub response_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(params: VerifyQuerySignaturesParams)]
+/// Context used to collect guardian signatures of a query response. A
+/// secp256k1 instruction with some of the signatures must come right before
+/// this one.
+pub struct VerifyQuerySignatures<'info> {
+    #[account(mut)]
+    /// Payer of the query signatures account.
+    pub payer: Signer<'info>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::GuardianSet::SEED_PREFIX,
+            &params.guardian_set_index.to_be_bytes()
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Guardian set that signed the response. Read-only.
+    pub guardian_set: Account<'info, wormhole::GuardianSet>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        seeds = [
+            QuerySignatures::SEED_PREFIX,
+            &params.response_hash[..],
+            payer.key().as_ref()
+        ],
+        bump,
+        space = QuerySignatures::MAXIMUM_SIZE
+    )]
+    /// Signers of the response verified so far. One per payer, so nobody
+    /// else can lock it to another guardian set or take its rent.
+    pub query_signatures: Box<Account<'info, QuerySignatures>>,
+
+    /// CHECK: Instructions sysvar, to read the secp256k1 instruction.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl VerifyQuerySignatures<'_> {
+    pub fn verify_query_signatures(ctx: Context<VerifyQuerySignatures>, params: &VerifyQuerySignaturesParams) -> Result<()> {
+        let guardian_set = &ctx.accounts.guardian_set;
+        require!(
+            guardian_set.is_active(Clock::get()?.unix_timestamp),
+            ErrorFactory::GuardianSetExpired
+        );
+
+        let query_signatures = &mut ctx.accounts.query_signatures;
+        // A fresh account has no signatures, guardian sets are never empty.
+        if query_signatures.signatures.is_empty() {
+            query_signatures.payer = ctx.accounts.payer.key();
+            query_signatures.guardian_set_index = guardian_set.index;
+            query_signatures.signatures = vec![false; guardian_set.keys.len()];
+        }
+        require!(
+            query_signatures.guardian_set_index == guardian_set.index,
+            ErrorFactory::InvalidQuerySignatures
+        );
+
+        let signers = wormhole::query_signers(&ctx.accounts.instructions.to_account_info(), &params.response_hash)
+            .map_err(|_| ErrorFactory::InvalidQuerySignatures)?;
+        for key in &signers {
+            let index = guardian_set.key_index(key).ok_or(ErrorFactory::InvalidQuerySignatures)?;
+            query_signatures.signatures[index] = true;
+        }
+
+        // Done
+        Ok(())
+    }
+}
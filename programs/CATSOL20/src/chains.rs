@@ -0,0 +1,79 @@
+use wormhole_anchor_sdk::wormhole;
+
+use crate::constants::CONVENTIONAL_SOLANA_ID;
+
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "solana-devnet",
+    feature = "tilt-devnet"
+)))]
+compile_error!("Enable one of the `mainnet`, `solana-devnet` or `tilt-devnet` features.");
+
+/// Chain registry: CAT chain IDs and the Wormhole chain IDs of the same
+/// chains. Payloads carry CAT chain IDs, which are the EVM chain IDs of EVM
+/// chains. Foreign emitters and received messages are keyed by Wormhole chain
+/// IDs. The registry follows the network the program is built for.
+#[cfg(feature = "mainnet")]
+pub const CHAINS: &[(u64, u16)] = &[
+    (CONVENTIONAL_SOLANA_ID, wormhole::CHAIN_ID_SOLANA),
+    (1, 2),      // Ethereum
+    (56, 4),     // BNB Smart Chain
+    (137, 5),    // Polygon
+    (43114, 6),  // Avalanche
+    (250, 10),   // Fantom
+    (42161, 23), // Arbitrum
+    (10, 24),    // Optimism
+    (8453, 30),  // Base
+];
+
+/// Chain registry of the Solana devnet, whose guardians observe the public
+/// testnets.
+#[cfg(feature = "solana-devnet")]
+pub const CHAINS: &[(u64, u16)] = &[
+    (CONVENTIONAL_SOLANA_ID, wormhole::CHAIN_ID_SOLANA),
+    (97, 4),           // BNB Smart Chain Testnet
+    (43113, 6),        // Avalanche Fuji
+    (4002, 10),        // Fantom Testnet
+    (11155111, 10002), // Sepolia
+    (421614, 10003),   // Arbitrum Sepolia
+    (84532, 10004),    // Base Sepolia
+    (11155420, 10005), // Optimism Sepolia
+    (80002, 10007),    // Polygon Amoy
+];
+
+/// Chain registry of the Tilt devnet.
+#[cfg(feature = "tilt-devnet")]
+pub const CHAINS: &[(u64, u16)] = &[
+    (CONVENTIONAL_SOLANA_ID, wormhole::CHAIN_ID_SOLANA),
+    (1337, 2), // Ethereum devnet
+    (1397, 4), // Second Ethereum devnet, as BNB Smart Chain
+];
+
+/// Wormhole chain ID of a CAT chain ID, if the chain is registered.
+pub fn wormhole_chain_id(cat_chain: u64) -> Option<u16> {
+    CHAINS
+        .iter()
+        .find(|(cat, _)| *cat == cat_chain)
+        .map(|(_, wh)| *wh)
+}
+
+/// Whether a foreign emitter can be registered for the Wormhole `chain`: it is
+/// in the registry, and is not Solana.
+pub fn is_emitter_chain(chain: u64) -> bool {
+    chain != wormhole::CHAIN_ID_SOLANA as u64
+        && u16::try_from(chain).ok().and_then(cat_chain_id).is_some()
+}
+
+/// CAT chain ID of a Wormhole chain ID, if the chain is registered.
+pub fn cat_chain_id(wormhole_chain: u16) -> Option<u64> {
+    CHAINS
+        .iter()
+        .find(|(_, wh)| *wh == wormhole_chain)
+        .map(|(cat, _)| *cat)
+}
+
+/// Key of a CAT chain's foreign emitter account: its Wormhole chain ID, or
+/// zero, which is never registered, if the chain is unknown.
+pub fn emitter_chain_key(cat_chain: u64) -> [u8; 8] {
+    u64::from(wormhole_chain_id(cat_chain).unwrap_or_default()).to_le_bytes()
+}
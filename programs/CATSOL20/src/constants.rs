@@ -1,11 +1,34 @@
-pub const CORE_BRIDGE_ADDRESS: &str = "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o";
-
 // SEED Prefix for the Wormhole Message Account
 pub const SEED_PREFIX_SENT: &[u8; 4] = b"sent";
 
+// SEED Prefix for a sender's reusable Wormhole Message Account
+pub const SEED_PREFIX_REUSABLE: &[u8; 8] = b"reusable";
+
+// Seconds a message stays in a reusable Wormhole Message Account before it
+// may be overwritten, so guardians observe it first. A message the guardians
+// miss in that window is lost once overwritten: the transfer can then only be
+// recovered by a refund from the destination chain.
+pub const REUSABLE_MESSAGE_DELAY: i64 = 60;
+
 // SEED Prefix for the Mint Account
 pub const SEED_PREFIX_MINT: &'static [u8; 13] = b"spl_cat_token";
 
+// SEED Prefix for a recipient program's Redeemer Account, derived from the
+// recipient program. It owns the tokens of a transfer with payload.
+pub const SEED_PREFIX_REDEEMER: &[u8; 8] = b"redeemer";
+
+// SEED Prefix for the Payload Authority. It signs the call into a recipient
+// program, which checks it to know the call comes from this program.
+pub const SEED_PREFIX_PAYLOAD_AUTHORITY: &[u8; 17] = b"payload_authority";
+
+// Seconds a remote supply may be used by `reconcile_supply`, measured from
+// the block time of its query response.
+pub const REMOTE_SUPPLY_MAX_AGE: i64 = 60 * 60;
+
+// Selectors of the ERC-20 calls in a remote supply query response:
+// `totalSupply()` and `decimals()`.
+pub const TOTAL_SUPPLY_SELECTOR: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+pub const DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
 
 // Conventional Solana ID
 pub const CONVENTIONAL_SOLANA_ID: u64 = 17;
\ No newline at end of file
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[event]
+/// Emitted for every redeemed message. The received bitmap only records that
+/// a sequence was redeemed; this is the record of what it delivered.
+pub struct Redeemed {
+    /// Emitter chain of the message.
+    pub emitter_chain: u16,
+    /// Sequence of the message.
+    pub sequence: u64,
+    /// Keccak256 hash of the VAA body, recomputed from the posted account.
+    pub vaa_hash: [u8; 32],
+    /// Tokens delivered, in the token's decimals.
+    pub amount: u64,
+    /// Token account the tokens went to.
+    pub recipient: Pubkey,
+}
+
+#[event]
+/// Emitted by `reconcile_supply` when the supply across chains exceeds the
+/// max supply. Minting stays paused until a reconciliation passes.
+pub struct SupplyExceeded {
+    /// Supply of the token on Solana.
+    pub local_supply: u64,
+    /// Sum of the recorded remote supplies, in the token's decimals.
+    pub remote_supply: u64,
+    /// Config's max supply.
+    pub max_supply: u64,
+}
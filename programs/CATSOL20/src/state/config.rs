@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole::Finality;
+
+use crate::{error::ErrorFactory, state::ForeignEmitter};
 
 #[derive(Default, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 /// Wormhole program related addresses.
@@ -27,16 +30,25 @@ pub struct Config {
     pub owner: Pubkey,
     /// Wormhole program's relevant addresses.
     pub wormhole: WormholeAddresses,
-    /// AKA batch_id. Just zero, but saving this information in this account
-    /// anyway.
+    /// AKA batch_id. Zero unless changed with `set_message_params`.
     pub batch_id: u32,
     /// AKA consistency level. u8 representation of Solana's
-    /// [Finality](wormhole_anchor_sdk::wormhole::Finality).
+    /// [Finality](wormhole_anchor_sdk::wormhole::Finality). Default for
+    /// destinations without their own.
     pub finality: u8,
     /// Minted supply.
     pub minted_supply: u64,
     /// Max supply.
     pub max_supply: u64,
+    /// Transfers of at least this amount (in the token's decimals) are
+    /// published as Finalized, whatever the destination. Zero disables it.
+    pub finalized_threshold: u64,
+    /// Set by `reconcile_supply` when the supply across chains exceeds
+    /// `max_supply`. Minting is refused until a reconciliation passes.
+    pub paused: bool,
+    /// Wormhole chains of the registered foreign emitters. `reconcile_supply`
+    /// needs the remote supply of each of them.
+    pub emitter_chains: Vec<u64>,
 }
 
 impl Config {
@@ -47,7 +59,33 @@ impl Config {
         + 1 // finality
         + 8 // minted_supply
         + 8 // max_supply   
+        + 8 // finalized_threshold
+        + 1 // paused
+        + 4 + 8 * Self::MAX_EMITTER_CHAINS // emitter_chains
     ;
+    /// Most foreign emitter chains the config can list.
+    pub const MAX_EMITTER_CHAINS: usize = 32;
     /// AKA `b"config"`.
     pub const SEED_PREFIX: &'static [u8; 6] = b"config";
-}
\ No newline at end of file
+
+    /// Finality to publish a transfer of `amount` to `foreign_emitter`'s chain
+    /// with.
+    pub fn message_finality(&self, foreign_emitter: &ForeignEmitter, amount: u64) -> u8 {
+        if self.finalized_threshold > 0 && amount >= self.finalized_threshold {
+            return Finality::Finalized as u8;
+        }
+        foreign_emitter.finality.unwrap_or(self.finality)
+    }
+
+    /// Lists `chain` in `emitter_chains`, unless it is already.
+    pub fn add_emitter_chain(&mut self, chain: u64) -> Result<()> {
+        if !self.emitter_chains.contains(&chain) {
+            require!(
+                self.emitter_chains.len() < Self::MAX_EMITTER_CHAINS,
+                ErrorFactory::TooManyEmitterChains
+            );
+            self.emitter_chains.push(chain);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+/// Foreign attestation account. The latest `Attest` message received from a
+/// registered emitter chain.
+pub struct ForeignAttestation {
+    /// Emitter chain of the attestation.
+    pub chain: u64,
+    /// Wormhole sequence of the attestation. Older ones are rejected.
+    pub sequence: u64,
+    /// Token address on the foreign chain.
+    pub token_address: [u8; 32],
+    /// Decimals of the foreign deployment.
+    pub decimals: u8,
+    /// Symbol, UTF-8 right-padded with zeros.
+    pub symbol: [u8; 32],
+    /// Name, UTF-8 right-padded with zeros.
+    pub name: [u8; 32],
+}
+
+impl ForeignAttestation {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // chain
+        + 8 // sequence
+        + 32 // token_address
+        + 1 // decimals
+        + 32 // symbol
+        + 32 // name
+    ;
+    /// AKA `b"foreign_attestation"`.
+    pub const SEED_PREFIX: &'static [u8; 19] = b"foreign_attestation";
+}
@@ -1,9 +1,23 @@
+pub use approved_emitter::*;
 pub use config::*;
+pub use foreign_attestation::*;
 pub use foreign_emitter::*;
+pub use outbound_transfer::*;
+pub use query_signatures::*;
 pub use received::*;
+pub use received_bitmap::*;
+pub use refunded::*;
+pub use remote_supply::*;
 pub use wormhole_emitter::*;
 
+pub mod approved_emitter;
 pub mod config;
+pub mod foreign_attestation;
 pub mod foreign_emitter;
+pub mod outbound_transfer;
+pub mod query_signatures;
 pub mod received;
+pub mod received_bitmap;
+pub mod refunded;
+pub mod remote_supply;
 pub mod wormhole_emitter;
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// Status of an outbound transfer, as far as this chain can tell.
+pub enum OutboundStatus {
+    /// Sent. Redeemed on the destination chain, unless it comes back as a
+    /// refund.
+    #[default]
+    Pending,
+    /// Refunded to the sender.
+    Refunded,
+}
+
+#[account]
+#[derive(Default, Debug)]
+/// Outbound transfer account. Records the transfer posted with the sequence
+/// in its seeds. `sender` and `status` come first so clients can filter on
+/// them, see [`OutboundTransfer::pending_filters`].
+pub struct OutboundTransfer {
+    /// Wallet the tokens were sent from.
+    pub sender: Pubkey,
+    pub status: OutboundStatus,
+    /// Wormhole sequence of the transfer message.
+    pub sequence: u64,
+    /// Amount taken from the sender, in the token's decimals.
+    pub amount: u64,
+    /// CAT chain ID of the destination.
+    pub recipient_chain: u64,
+    pub recipient: [u8; 32],
+    /// Unix timestamp of the transfer.
+    pub timestamp: i64,
+    /// Payer of this account. Gets the rent back when it is closed.
+    pub payer: Pubkey,
+}
+
+impl OutboundTransfer {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // sender
+        + 1 // status
+        + 8 // sequence
+        + 8 // amount
+        + 8 // recipient_chain
+        + 32 // recipient
+        + 8 // timestamp
+        + 32 // payer
+    ;
+    /// AKA `b"outbound"`.
+    pub const SEED_PREFIX: &'static [u8; 8] = b"outbound";
+    /// Seconds after the transfer before the account of a pending transfer
+    /// can be closed (30 days). Refunded transfers can be closed at once.
+    pub const RETENTION_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+    /// Offset of `sender` in the account data.
+    pub const SENDER_OFFSET: usize = 8;
+    /// Offset of `status` in the account data.
+    pub const STATUS_OFFSET: usize = Self::SENDER_OFFSET + 32;
+
+    /// `(offset, bytes)` memcmp filters matching the pending transfers of
+    /// `sender`, for a `getProgramAccounts` call on this program.
+    pub fn pending_filters(sender: &Pubkey) -> Vec<(usize, Vec<u8>)> {
+        vec![
+            (0, Self::DISCRIMINATOR.to_vec()),
+            (Self::SENDER_OFFSET, sender.to_bytes().to_vec()),
+            (Self::STATUS_OFFSET, vec![OutboundStatus::Pending as u8]),
+        ]
+    }
+}
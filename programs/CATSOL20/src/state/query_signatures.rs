@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+#[account]
+#[derive(Default, Debug)]
+/// Query signatures account. Guardians whose signatures of a query response
+/// were verified, collected over as many transactions as needed. Derived from
+/// the response's hash and the payer, and closed once the response is
+/// recorded.
+pub struct QuerySignatures {
+    /// Payer of this account. Gets the rent back when the response is
+    /// recorded.
+    pub payer: Pubkey,
+    /// Index of the signers' guardian set.
+    pub guardian_set_index: u32,
+    /// Whether the guardian at each index of the set signed.
+    pub signatures: Vec<bool>,
+}
+
+impl QuerySignatures {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // payer
+        + 4 // guardian_set_index
+        + 4 + wormhole::MAX_VERIFY_SIGNERS // signatures
+    ;
+    /// AKA `b"query_signatures"`.
+    pub const SEED_PREFIX: &'static [u8; 16] = b"query_signatures";
+
+    /// Number of guardians whose signature was verified.
+    pub fn num_verified(&self) -> usize {
+        self.signatures.iter().filter(|signed| **signed).count()
+    }
+}
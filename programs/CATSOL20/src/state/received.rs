@@ -2,10 +2,24 @@ use anchor_lang::prelude::*;
 
 #[account]
 #[derive(Default, Debug)]
-/// Received account.
+/// Received account. Record of a redeemed message, paid for by the redeemer.
+/// No longer created: the [`ReceivedBitmap`](crate::state::ReceivedBitmap)
+/// keeps messages from being replayed. Existing accounts are closed with
+/// `close_received` by their payer or the owner once
+/// [`Received::RETENTION_PERIOD`] is over.
 pub struct Received {
     /// Keccak256 hash of verified Wormhole message.
     pub wormhole_message_hash: [u8; 32],
+    /// Payer of this account. Gets the rent back when it is closed.
+    pub payer: Pubkey,
+    /// Emitter chain of the message.
+    pub emitter_chain: u16,
+    /// Sequence of the message.
+    pub sequence: u64,
+    /// Tokens delivered, in the token's decimals.
+    pub amount: u64,
+    /// Token account the tokens went to.
+    pub recipient: Pubkey,
 }
 
 
@@ -13,7 +27,20 @@ pub struct Received {
 impl Received {
     pub const MAXIMUM_SIZE: usize = 8 // discriminator
         + 32 // wormhole_message_hash
+        + 32 // payer
+        + 2 // emitter_chain
+        + 8 // sequence
+        + 8 // amount
+        + 32 // recipient
+    ;
+    /// Size of accounts created before the received bitmap, which only hold
+    /// `wormhole_message_hash`. Closed with `close_legacy_received`.
+    pub const LEGACY_SIZE: usize = 8 // discriminator
+        + 32 // wormhole_message_hash
     ;
     /// AKA `b"received"`.
     pub const SEED_PREFIX: &'static [u8; 8] = b"received";
+    /// Seconds after the message's timestamp before the account can be
+    /// closed (30 days).
+    pub const RETENTION_PERIOD: i64 = 30 * 24 * 60 * 60;
 }
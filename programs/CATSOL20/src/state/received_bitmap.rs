@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorFactory;
+
+#[account]
+#[derive(Debug)]
+/// Received bitmap account. Replay protection for
+/// [`ReceivedBitmap::SEQUENCES_PER_ACCOUNT`] consecutive sequences of one
+/// emitter chain, one bit per message.
+pub struct ReceivedBitmap {
+    /// Emitter chain of the messages.
+    pub chain: u16,
+    /// First sequence covered by this account.
+    pub first_sequence: u64,
+    /// Bit `i` is set once message `first_sequence + i` is redeemed.
+    pub bits: [u8; 1024],
+}
+
+impl ReceivedBitmap {
+    pub const SEQUENCES_PER_ACCOUNT: u64 = 8 * 1024;
+
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 2 // chain
+        + 8 // first_sequence
+        + 1024 // bits
+    ;
+    /// AKA `b"received_bitmap"`.
+    pub const SEED_PREFIX: &'static [u8; 15] = b"received_bitmap";
+
+    /// Index of the account covering `sequence`. Used as a seed.
+    pub fn index(sequence: u64) -> u64 {
+        sequence / Self::SEQUENCES_PER_ACCOUNT
+    }
+
+    fn position(sequence: u64) -> (usize, u8) {
+        let bit = sequence % Self::SEQUENCES_PER_ACCOUNT;
+        ((bit / 8) as usize, 1 << (bit % 8))
+    }
+
+    pub fn is_received(&self, sequence: u64) -> bool {
+        let (byte, mask) = Self::position(sequence);
+        self.bits[byte] & mask != 0
+    }
+
+    /// Mark message (`chain`, `sequence`) as redeemed. Fails if it already was.
+    pub fn mark_received(&mut self, chain: u16, sequence: u64) -> Result<()> {
+        require!(!self.is_received(sequence), ErrorFactory::AlreadyReceived);
+
+        // Only needed on a new account, but cheap.
+        self.chain = chain;
+        self.first_sequence = Self::index(sequence) * Self::SEQUENCES_PER_ACCOUNT;
+
+        let (byte, mask) = Self::position(sequence);
+        self.bits[byte] |= mask;
+        Ok(())
+    }
+}
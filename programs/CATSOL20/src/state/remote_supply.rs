@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+/// Remote supply account. Total supply of the deployment on a registered
+/// emitter chain, from the latest guardian-signed query response.
+pub struct RemoteSupply {
+    /// Chain of the deployment.
+    pub chain: u64,
+    /// Total supply, in this token's decimals. Saturates at `u64::MAX`.
+    pub supply: u64,
+    /// Decimals of the deployment.
+    pub decimals: u8,
+    /// Block the supply was read at.
+    pub block_number: u64,
+    /// Time of that block, in seconds. Older responses are rejected.
+    pub block_time: i64,
+}
+
+impl RemoteSupply {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // chain
+        + 8 // supply
+        + 1 // decimals
+        + 8 // block_number
+        + 8 // block_time
+    ;
+    /// AKA `b"remote_supply"`.
+    pub const SEED_PREFIX: &'static [u8; 13] = b"remote_supply";
+}
@@ -1,4 +1,9 @@
 pub mod utils_cat {
+    use anchor_lang::prelude::*;
+    use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+
+    use crate::error::ErrorFactory;
+
     pub fn normalize_amount(amount: u64, decimals: u8) -> u64 {
         if decimals > 8 {
             amount / 10u64.pow((decimals - 8).into())
@@ -14,4 +19,73 @@ pub mod utils_cat {
             amount
         }
     }
+
+    /// ABI encoded uint256 `supply` with `from_decimals` in `to_decimals`.
+    /// Saturates at `u64::MAX`, so an oversized remote supply still fails
+    /// reconciliation instead of being rejected.
+    pub fn scale_supply(supply: &[u8; 32], from_decimals: u8, to_decimals: u8) -> u64 {
+        let value = if supply[..16].iter().any(|byte| *byte != 0) {
+            u128::MAX
+        } else {
+            u128::from_be_bytes(supply[16..].try_into().unwrap())
+        };
+        let scaled = if value == 0 {
+            0
+        } else if from_decimals >= to_decimals {
+            10u128
+                .checked_pow((from_decimals - to_decimals).into())
+                .map_or(0, |factor| value / factor)
+        } else {
+            10u128
+                .checked_pow((to_decimals - from_decimals).into())
+                .and_then(|factor| value.checked_mul(factor))
+                .unwrap_or(u128::MAX)
+        };
+        u64::try_from(scaled).unwrap_or(u64::MAX)
+    }
+
+    /// Grow `account` to `space` bytes, zeroing the new bytes. `payer` tops
+    /// up its rent. Used to migrate accounts to a newer layout.
+    pub fn grow_account<'info>(
+        account: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        space: usize,
+    ) -> Result<()> {
+        if account.data_len() >= space {
+            return Ok(());
+        }
+
+        let rent = Rent::get()?.minimum_balance(space);
+        if rent > account.lamports() {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, rent - account.lamports())?;
+        }
+        account.realloc(space, true).map_err(Into::into)
+    }
+
+    /// `value` as an attested symbol or name: UTF-8, cut to 32 bytes and
+    /// right-padded with zeros. Metaplex's own zero padding is dropped first.
+    pub fn attest_bytes(value: &str) -> [u8; 32] {
+        let value = value.trim_end_matches('\0').as_bytes();
+        let len = value.len().min(32);
+        let mut bytes = [0u8; 32];
+        bytes[..len].copy_from_slice(&value[..len]);
+        bytes
+    }
+
+    /// Attested `(symbol, name)` of a Metaplex metadata account. Both are zero
+    /// if the mint has no metadata.
+    pub fn metadata_attest_bytes(metadata: &AccountInfo) -> Result<([u8; 32], [u8; 32])> {
+        if metadata.data_is_empty() {
+            return Ok(([0u8; 32], [0u8; 32]));
+        }
+        let metadata = Metadata::safe_deserialize(&metadata.try_borrow_data()?)
+            .map_err(|_| ErrorFactory::InvalidMetadata)?;
+        Ok((attest_bytes(&metadata.data.symbol), attest_bytes(&metadata.data.name)))
+    }
 }
@@ -0,0 +1,1856 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use cat_sol20::{
+    cat_chain_id, utils_cat::*, BridgeOut, BridgeOutParams, CATSOLStructs, Config,
+    CrossChainStruct, ErrorFactory, ForeignAttestation, ForeignEmitter, OutboundStatus,
+    OutboundTransfer, ReceivePayload, ReceivedBitmap, RecipientType, Refunded, RemoteSupply,
+    SupplyExceeded, CONVENTIONAL_SOLANA_ID, HELLO_VERSION, REMOTE_SUPPLY_MAX_AGE,
+    REUSABLE_MESSAGE_DELAY, U256,
+};
+use common::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+};
+use wormhole_anchor_sdk::wormhole;
+use wormhole_test_guardian::core_bridge::{
+    self, CONSISTENCY_LEVEL_CONFIRMED, CONSISTENCY_LEVEL_FINALIZED,
+};
+
+const ONE: u64 = 1_000_000_000;
+
+#[tokio::test]
+async fn initialize_sets_up_config_mint_and_hello_message() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.owner, owner);
+    assert_eq!(config.max_supply, MAX_SUPPLY);
+    assert_eq!(config.minted_supply, 0);
+    assert_eq!(config.batch_id, 0);
+    assert_eq!(config.finality, wormhole::Finality::Confirmed as u8);
+    assert_eq!(config.finalized_threshold, 0);
+    assert_eq!(config.wormhole.sequence, sequence_address());
+
+    let mint: anchor_spl::token::Mint = account(&mut context, mint_address()).await;
+    assert_eq!(mint.decimals, DECIMALS);
+    assert_eq!(mint.supply, 0);
+    assert_eq!(mint.mint_authority, Some(mint_address()).into());
+
+    let metadata = context
+        .banks_client
+        .get_account(metadata_address())
+        .await
+        .unwrap();
+    assert!(metadata.is_some());
+
+    let hello = CATSOLStructs::Hello {
+        emitter: emitter_address().to_bytes(),
+        chain: U256::from(CONVENTIONAL_SOLANA_ID),
+        decimals: DECIMALS,
+        version: HELLO_VERSION,
+    };
+    assert_eq!(posted_message(&mut context, 1).await, hello);
+}
+
+#[tokio::test]
+async fn mint_tokens_respects_max_supply() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+
+    process(
+        &mut context,
+        &[mint_tokens_ix(&owner, &owner, MAX_SUPPLY - 1)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let result = process(&mut context, &[mint_tokens_ix(&owner, &owner, 2)], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidMintAmount);
+
+    process(&mut context, &[mint_tokens_ix(&owner, &owner, 1)], &[])
+        .await
+        .unwrap();
+
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.minted_supply, MAX_SUPPLY);
+    assert_eq!(mint_supply(&mut context).await, MAX_SUPPLY);
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, MAX_SUPPLY);
+}
+
+#[tokio::test]
+async fn mint_tokens_is_owner_only() {
+    let (mut context, _) = start().await;
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+
+    let ix = mint_tokens_ix(&intruder.pubkey(), &intruder.pubkey(), 1);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+}
+
+#[tokio::test]
+async fn transfer_ownership_hands_over_owner_only_instructions() {
+    let (mut context, _) = start().await;
+    let old_owner = context.payer.pubkey();
+    let new_owner = funded_keypair(&mut context, 1_000_000_000).await;
+
+    let ix = transfer_ownership_ix(&old_owner, &old_owner);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyOwner);
+
+    let ix = transfer_ownership_ix(&old_owner, &new_owner.pubkey());
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.owner, new_owner.pubkey());
+
+    let ix = mint_tokens_ix(&old_owner, &old_owner, 1);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = transfer_ownership_ix(&old_owner, &old_owner);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = mint_tokens_ix(&new_owner.pubkey(), &new_owner.pubkey(), 1);
+    process(&mut context, &[ix], &[&new_owner]).await.unwrap();
+}
+
+#[tokio::test]
+async fn register_emitter_validates_chain_and_address() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+
+    // Unknown to the chain registry, Solana's, a zero address, and chain 2
+    // with bits above the u16 Wormhole chain ID set.
+    for (chain, address) in [
+        (0, [1; 32]),
+        (3, [1; 32]),
+        (1, [1; 32]),
+        (2, [0; 32]),
+        (1 << 16 | 2, [1; 32]),
+    ] {
+        let ix = register_emitter_ix(&owner, chain, address);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_program_error(result, ErrorFactory::InvalidForeignEmitter);
+    }
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = register_emitter_ix(&intruder.pubkey(), 2, [1; 32]);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    // Registering again replaces the emitter for that chain.
+    for address in [[1; 32], FOREIGN_EMITTER] {
+        let ix = register_emitter_ix(&owner, 2, address);
+        process(&mut context, &[ix], &[]).await.unwrap();
+    }
+
+    let emitter: ForeignEmitter = account(&mut context, foreign_emitter_address(2)).await;
+    assert_eq!(emitter.chain, 2);
+    assert_eq!(emitter.address, FOREIGN_EMITTER);
+
+    // The chain is listed once.
+    let ix = register_emitter_ix(&owner, 4, [1; 32]);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.emitter_chains, [2, 4]);
+}
+
+#[tokio::test]
+async fn bridge_in_mints_denormalized_amount() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_body(0, 123_456_789, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let events = process_redeemed(&mut context, &[ix], &[]).await.unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, ata(&recipient)).await,
+        1_234_567_890
+    );
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].emitter_chain, FOREIGN_CHAIN);
+    assert_eq!(events[0].sequence, 0);
+    assert_eq!(events[0].vaa_hash, body.body_hash());
+    assert_eq!(events[0].amount, 1_234_567_890);
+    assert_eq!(events[0].recipient, ata(&recipient));
+
+    // The received bitmap alone keeps the message from being replayed.
+    assert!(is_received(&mut context, body.emitter_chain, body.sequence).await);
+    assert!(context
+        .banks_client
+        .get_account(received_address(body.emitter_chain, body.sequence))
+        .await
+        .unwrap()
+        .is_none());
+
+    // Each bitmap account covers its own range of sequences.
+    let sequence = ReceivedBitmap::SEQUENCES_PER_ACCOUNT + 1;
+    let body = transfer_body(sequence, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let bitmap: ReceivedBitmap = account(
+        &mut context,
+        received_bitmap_address(FOREIGN_CHAIN, sequence),
+    )
+    .await;
+    assert_eq!(bitmap.chain, FOREIGN_CHAIN);
+    assert_eq!(bitmap.first_sequence, ReceivedBitmap::SEQUENCES_PER_ACCOUNT);
+    assert!(bitmap.is_received(sequence));
+    assert!(!bitmap.is_received(sequence - 1));
+    assert!(is_received(&mut context, FOREIGN_CHAIN, 0).await);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_replay() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    process(&mut context, std::slice::from_ref(&ix), &[])
+        .await
+        .unwrap();
+
+    // The bit for (chain, sequence) is already set.
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+
+    // A different VAA reusing the sequence is rejected as well.
+    let body = transfer_body(0, 200, &recipient, CONVENTIONAL_SOLANA_ID).with_nonce(1);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+
+    assert_eq!(token_balance(&mut context, ata(&recipient)).await, 1_000);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_wrong_destination_chain() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Wormhole's chain ID for Solana, not its CAT chain ID.
+    let body = transfer_body(0, 100, &recipient, wormhole::CHAIN_ID_SOLANA.into());
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidDestinationChain);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_mismatched_ata() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+    let thief = Pubkey::new_unique();
+
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    let ix = bridge_in_ix(&owner, &thief, &ata(&thief), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::MisMatchdATAAddress);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_unregistered_emitter() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), [1; 32]);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidForeignEmitter);
+}
+
+#[tokio::test]
+async fn bridge_out_costs_its_estimate() {
+    let (mut context, wormhole) = start_with_fee(5_000).await;
+    let owner = context.payer.pubkey();
+    let sender = funded_keypair(&mut context, ONE).await;
+    process(
+        &mut context,
+        &[mint_tokens_ix(&owner, &sender.pubkey(), 5 * ONE)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let bridge = wormhole.bridge_data(&mut context).await;
+    let fee_collector = core_bridge::fee_collector_address();
+    let collector_lamports = context
+        .banks_client
+        .get_balance(fee_collector)
+        .await
+        .unwrap();
+    let data = vec![7u8; 40];
+    let estimate = BridgeOut::estimate_cost(&bridge, collector_lamports, &rent, Some(&data));
+    assert_eq!(estimate.fee, 5_000);
+
+    let before = context
+        .banks_client
+        .get_balance(sender.pubkey())
+        .await
+        .unwrap();
+    let params = BridgeOutParams {
+        amount: ONE,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    let ix = bridge_out_with_payload_ix(&sender.pubkey(), 1, params, data);
+    process(&mut context, &[ix], &[&sender]).await.unwrap();
+
+    let after = context
+        .banks_client
+        .get_balance(sender.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(before - after, estimate.total());
+}
+
+#[tokio::test]
+async fn bridge_out_burns_and_posts_normalized_amount() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+
+    process(
+        &mut context,
+        &[mint_tokens_ix(&owner, &owner, 5_000_000_000)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let params = BridgeOutParams {
+        amount: 1_234_567_891,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    process(&mut context, &[bridge_out_ix(&owner, 1, params)], &[])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, ata(&owner)).await,
+        3_765_432_109
+    );
+    assert_eq!(mint_supply(&mut context).await, 3_765_432_109);
+
+    let payload = match posted_message(&mut context, 2).await {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => panic!("expected CrossChainPayload"),
+    };
+    assert_eq!(Into::<u64>::into(payload.amount), 123_456_789);
+    assert_eq!(payload.token_decimals, DECIMALS);
+    assert_eq!(payload.source_token_address, emitter_address().to_bytes());
+    assert_eq!(payload.source_user_address, owner.to_bytes());
+    assert_eq!(
+        Into::<u64>::into(payload.dest_token_chain),
+        FOREIGN_CAT_CHAIN
+    );
+    assert_eq!(payload.dest_user_address, [0xee; 32]);
+    assert_eq!(payload.dest_token_address, FOREIGN_EMITTER);
+}
+
+#[tokio::test]
+async fn bridge_out_requires_registered_recipient_chain() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+
+    process(&mut context, &[mint_tokens_ix(&owner, &owner, 100)], &[])
+        .await
+        .unwrap();
+
+    let params = BridgeOutParams {
+        amount: 100,
+        recipient_chain: 5,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    let result = process(&mut context, &[bridge_out_ix(&owner, 1, params)], &[]).await;
+    assert!(result.is_err());
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, 100);
+}
+
+#[tokio::test]
+async fn bridge_out_then_in_round_trips_up_to_dust() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+
+    let sent = 1_000_000_007;
+    process(&mut context, &[mint_tokens_ix(&owner, &owner, sent)], &[])
+        .await
+        .unwrap();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let params = BridgeOutParams {
+        amount: sent,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: owner.to_bytes(),
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    process(&mut context, &[bridge_out_ix(&owner, 1, params)], &[])
+        .await
+        .unwrap();
+
+    // Send the normalized amount straight back.
+    let amount: u64 = match posted_message(&mut context, 2).await {
+        CATSOLStructs::CrossChainPayload { payload } => payload.amount.into(),
+        _ => panic!("expected CrossChainPayload"),
+    };
+    let body = transfer_body(0, amount, &owner, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&owner, &owner, &ata(&owner), &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let received = token_balance(&mut context, ata(&owner)).await;
+    assert_eq!(received, 1_000_000_000);
+    assert_eq!(mint_supply(&mut context).await, received);
+}
+
+#[test]
+fn normalize_then_denormalize_only_drops_dust() {
+    let amounts = [
+        0,
+        1,
+        9,
+        10,
+        99,
+        12_345_678_901,
+        u64::MAX / 1_000_000_000_000,
+    ];
+    for decimals in 0..=18u8 {
+        let dust = 10u64.pow(decimals.saturating_sub(8).into());
+        for amount in amounts {
+            let round_trip = denormalize_amount(normalize_amount(amount, decimals), decimals);
+            assert!(round_trip <= amount);
+            assert!(amount - round_trip < dust);
+            assert_eq!(round_trip % dust, 0);
+        }
+    }
+}
+
+/// Mint `amount` to the owner and bridge all of it out to `FOREIGN_CHAIN` as
+/// outbound sequence 1. Returns the posted transfer.
+async fn bridge_out_to_foreign_chain(
+    context: &mut solana_program_test::ProgramTestContext,
+    amount: u64,
+) -> CrossChainStruct {
+    let owner = context.payer.pubkey();
+    process(context, &[mint_tokens_ix(&owner, &owner, amount)], &[])
+        .await
+        .unwrap();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(context, &[ix], &[]).await.unwrap();
+
+    let params = BridgeOutParams {
+        amount,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    process(context, &[bridge_out_ix(&owner, 1, params)], &[])
+        .await
+        .unwrap();
+
+    match posted_message(context, 2).await {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => panic!("expected CrossChainPayload"),
+    }
+}
+
+#[tokio::test]
+async fn refund_re_mints_to_original_sender_once() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payload = bridge_out_to_foreign_chain(&mut context, 1_234_567_891).await;
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, 0);
+
+    let body = refund_body(FOREIGN_CHAIN, 0, 1, payload.clone());
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[])
+        .await
+        .unwrap();
+
+    // Dust burned on the way out is not refunded.
+    assert_eq!(
+        token_balance(&mut context, ata(&owner)).await,
+        1_234_567_890
+    );
+    let refunded: Refunded = account(&mut context, refunded_address(1)).await;
+    assert_eq!(refunded.wormhole_message_hash, body.body_hash());
+
+    // A second refund message for the same transfer is rejected.
+    let body = refund_body(FOREIGN_CHAIN, 1, 1, payload);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let result = process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[]).await;
+    assert!(result.is_err());
+    assert_eq!(
+        token_balance(&mut context, ata(&owner)).await,
+        1_234_567_890
+    );
+}
+
+#[tokio::test]
+async fn refund_rejects_transfers_it_did_not_send() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payload = bridge_out_to_foreign_chain(&mut context, 1_000_000_000).await;
+
+    // Refunded by a chain the transfer did not go to.
+    let ix = register_emitter_ix(&owner, 4, FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let body = refund_body(4, 0, 1, payload.clone());
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let result = process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+
+    // Refund of a transfer sent by another program.
+    let mut foreign = payload.clone();
+    foreign.source_token_address = [0x42; 32];
+    let body = refund_body(FOREIGN_CHAIN, 1, 1, foreign);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let result = process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+
+    // Sequence in the instruction differs from the message.
+    let body = refund_body(FOREIGN_CHAIN, 2, 1, payload);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let result = process(&mut context, &[refund_ix(&owner, &owner, 7, &body)], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, 0);
+}
+
+#[tokio::test]
+async fn refund_must_match_the_outbound_transfer() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payload = bridge_out_to_foreign_chain(&mut context, 1_234_567_891).await;
+
+    // More than was sent.
+    let mut more = payload.clone();
+    more.amount = U256::from(123_456_790u64);
+    // From another wallet.
+    let other = Pubkey::new_unique();
+    let mut other_sender = payload.clone();
+    other_sender.source_user_address = other.to_bytes();
+    for (vaa_sequence, (sender, payload)) in [(&owner, more), (&other, other_sender)]
+        .into_iter()
+        .enumerate()
+    {
+        let body = refund_body(FOREIGN_CHAIN, vaa_sequence as u64, 1, payload);
+        wormhole.post_vaa(&mut context, &body).await.unwrap();
+        let ix = refund_ix(&owner, sender, 1, &body);
+        let result = process(&mut context, &[ix], &[]).await;
+        assert_program_error(result, ErrorFactory::OutboundTransferMismatch);
+    }
+
+    // Refunded by another chain the transfer claims it went to.
+    let ix = register_emitter_ix(&owner, 4, FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let mut elsewhere = payload.clone();
+    elsewhere.dest_token_chain = U256::from(1397u64);
+    let body = refund_body(4, 0, 1, elsewhere);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let result = process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[]).await;
+    assert_program_error(result, ErrorFactory::OutboundTransferMismatch);
+
+    // Leaving the record out does not skip the checks.
+    let body = refund_body(FOREIGN_CHAIN, 2, 1, payload);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = without_outbound_transfer(refund_ix(&owner, &owner, 1, &body), 1);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintSeeds.into()),
+    );
+    let outbound: OutboundTransfer = account(&mut context, outbound_transfer_address(1)).await;
+    assert_eq!(outbound.status, OutboundStatus::Pending);
+
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, 0);
+
+    // Transfers sent before outbound transfer accounts have none.
+    context.set_account(
+        &outbound_transfer_address(1),
+        &solana_sdk::account::AccountSharedData::default(),
+    );
+    let ix = refund_ix(&owner, &owner, 1, &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(
+        token_balance(&mut context, ata(&owner)).await,
+        1_234_567_890
+    );
+}
+
+#[tokio::test]
+async fn refund_only_pays_original_sender() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let thief = Pubkey::new_unique();
+    let payload = bridge_out_to_foreign_chain(&mut context, 1_000_000_000).await;
+
+    let body = refund_body(FOREIGN_CHAIN, 0, 1, payload);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let result = process(&mut context, &[refund_ix(&owner, &thief, 1, &body)], &[]).await;
+    assert_program_error(result, ErrorFactory::MisMatchdATAAddress);
+}
+
+#[tokio::test]
+async fn refunds_and_transfers_are_not_interchangeable() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payload = bridge_out_to_foreign_chain(&mut context, 1_000_000_000).await;
+
+    let refund = refund_body(FOREIGN_CHAIN, 0, 1, payload);
+    wormhole.post_vaa(&mut context, &refund).await.unwrap();
+    let ix = bridge_in_ix(&owner, &owner, &ata(&owner), &refund);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+
+    let transfer = transfer_body(1, 100, &owner, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &transfer).await.unwrap();
+    let ix = refund_ix(&owner, &owner, 1, &transfer);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+}
+
+#[tokio::test]
+async fn bridge_out_with_payload_posts_app_data() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+    process(
+        &mut context,
+        &[mint_tokens_ix(&owner, &owner, 1_000_000_000)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let params = BridgeOutParams {
+        amount: 1_000_000_000,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    let ix = bridge_out_with_payload_ix(&owner, 1, params, b"swap".to_vec());
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, 0);
+    match posted_message(&mut context, 2).await {
+        CATSOLStructs::TransferWithPayload { payload, data } => {
+            assert_eq!(Into::<u64>::into(payload.amount), 100_000_000);
+            assert_eq!(payload.dest_user_address, [0xee; 32]);
+            assert_eq!(data, b"swap");
+        }
+        _ => panic!("expected TransferWithPayload"),
+    }
+}
+
+#[tokio::test]
+async fn bridge_in_with_payload_delivers_to_recipient_program() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_with_payload_body(0, 12_345, &RECEIVER_ID, b"deposit");
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_with_payload_ix(&owner, &RECEIVER_ID, &body);
+    process(&mut context, std::slice::from_ref(&ix), &[])
+        .await
+        .unwrap();
+
+    assert_eq!(
+        token_balance(&mut context, ata(&redeemer_address())).await,
+        123_450
+    );
+    assert_eq!(
+        receipt(&mut context).await,
+        Some(ReceivePayload {
+            amount: 123_450,
+            source_chain: FOREIGN_CHAIN,
+            sender: [0xee; 32],
+            data: b"deposit".to_vec(),
+        })
+    );
+
+    // The bit for (chain, sequence) is already set.
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+}
+
+#[tokio::test]
+async fn bridge_in_with_payload_reverts_when_recipient_fails() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_with_payload_body(0, 12_345, &RECEIVER_ID, b"reject");
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_with_payload_ix(&owner, &RECEIVER_ID, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_instruction_error(result, InstructionError::Custom(RECEIVER_REJECTED));
+
+    assert_eq!(mint_supply(&mut context).await, 0);
+    assert_eq!(receipt(&mut context).await, None);
+}
+
+#[tokio::test]
+async fn bridge_in_with_payload_requires_named_recipient_program() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Named recipient is another program.
+    let body = transfer_with_payload_body(0, 12_345, &anchor_spl::token::ID, b"deposit");
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_with_payload_ix(&owner, &RECEIVER_ID, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRecipientProgram);
+
+    // Plain transfers go through `bridge_in` and vice versa.
+    let body = transfer_body(1, 12_345, &RECEIVER_ID, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_with_payload_ix(&owner, &RECEIVER_ID, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+
+    let body = transfer_with_payload_body(2, 12_345, &owner, b"deposit");
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&owner, &owner, &ata(&owner), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+}
+
+#[tokio::test]
+async fn bridge_in_to_token_account_mints_to_any_token_account() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // A vault owned by some program's PDA, not an ATA.
+    let vault_owner = Pubkey::find_program_address(&[b"vault"], &RECEIVER_ID).0;
+    let vault = create_token_account(&mut context, &mint_address(), &vault_owner).await;
+
+    let body = transfer_to_body(0, 100, &vault, RecipientType::TokenAccount);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_to_token_account_ix(&owner, &vault, &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    assert_eq!(token_balance(&mut context, vault).await, 1_000);
+}
+
+#[tokio::test]
+async fn bridge_in_honors_recipient_type() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Flagged wallets redeem like legacy transfers.
+    let recipient = Pubkey::new_unique();
+    let body = transfer_to_body(0, 100, &recipient, RecipientType::Wallet);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, ata(&recipient)).await, 1_000);
+
+    // Token accounts only redeem through `bridge_in_to_token_account`...
+    let vault = create_token_account(&mut context, &mint_address(), &recipient).await;
+    let body = transfer_to_body(1, 100, &vault, RecipientType::TokenAccount);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&owner, &vault, &ata(&vault), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRecipientType);
+
+    // ...and wallets never do.
+    let body = transfer_body(2, 100, &vault, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_to_token_account_ix(&owner, &vault, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRecipientType);
+    assert_eq!(token_balance(&mut context, vault).await, 0);
+}
+
+#[tokio::test]
+async fn bridge_in_to_token_account_validates_the_account() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let vault_owner = Keypair::new();
+
+    // Another token account than the one in the payload.
+    let vault = create_token_account(&mut context, &mint_address(), &vault_owner.pubkey()).await;
+    let other = create_token_account(&mut context, &mint_address(), &vault_owner.pubkey()).await;
+    let body = transfer_to_body(0, 100, &vault, RecipientType::TokenAccount);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_to_token_account_ix(&owner, &other, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::MisMatchdATAAddress);
+
+    // Another mint.
+    let mint = create_mint(&mut context, DECIMALS).await;
+    let wrong_mint = create_token_account(&mut context, &mint, &vault_owner.pubkey()).await;
+    let body = transfer_to_body(1, 100, &wrong_mint, RecipientType::TokenAccount);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_to_token_account_ix(&owner, &wrong_mint, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidTokenAccountMint);
+
+    // Frozen.
+    freeze(&mut context, &vault).await;
+    let body = transfer_to_body(2, 100, &vault, RecipientType::TokenAccount);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_to_token_account_ix(&owner, &vault, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidATAAccount);
+
+    // Closed.
+    close_token_account(&mut context, &other, &vault_owner).await;
+    let body = transfer_to_body(3, 100, &other, RecipientType::TokenAccount);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_to_token_account_ix(&owner, &other, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::AccountNotInitialized.into()),
+    );
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_messages_with_a_legacy_received_account() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Redeemed before the received bitmap existed.
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    set_legacy_received(&mut context, &body).await;
+
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    // Its bit was never set, but the legacy account is still there.
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+    assert_eq!(mint_supply(&mut context).await, 0);
+}
+
+#[tokio::test]
+async fn close_received_returns_rent_to_the_payer() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let relayer = funded_keypair(&mut context, 1_000_000_000).await;
+
+    // Redeemed just now: still within the retention period.
+    let now = unix_timestamp(&mut context).await;
+    let recent =
+        transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID).with_timestamp(now as u32);
+    wormhole.post_vaa(&mut context, &recent).await.unwrap();
+    let ix = bridge_in_ix(&relayer.pubkey(), &recipient, &ata(&recipient), &recent);
+    process(&mut context, &[ix], &[&relayer]).await.unwrap();
+    set_received(&mut context, &recent, &relayer.pubkey()).await;
+
+    let ix = close_received_ix(&relayer.pubkey(), &relayer.pubkey(), &recent);
+    let result = process(&mut context, &[ix], &[&relayer]).await;
+    assert_program_error(result, ErrorFactory::RetentionPeriodNotOver);
+
+    // Redeemed long ago.
+    let body = transfer_body(1, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = bridge_in_ix(&relayer.pubkey(), &recipient, &ata(&recipient), &body);
+    process(&mut context, &[ix], &[&relayer]).await.unwrap();
+    set_received(&mut context, &body, &relayer.pubkey()).await;
+    let address = received_address(body.emitter_chain, body.sequence);
+    let rent = context.banks_client.get_balance(address).await.unwrap();
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = close_received_ix(&intruder.pubkey(), &relayer.pubkey(), &body);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::PayerOrOwnerOnly);
+
+    // The rent only goes back to the payer.
+    let ix = close_received_ix(&owner, &owner, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRentRecipient);
+
+    // The payer closes its own account.
+    let before = context
+        .banks_client
+        .get_balance(relayer.pubkey())
+        .await
+        .unwrap();
+    let ix = close_received_ix(&relayer.pubkey(), &relayer.pubkey(), &body);
+    process(&mut context, &[ix], &[&relayer]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(relayer.pubkey())
+            .await
+            .unwrap(),
+        before + rent
+    );
+
+    // The message stays redeemed.
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+    assert_eq!(mint_supply(&mut context).await, 2_000);
+
+    // The owner closes it for the payer too.
+    let body = transfer_body(2, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    set_received(&mut context, &body, &relayer.pubkey()).await;
+    let ix = close_received_ix(&owner, &relayer.pubkey(), &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn close_legacy_received_reclaims_rent_after_retention_period() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+    let rent_recipient = Pubkey::new_unique();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Redeemed just now: still within the retention period.
+    let now = unix_timestamp(&mut context).await;
+    let recent =
+        transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID).with_timestamp(now as u32);
+    wormhole.post_vaa(&mut context, &recent).await.unwrap();
+    set_legacy_received(&mut context, &recent).await;
+
+    let ix = close_legacy_received_ix(&owner, &rent_recipient, &recent);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::RetentionPeriodNotOver);
+
+    // Redeemed long ago.
+    let body = transfer_body(1, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    set_legacy_received(&mut context, &body).await;
+    let legacy = received_address(body.emitter_chain, body.sequence);
+    let rent = context.banks_client.get_balance(legacy).await.unwrap();
+
+    // Legacy accounts do not load as `Received`.
+    let ix = close_received_ix(&owner, &rent_recipient, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into()),
+    );
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = close_legacy_received_ix(&intruder.pubkey(), &rent_recipient, &body);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = close_legacy_received_ix(&owner, &rent_recipient, &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    assert!(context
+        .banks_client
+        .get_account(legacy)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(rent_recipient)
+            .await
+            .unwrap(),
+        rent
+    );
+
+    // The message stays redeemed.
+    assert!(is_received(&mut context, body.emitter_chain, body.sequence).await);
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+    assert_eq!(mint_supply(&mut context).await, 0);
+
+    // Accounts that record their payer close through `close_received`.
+    let body = transfer_body(2, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    set_received(&mut context, &body, &owner).await;
+    let ix = close_legacy_received_ix(&owner, &rent_recipient, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::NotLegacyReceived);
+}
+
+#[tokio::test]
+async fn set_message_params_is_owner_only_and_validated() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = set_message_params_ix(&intruder.pubkey(), 7, 1, 0);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+    let ix = set_emitter_finality_ix(&intruder.pubkey(), FOREIGN_CHAIN.into(), Some(1));
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = set_message_params_ix(&owner, 7, 2, 0);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidFinality);
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(2));
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidFinality);
+
+    let ix = set_message_params_ix(&owner, 7, 1, 500);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.batch_id, 7);
+    assert_eq!(config.finality, wormhole::Finality::Finalized as u8);
+    assert_eq!(config.finalized_threshold, 500);
+
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(0));
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let emitter: ForeignEmitter =
+        account(&mut context, foreign_emitter_address(FOREIGN_CHAIN.into())).await;
+    assert_eq!(emitter.finality, Some(0));
+}
+
+#[tokio::test]
+async fn migrate_grows_config_and_emitters_from_earlier_layouts() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let before: Config = account(&mut context, config_address()).await;
+
+    // Accounts created before the fields appended since the first release.
+    let appended = 8 + 1 + 4 + 8 * Config::MAX_EMITTER_CHAINS;
+    truncate_account(
+        &mut context,
+        config_address(),
+        Config::MAXIMUM_SIZE - appended,
+    )
+    .await;
+    let emitter = foreign_emitter_address(FOREIGN_CHAIN.into());
+    truncate_account(&mut context, emitter, 8 + 8 + 32).await;
+
+    let not_deserialized =
+        || InstructionError::Custom(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    let ix = set_message_params_ix(&owner, 7, 1, 0);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_instruction_error(result, not_deserialized());
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = migrate_config_ix(&intruder.pubkey());
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = migrate_config_ix(&owner);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.owner, owner);
+    assert!(config.wormhole == before.wormhole);
+    assert_eq!(config.finality, before.finality);
+    assert_eq!(config.max_supply, MAX_SUPPLY);
+    assert!(!config.paused);
+    assert_eq!(config.finalized_threshold, 0);
+    assert!(config.emitter_chains.is_empty());
+
+    // Emitters still need their own migration.
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(1));
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_instruction_error(result, not_deserialized());
+
+    let ix = migrate_emitter_ix(&intruder.pubkey(), FOREIGN_CHAIN.into());
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = migrate_emitter_ix(&owner, FOREIGN_CHAIN.into());
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let foreign_emitter: ForeignEmitter = account(&mut context, emitter).await;
+    assert_eq!(foreign_emitter.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(foreign_emitter.address, FOREIGN_EMITTER);
+    assert_eq!(foreign_emitter.finality, None);
+    assert_eq!(foreign_emitter.max_vaa_age, 0);
+    // Its chain is listed again, for reconcile_supply.
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.emitter_chains, [u64::from(FOREIGN_CHAIN)]);
+
+    // Migrated accounts work again, and migrating them again changes nothing.
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(1));
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let ixs = [
+        migrate_config_ix(&owner),
+        migrate_emitter_ix(&owner, FOREIGN_CHAIN.into()),
+    ];
+    process(&mut context, &ixs, &[]).await.unwrap();
+    let foreign_emitter: ForeignEmitter = account(&mut context, emitter).await;
+    assert_eq!(foreign_emitter.finality, Some(1));
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.emitter_chains, [u64::from(FOREIGN_CHAIN)]);
+}
+
+#[tokio::test]
+async fn bridge_out_finality_follows_destination_and_amount() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = mint_tokens_ix(&owner, &owner, 10 * ONE);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    const HIGH_VALUE_CHAIN: u16 = 4;
+    for (chain, address) in [
+        (FOREIGN_CHAIN, FOREIGN_EMITTER),
+        (HIGH_VALUE_CHAIN, [3; 32]),
+    ] {
+        let ix = register_emitter_ix(&owner, chain.into(), address);
+        process(&mut context, &[ix], &[]).await.unwrap();
+    }
+    let ixs = [
+        set_message_params_ix(&owner, 7, wormhole::Finality::Confirmed as u8, ONE),
+        set_emitter_finality_ix(
+            &owner,
+            HIGH_VALUE_CHAIN.into(),
+            Some(wormhole::Finality::Finalized as u8),
+        ),
+    ];
+    process(&mut context, &ixs, &[]).await.unwrap();
+
+    // (destination, amount, expected consistency level)
+    let transfers = [
+        (FOREIGN_CHAIN, ONE - 1, CONSISTENCY_LEVEL_CONFIRMED),
+        (HIGH_VALUE_CHAIN, ONE - 1, CONSISTENCY_LEVEL_FINALIZED),
+        (FOREIGN_CHAIN, ONE, CONSISTENCY_LEVEL_FINALIZED),
+    ];
+    for (sequence, (chain, amount, consistency_level)) in (1..).zip(transfers) {
+        let params = BridgeOutParams {
+            amount,
+            recipient_chain: cat_chain_id(chain).unwrap(),
+            recipient: [0xee; 32],
+            recipient_contract: [0xcc; 32],
+        };
+        process(
+            &mut context,
+            &[bridge_out_ix(&owner, sequence, params)],
+            &[],
+        )
+        .await
+        .unwrap();
+
+        let message = posted(&mut context, sequence + 1).await;
+        assert_eq!(message.finality(), consistency_level);
+        assert_eq!(message.batch_id(), 7);
+    }
+}
+
+#[tokio::test]
+async fn bridge_in_enforces_emitter_age_and_consistency_policy() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let intruder = funded_keypair(&mut context, ONE).await;
+    let ix = set_emitter_policy_ix(
+        &intruder.pubkey(),
+        FOREIGN_CHAIN.into(),
+        3_600,
+        &[EVM_FINALIZED],
+    );
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let levels = [0; ForeignEmitter::MAX_CONSISTENCY_LEVELS + 1];
+    let ix = set_emitter_policy_ix(&owner, FOREIGN_CHAIN.into(), 3_600, &levels);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::TooManyConsistencyLevels);
+
+    // Finalized only.
+    let ix = set_emitter_policy_ix(&owner, FOREIGN_CHAIN.into(), 3_600, &[EVM_FINALIZED]);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let emitter: ForeignEmitter =
+        account(&mut context, foreign_emitter_address(FOREIGN_CHAIN.into())).await;
+    assert_eq!(emitter.max_vaa_age, 3_600);
+    assert_eq!(emitter.consistency_levels, [EVM_FINALIZED]);
+
+    let now = unix_timestamp(&mut context).await;
+    // An instant message carries a higher level than a finalized one.
+    let instant = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+        .with_timestamp(now as u32)
+        .with_consistency_level(EVM_INSTANT);
+    // (message, expected error)
+    let messages = [
+        (
+            instant.clone(),
+            Some(ErrorFactory::InsufficientConsistencyLevel),
+        ),
+        (
+            transfer_body(1, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+                .with_timestamp((now - 3_601) as u32)
+                .with_consistency_level(EVM_FINALIZED),
+            Some(ErrorFactory::StaleVaa),
+        ),
+        (
+            transfer_body(2, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+                .with_timestamp((now - 3_000) as u32)
+                .with_consistency_level(EVM_FINALIZED),
+            None,
+        ),
+    ];
+    for (body, error) in messages {
+        wormhole.post_vaa(&mut context, &body).await.unwrap();
+        let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+        let result = process(&mut context, &[ix], &[]).await;
+        match error {
+            Some(error) => assert_program_error(result, error),
+            None => result.unwrap(),
+        }
+    }
+
+    // Instant or stronger. A finalized message carries a lower level than
+    // an instant one.
+    let ix = set_emitter_policy_ix(
+        &owner,
+        FOREIGN_CHAIN.into(),
+        3_600,
+        &[EVM_INSTANT, EVM_SAFE, EVM_FINALIZED],
+    );
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let finalized = transfer_body(3, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+        .with_timestamp(now as u32)
+        .with_consistency_level(EVM_FINALIZED);
+    wormhole.post_vaa(&mut context, &finalized).await.unwrap();
+    for body in [&finalized, &instant] {
+        let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), body);
+        process(&mut context, &[ix], &[]).await.unwrap();
+    }
+    assert_eq!(token_balance(&mut context, ata(&recipient)).await, 3_000);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_vaa_hash_not_matching_posted_vaa() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let recipient = Pubkey::new_unique();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+
+    // A posted account found through a hash its contents don't have.
+    let forged_hash = [0x42; 32];
+    let forged = repost_under_hash(&mut context, &body, forged_hash).await;
+    let mut ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    ix.data[8..40].copy_from_slice(&forged_hash);
+    for meta in &mut ix.accounts {
+        if meta.pubkey == body.posted_vaa_address() {
+            meta.pubkey = forged;
+        }
+    }
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidVaaHash);
+
+    let ix = bridge_in_ix(&owner, &recipient, &ata(&recipient), &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(token_balance(&mut context, ata(&recipient)).await, 1_000);
+}
+
+#[tokio::test]
+async fn update_metadata_is_owner_only_until_made_immutable() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+    let creator = Pubkey::new_unique();
+    let collection = Pubkey::new_unique();
+    let params = cat_sol20::UpdateMetadataParams {
+        name: "CAT Token v2".to_string(),
+        symbol: "CAT2".to_string(),
+        uri: "https://example.com/cat-v2.json".to_string(),
+        creators: Some(vec![cat_sol20::MetadataCreator {
+            address: creator,
+            share: 100,
+        }]),
+        collection: Some(collection),
+    };
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = update_metadata_ix(&intruder.pubkey(), params.clone());
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+    let ix = set_metadata_immutable_ix(&intruder.pubkey());
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = update_metadata_ix(&owner, params.clone());
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let updated = metadata(&mut context).await;
+    assert_eq!(updated.update_authority, mint_address());
+    assert!(updated.data.name.starts_with("CAT Token v2"));
+    assert!(updated.data.symbol.starts_with("CAT2"));
+    assert!(updated
+        .data
+        .uri
+        .starts_with("https://example.com/cat-v2.json"));
+    let creators = updated.data.creators.unwrap();
+    assert_eq!(creators.len(), 1);
+    assert_eq!(creators[0].address, creator);
+    assert!(!creators[0].verified);
+    assert_eq!(updated.collection.unwrap().key, collection);
+    assert!(updated.is_mutable);
+
+    let ix = set_metadata_immutable_ix(&owner);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert!(!metadata(&mut context).await.is_mutable);
+
+    let ix = update_metadata_ix(&owner, params);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+}
+
+#[tokio::test]
+async fn attest_token_posts_mint_decimals_and_metadata() {
+    let (mut context, _) = start().await;
+    let payer = context.payer.pubkey();
+
+    process(&mut context, &[attest_token_ix(&payer, 1)], &[])
+        .await
+        .unwrap();
+
+    let expected = CATSOLStructs::Attest {
+        token_address: mint_address().to_bytes(),
+        token_chain: U256::from(CONVENTIONAL_SOLANA_ID),
+        decimals: DECIMALS,
+        symbol: attest_bytes("CAT"),
+        name: attest_bytes("CAT Token"),
+    };
+    assert_eq!(posted_message(&mut context, 2).await, expected);
+}
+
+#[tokio::test]
+async fn receive_attestation_checks_foreign_deployment() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+
+    // Only registered emitters can attest.
+    let body = attest_body(5, "CAT", "CAT Token");
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = receive_attestation_ix(&owner, &body);
+    assert!(process(&mut context, &[ix], &[]).await.is_err());
+
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let mismatched = attest_body(4, "DOG", "CAT Token");
+    wormhole.post_vaa(&mut context, &mismatched).await.unwrap();
+    let ix = receive_attestation_ix(&owner, &mismatched);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AttestationMismatch);
+
+    let ix = receive_attestation_ix(&owner, &body);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let attestation: ForeignAttestation = account(
+        &mut context,
+        foreign_attestation_address(FOREIGN_CHAIN.into()),
+    )
+    .await;
+    assert_eq!(attestation.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(attestation.sequence, 5);
+    assert_eq!(attestation.token_address, [0xab; 32]);
+    assert_eq!(attestation.decimals, 18);
+    assert_eq!(attestation.symbol, attest_bytes("CAT"));
+    assert_eq!(attestation.name, attest_bytes("CAT Token"));
+
+    // Older attestations, and the same one again, are rejected.
+    let older = attest_body(3, "CAT", "CAT Token");
+    wormhole.post_vaa(&mut context, &older).await.unwrap();
+    let ix = receive_attestation_ix(&owner, &older);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::StaleAttestation);
+    let ix = receive_attestation_ix(&owner, &body);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::StaleAttestation);
+
+    // Transfers are not attestations.
+    let transfer = transfer_body(6, 100, &owner, CONVENTIONAL_SOLANA_ID);
+    wormhole.post_vaa(&mut context, &transfer).await.unwrap();
+    let ix = receive_attestation_ix(&owner, &transfer);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+}
+
+#[tokio::test]
+async fn post_hello_is_owner_only() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = post_hello_ix(&intruder.pubkey(), 1);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    process(&mut context, &[post_hello_ix(&owner, 1)], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        posted_message(&mut context, 2).await,
+        posted_message(&mut context, 1).await
+    );
+}
+
+#[tokio::test]
+async fn receive_hello_registers_approved_emitters_only() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payer = funded_keypair(&mut context, 1_000_000_000).await;
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = approve_emitter_ix(&intruder.pubkey(), FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+    let ix = approve_emitter_ix(&owner, 1, FOREIGN_EMITTER);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidForeignEmitter);
+
+    // Nothing approved yet.
+    let body = hello_body(0, FOREIGN_EMITTER, FOREIGN_EMITTER);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &body);
+    assert!(process(&mut context, &[ix], &[&payer]).await.is_err());
+
+    let ix = approve_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Another emitter on the approved chain.
+    let other = hello_body(1, [0xcb; 32], [0xcb; 32]);
+    wormhole.post_vaa(&mut context, &other).await.unwrap();
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &other);
+    let result = process(&mut context, &[ix], &[&payer]).await;
+    assert_program_error(result, ErrorFactory::EmitterNotApproved);
+
+    // The approved emitter naming someone else.
+    let misnamed = hello_body(2, FOREIGN_EMITTER, [0xcb; 32]);
+    wormhole.post_vaa(&mut context, &misnamed).await.unwrap();
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &misnamed);
+    let result = process(&mut context, &[ix], &[&payer]).await;
+    assert_program_error(result, ErrorFactory::InvalidHello);
+
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &body);
+    process(&mut context, &[ix], &[&payer]).await.unwrap();
+    let emitter: ForeignEmitter =
+        account(&mut context, foreign_emitter_address(FOREIGN_CHAIN.into())).await;
+    assert_eq!(emitter.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(emitter.address, FOREIGN_EMITTER);
+    let config: Config = account(&mut context, config_address()).await;
+    assert_eq!(config.emitter_chains, [u64::from(FOREIGN_CHAIN)]);
+
+    // The approval is used up.
+    let approval = context
+        .banks_client
+        .get_account(approved_emitter_address(FOREIGN_CHAIN.into()))
+        .await
+        .unwrap();
+    assert!(approval.is_none());
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &body);
+    assert!(process(&mut context, &[ix], &[&payer]).await.is_err());
+}
+
+#[tokio::test]
+async fn outbound_transfers_are_indexed_by_sequence_until_refunded() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payload = bridge_out_to_foreign_chain(&mut context, 1_234_567_891).await;
+
+    let outbound: OutboundTransfer = account(&mut context, outbound_transfer_address(1)).await;
+    assert_eq!(outbound.sender, owner);
+    assert_eq!(outbound.status, OutboundStatus::Pending);
+    assert_eq!(outbound.sequence, 1);
+    assert_eq!(outbound.amount, 1_234_567_891);
+    assert_eq!(outbound.recipient_chain, FOREIGN_CAT_CHAIN);
+    assert_eq!(outbound.recipient, [0xee; 32]);
+    assert!(outbound.timestamp > 0);
+
+    // The pending filters match the account, and stop matching once it is
+    // refunded.
+    let matches = |data: &[u8], sender: &Pubkey| {
+        OutboundTransfer::pending_filters(sender)
+            .iter()
+            .all(|(offset, bytes)| data[*offset..].starts_with(bytes))
+    };
+    let data = context
+        .banks_client
+        .get_account(outbound_transfer_address(1))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(matches(&data, &owner));
+    assert!(!matches(&data, &Pubkey::new_unique()));
+
+    let body = refund_body(FOREIGN_CHAIN, 0, 1, payload);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[])
+        .await
+        .unwrap();
+
+    let outbound: OutboundTransfer = account(&mut context, outbound_transfer_address(1)).await;
+    assert_eq!(outbound.status, OutboundStatus::Refunded);
+    let data = context
+        .banks_client
+        .get_account(outbound_transfer_address(1))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(!matches(&data, &owner));
+}
+
+#[tokio::test]
+async fn close_outbound_transfer_returns_rent_once_settled() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let payload = bridge_out_to_foreign_chain(&mut context, ONE).await;
+    let refunded = outbound_transfer_address(1);
+    let outbound: OutboundTransfer = account(&mut context, refunded).await;
+    assert_eq!(outbound.payer, owner);
+
+    // Pending: kept for the retention period.
+    let ix = close_outbound_transfer_ix(&owner, &owner, &refunded);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::OutboundTransferNotSettled);
+
+    // Refunded: closed at once, by its payer or the owner.
+    let body = refund_body(FOREIGN_CHAIN, 0, 1, payload);
+    wormhole.post_vaa(&mut context, &body).await.unwrap();
+    process(&mut context, &[refund_ix(&owner, &owner, 1, &body)], &[])
+        .await
+        .unwrap();
+
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = close_outbound_transfer_ix(&intruder.pubkey(), &intruder.pubkey(), &refunded);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::PayerOrOwnerOnly);
+    let ix = close_outbound_transfer_ix(&owner, &intruder.pubkey(), &refunded);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRentRecipient);
+
+    let rent = context.banks_client.get_balance(refunded).await.unwrap();
+    let before = context.banks_client.get_balance(owner).await.unwrap();
+    let ix = close_outbound_transfer_ix(&owner, &owner, &refunded);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert!(context
+        .banks_client
+        .get_account(refunded)
+        .await
+        .unwrap()
+        .is_none());
+    // Less the transaction fee.
+    assert_eq!(
+        context.banks_client.get_balance(owner).await.unwrap(),
+        before + rent - 5_000
+    );
+
+    // Pending, after the retention period.
+    let params = BridgeOutParams {
+        amount: ONE,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+    process(&mut context, &[mint_tokens_ix(&owner, &owner, ONE)], &[])
+        .await
+        .unwrap();
+    process(&mut context, &[bridge_out_ix(&owner, 2, params)], &[])
+        .await
+        .unwrap();
+    let pending = outbound_transfer_address(2);
+    advance_clock(&mut context, OutboundTransfer::RETENTION_PERIOD).await;
+    let ix = close_outbound_transfer_ix(&owner, &owner, &pending);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    assert!(context
+        .banks_client
+        .get_account(pending)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn bridge_out_reusable_posts_every_transfer_to_one_account() {
+    let (mut context, _) = start().await;
+    let owner = context.payer.pubkey();
+    process(
+        &mut context,
+        &[mint_tokens_ix(&owner, &owner, 3 * ONE)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let params = BridgeOutParams {
+        amount: ONE,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    };
+
+    let message = reusable_message_address(&owner);
+    let ix = bridge_out_reusable_ix(&owner, 1, params.clone());
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let posted: wormhole::PostedVaaData = account(&mut context, message).await;
+    assert_eq!(posted.sequence(), 1);
+    assert!(matches!(
+        CATSOLStructs::try_from_slice(&posted.payload).unwrap(),
+        CATSOLStructs::CrossChainPayload { payload } if payload.source_user_address == owner.to_bytes()
+    ));
+    let rent = context.banks_client.get_balance(message).await.unwrap();
+
+    // Guardians may not have observed the first transfer yet.
+    let ix = bridge_out_reusable_ix(&owner, 2, params.clone());
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::ReusableMessageInUse);
+
+    advance_clock(&mut context, REUSABLE_MESSAGE_DELAY).await;
+    let ix = bridge_out_reusable_ix(&owner, 2, params);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // The second transfer overwrote the first, without a new message account.
+    let posted: wormhole::PostedVaaData = account(&mut context, message).await;
+    assert_eq!(posted.sequence(), 2);
+    assert_eq!(
+        context.banks_client.get_balance(message).await.unwrap(),
+        rent
+    );
+    // Both transfers are recorded.
+    for sequence in [1, 2] {
+        let outbound: OutboundTransfer =
+            account(&mut context, outbound_transfer_address(sequence)).await;
+        assert_eq!(outbound.sender, owner);
+        assert_eq!(outbound.status, OutboundStatus::Pending);
+        assert_eq!(outbound.sequence, sequence);
+        assert_eq!(outbound.amount, ONE);
+        assert_eq!(outbound.recipient_chain, FOREIGN_CAT_CHAIN);
+    }
+    assert_eq!(token_balance(&mut context, ata(&owner)).await, ONE);
+}
+
+#[tokio::test]
+async fn record_remote_supply_requires_a_guardian_quorum_and_the_emitter() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), EVM_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    let now = unix_timestamp(&mut context).await;
+    let response = supply_response(now, 500 * 10u128.pow(18), 18);
+    let guardian_set = &wormhole.guardian_set;
+
+    // Signatures of another response do not count.
+    let other = supply_response(now - 1, 1, 18);
+    let ixs = [
+        guardian_set.sign_query_response(&other, &[0], 0),
+        verify_query_signatures_ix(&owner, guardian_set.index, &response),
+    ];
+    let result = process(&mut context, &ixs, &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidQuerySignatures);
+
+    // Another payer collects signatures of the same response in its own
+    // account, which does not count towards the owner's.
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ixs = [
+        guardian_set.sign_query_response(&response, &[0], 0),
+        verify_query_signatures_ix(&intruder.pubkey(), guardian_set.index, &response),
+    ];
+    process(&mut context, &ixs, &[&intruder]).await.unwrap();
+
+    // One signature short of quorum, then the last one.
+    let signers = (0..guardian_set.quorum()).collect::<Vec<_>>();
+    verify_query_signatures(&mut context, guardian_set, &response, &signers[1..])
+        .await
+        .unwrap();
+    let ix = record_remote_supply_ix(&owner, guardian_set.index, FOREIGN_CHAIN, &response);
+    let result = process(&mut context, std::slice::from_ref(&ix), &[]).await;
+    assert_program_error(result, ErrorFactory::QueryQuorumNotReached);
+
+    verify_query_signatures(&mut context, guardian_set, &response, &signers[..1])
+        .await
+        .unwrap();
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // The supply is recorded in the token's decimals.
+    let remote: RemoteSupply =
+        account(&mut context, remote_supply_address(FOREIGN_CHAIN.into())).await;
+    assert_eq!(remote.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(remote.supply, 500 * 10u64.pow(DECIMALS.into()));
+    assert_eq!(remote.decimals, 18);
+    assert_eq!(remote.block_time, now);
+    assert!(context
+        .banks_client
+        .get_account(query_signatures_address(&response, &owner))
+        .await
+        .unwrap()
+        .is_none());
+
+    // The same response again is stale.
+    let result = record_remote_supply(&mut context, &wormhole, &response).await;
+    assert_program_error(result, ErrorFactory::StaleQueryResponse);
+
+    // Only the registered emitter's supply is accepted.
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let newer = supply_response(now + 1, 400 * 10u128.pow(18), 18);
+    let result = record_remote_supply(&mut context, &wormhole, &newer).await;
+    assert_program_error(result, ErrorFactory::InvalidQueryResponse);
+}
+
+#[tokio::test]
+async fn reconcile_supply_pauses_minting_while_global_supply_exceeds_max() {
+    let (mut context, wormhole) = start().await;
+    let owner = context.payer.pubkey();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), EVM_EMITTER);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let ix = mint_tokens_ix(&owner, &owner, MAX_SUPPLY / 2);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // The remote deployment holds more than the other half.
+    let scale = 10u128.pow((18 - DECIMALS).into());
+    let now = unix_timestamp(&mut context).await;
+    let response = supply_response(now - 10, u128::from(MAX_SUPPLY / 2 + 1) * scale, 18);
+    record_remote_supply(&mut context, &wormhole, &response)
+        .await
+        .unwrap();
+
+    let chains = [u64::from(FOREIGN_CHAIN)];
+    let intruder = funded_keypair(&mut context, 1_000_000_000).await;
+    let ix = reconcile_supply_ix(&intruder.pubkey(), &chains);
+    let result = process(&mut context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = reconcile_supply_ix(&owner, &chains);
+    let events: Vec<SupplyExceeded> = process_events(&mut context, &[ix], &[]).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].local_supply, MAX_SUPPLY / 2);
+    assert_eq!(events[0].remote_supply, MAX_SUPPLY / 2 + 1);
+    assert_eq!(events[0].max_supply, MAX_SUPPLY);
+    assert!(
+        account::<Config>(&mut context, config_address())
+            .await
+            .paused
+    );
+
+    let ix = mint_tokens_ix(&owner, &owner, 1);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::MintingPaused);
+
+    // Once the remote deployment burns, a reconciliation resumes minting.
+    let response = supply_response(now, u128::from(MAX_SUPPLY / 4) * scale, 18);
+    record_remote_supply(&mut context, &wormhole, &response)
+        .await
+        .unwrap();
+    let ix = reconcile_supply_ix(&owner, &chains);
+    let events: Vec<SupplyExceeded> = process_events(&mut context, &[ix], &[]).await.unwrap();
+    assert!(events.is_empty());
+    assert!(
+        !account::<Config>(&mut context, config_address())
+            .await
+            .paused
+    );
+    let ix = mint_tokens_ix(&owner, &owner, 1);
+    process(&mut context, &[ix], &[]).await.unwrap();
+
+    // Every registered emitter chain counts, only while its supply is recent.
+    let ix = reconcile_supply_ix(&owner, &[]);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::RemoteSupplyMismatch);
+
+    advance_clock(&mut context, REMOTE_SUPPLY_MAX_AGE + 1).await;
+    let ix = reconcile_supply_ix(&owner, &chains);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::StaleRemoteSupply);
+
+    // A chain registered since has no supply yet, and a chain counts once.
+    let now = unix_timestamp(&mut context).await;
+    let response = supply_response(now, u128::from(MAX_SUPPLY / 4) * scale, 18);
+    record_remote_supply(&mut context, &wormhole, &response)
+        .await
+        .unwrap();
+    let ix = register_emitter_ix(&owner, 4, [0x44; 32]);
+    process(&mut context, &[ix], &[]).await.unwrap();
+    let ix = reconcile_supply_ix(&owner, &chains);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::RemoteSupplyMismatch);
+    let ix = reconcile_supply_ix(&owner, &[chains[0], chains[0]]);
+    let result = process(&mut context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::DuplicateRemoteSupply);
+}
+
+#[test]
+fn scale_supply_saturates_instead_of_overflowing() {
+    let mut supply = [0u8; 32];
+    supply[24..].copy_from_slice(&1_500_000_000_000_000_000u64.to_be_bytes());
+    assert_eq!(scale_supply(&supply, 18, 9), 1_500_000_000);
+    assert_eq!(scale_supply(&supply, 18, 18), 1_500_000_000_000_000_000);
+    assert_eq!(scale_supply(&supply, 9, 18), u64::MAX);
+    assert_eq!(scale_supply(&[0xff; 32], 18, 9), u64::MAX);
+    assert_eq!(scale_supply(&[0; 32], 0, 255), 0);
+}
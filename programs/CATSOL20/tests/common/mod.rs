@@ -0,0 +1,1500 @@
+#![allow(dead_code)]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction, program_pack::Pack},
+    system_program, Discriminator, InstructionData,
+};
+use anchor_spl::{associated_token, token};
+use cat_sol20::{
+    utils_cat, wormhole_chain_id, ApprovedEmitter, CATSOLStructs, Config, CrossChainStruct,
+    ErrorFactory, ForeignAttestation, ForeignEmitter, InitializeParams, OutboundTransfer,
+    QuerySignatures, ReceivePayload, Received, ReceivedBitmap, RecipientType, Redeemed, Refunded,
+    RemoteSupply, WormholeEmitter, CONVENTIONAL_SOLANA_ID, DECIMALS_SELECTOR, SEED_PREFIX_MINT,
+    SEED_PREFIX_PAYLOAD_AUTHORITY, SEED_PREFIX_REDEEMER, SEED_PREFIX_REUSABLE, SEED_PREFIX_SENT,
+    TOTAL_SUPPLY_SELECTOR, U256,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    system_instruction, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use wormhole_anchor_sdk::wormhole::{self, program::ID as CORE_BRIDGE_ID, EthCallData};
+use wormhole_test_guardian::{
+    capture_log_data, eth_call_response, program_data, GuardianSet, GuardianSetData, LocalWormhole,
+    VaaBody, SIGNATURES_PER_TRANSACTION,
+};
+
+pub use wormhole_test_guardian::process;
+
+pub const DECIMALS: u8 = 9;
+pub const MAX_SUPPLY: u64 = 1_000_000_000_000_000;
+
+/// Foreign chain and emitter used by most tests.
+pub const FOREIGN_CHAIN: u16 = 2;
+/// CAT chain ID of `FOREIGN_CHAIN` (Ethereum devnet on Tilt).
+pub const FOREIGN_CAT_CHAIN: u64 = 1337;
+pub const FOREIGN_EMITTER: [u8; 32] = [0xca; 32];
+/// Consistency levels of an EVM `FOREIGN_CHAIN`.
+pub const EVM_INSTANT: u8 = 200;
+pub const EVM_SAFE: u8 = 201;
+pub const EVM_FINALIZED: u8 = 1;
+/// An emitter contract on an EVM chain: a left-padded 20-byte address.
+pub const EVM_EMITTER: [u8; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee,
+    0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee, 0xee,
+];
+
+/// Recipient program for transfers with payload, and the account it records
+/// each call in.
+pub const RECEIVER_ID: Pubkey = Pubkey::new_from_array([0x7e; 32]);
+pub const RECEIPT: Pubkey = Pubkey::new_from_array([0x7f; 32]);
+/// Error the receiver fails with when the payload is `b"reject"`.
+pub const RECEIVER_REJECTED: u32 = 0xdead;
+
+// Anchor's `entry` ties the accounts slice to the account infos' lifetime,
+// which `processor!` cannot express. Tests leak the (tiny) slice instead.
+fn process_cat_sol20(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    cat_sol20::entry(program_id, accounts, data)
+}
+
+fn process_metadata(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let program_id = Box::leak(Box::new(*program_id));
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    mpl_token_metadata::processor::process_instruction(program_id, accounts, data)
+}
+
+/// Stand-in recipient program. Checks that the bridge signed the call and
+/// already delivered the tokens, then writes the `ReceivePayload` it got into
+/// `RECEIPT`, its first extra account.
+fn process_receiver(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (discriminator, args) = data.split_at(8);
+    if discriminator != ReceivePayload::discriminator() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let args =
+        ReceivePayload::try_from_slice(args).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    if *accounts[0].key != payload_authority_address() || !accounts[0].is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let token_account = token::spl_token::state::Account::unpack(&accounts[2].data.borrow())?;
+    if token_account.owner != redeemer_address() || token_account.amount < args.amount {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    if args.data == b"reject" {
+        return Err(ProgramError::Custom(RECEIVER_REJECTED));
+    }
+
+    let mut receipt = accounts[4].try_borrow_mut_data()?;
+    args.serialize(&mut &mut receipt[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Program test with CATSOL20, Token Metadata and the local core bridge. SPL
+/// Token and the Associated Token program are built into `ProgramTest`.
+pub fn program_test() -> ProgramTest {
+    let mut program_test =
+        ProgramTest::new("cat_sol20", cat_sol20::ID, processor!(process_cat_sol20));
+    program_test.add_program(
+        "mpl_token_metadata",
+        mpl_token_metadata::id(),
+        processor!(process_metadata),
+    );
+    program_test.add_program("receiver", RECEIVER_ID, processor!(process_receiver));
+    program_test.add_account(
+        RECEIPT,
+        solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data: vec![0; 256],
+            owner: RECEIVER_ID,
+            ..Default::default()
+        },
+    );
+    LocalWormhole::add_program(&mut program_test);
+    program_test
+}
+
+/// Start a validator with the core bridge and CATSOL20 initialized. The
+/// context payer is the program owner.
+pub async fn start() -> (ProgramTestContext, LocalWormhole) {
+    start_with(LocalWormhole::new(GuardianSet::new(0, 19))).await
+}
+
+/// Same as [`start`], with a core bridge charging `fee` lamports per message.
+pub async fn start_with_fee(fee: u64) -> (ProgramTestContext, LocalWormhole) {
+    start_with(LocalWormhole::new(GuardianSet::new(0, 19)).with_fee(fee)).await
+}
+
+async fn start_with(wormhole: LocalWormhole) -> (ProgramTestContext, LocalWormhole) {
+    let mut context = program_test().start_with_context().await;
+    capture_log_data();
+    wormhole.initialize(&mut context).await.unwrap();
+
+    let owner = context.payer.pubkey();
+    process(
+        &mut context,
+        &[initialize_ix(&owner, initialize_params())],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    (context, wormhole)
+}
+
+pub fn initialize_params() -> InitializeParams {
+    InitializeParams {
+        decimals: DECIMALS,
+        max_supply: MAX_SUPPLY,
+        name: "CAT Token".to_string(),
+        symbol: "CAT".to_string(),
+        uri: "https://example.com/cat.json".to_string(),
+    }
+}
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[Config::SEED_PREFIX], &cat_sol20::ID).0
+}
+
+pub fn payload_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_PAYLOAD_AUTHORITY], &cat_sol20::ID).0
+}
+
+pub fn mint_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_MINT], &cat_sol20::ID).0
+}
+
+pub fn metadata_address() -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            mpl_token_metadata::id().as_ref(),
+            mint_address().as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+pub fn emitter_address() -> Pubkey {
+    Pubkey::find_program_address(&[WormholeEmitter::SEED_PREFIX], &cat_sol20::ID).0
+}
+
+pub fn sequence_address() -> Pubkey {
+    wormhole_test_guardian::core_bridge::sequence_address(&emitter_address())
+}
+
+/// Message account posted for `seed`. Note that the account for sequence `n`
+/// is seeded with `n + 1`.
+pub fn message_address(seed: u64) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_SENT, &seed.to_le_bytes()], &cat_sol20::ID).0
+}
+
+pub fn reusable_message_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_REUSABLE, owner.as_ref()], &cat_sol20::ID).0
+}
+
+pub fn foreign_emitter_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ForeignEmitter::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn foreign_attestation_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ForeignAttestation::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn remote_supply_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[RemoteSupply::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn query_signatures_address(response: &[u8], payer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            QuerySignatures::SEED_PREFIX,
+            &wormhole::query_response_hash(response),
+            payer.as_ref(),
+        ],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn approved_emitter_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ApprovedEmitter::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn received_address(chain: u16, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            Received::SEED_PREFIX,
+            &chain.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+/// Received bitmap covering (`chain`, `sequence`).
+pub fn received_bitmap_address(chain: u16, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ReceivedBitmap::SEED_PREFIX,
+            &chain.to_le_bytes(),
+            &ReceivedBitmap::index(sequence).to_le_bytes(),
+        ],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn refunded_address(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Refunded::SEED_PREFIX, &sequence.to_le_bytes()],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn outbound_transfer_address(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[OutboundTransfer::SEED_PREFIX, &sequence.to_le_bytes()],
+        &cat_sol20::ID,
+    )
+    .0
+}
+
+pub fn redeemer_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], &RECEIVER_ID).0
+}
+
+pub fn ata(wallet: &Pubkey) -> Pubkey {
+    associated_token::get_associated_token_address(wallet, &mint_address())
+}
+
+pub fn initialize_ix(owner: &Pubkey, params: InitializeParams) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::Initialize {
+            owner: *owner,
+            config: config_address(),
+            token_mint: mint_address(),
+            metadata_account: metadata_address(),
+            token_program: token::ID,
+            metadata_program: mpl_token_metadata::id(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: wormhole_test_guardian::core_bridge::bridge_address(),
+            wormhole_fee_collector: wormhole_test_guardian::core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(wormhole::INITIAL_SEQUENCE),
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::Initialize { params }.data(),
+    }
+}
+
+pub fn mint_tokens_ix(owner: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::MintTokens {
+            owner: *owner,
+            config: config_address(),
+            ata_authority: *recipient,
+            token_mint: mint_address(),
+            token_user_ata: ata(recipient),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::MintTokens { amount }.data(),
+    }
+}
+
+pub fn transfer_ownership_ix(owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::TransferOwnership {
+            owner: *owner,
+            new_owner: *new_owner,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::TransferOwnership {}.data(),
+    }
+}
+
+pub fn register_emitter_ix(owner: &Pubkey, chain: u64, address: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::RegisterEmitter {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::RegisterEmitter {
+            params: cat_sol20::RegisterEmitterParams { chain, address },
+        }
+        .data(),
+    }
+}
+
+pub fn migrate_config_ix(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::MigrateConfig {
+            owner: *owner,
+            config: config_address(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::MigrateConfig {}.data(),
+    }
+}
+
+pub fn migrate_emitter_ix(owner: &Pubkey, chain: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::MigrateEmitter {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::MigrateEmitter {
+            params: cat_sol20::MigrateEmitterParams { chain },
+        }
+        .data(),
+    }
+}
+
+pub fn set_message_params_ix(
+    owner: &Pubkey,
+    batch_id: u32,
+    finality: u8,
+    finalized_threshold: u64,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::SetMessageParams {
+            owner: *owner,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::SetMessageParams {
+            params: cat_sol20::SetMessageParamsParams {
+                batch_id,
+                finality,
+                finalized_threshold,
+            },
+        }
+        .data(),
+    }
+}
+
+pub fn set_emitter_finality_ix(owner: &Pubkey, chain: u64, finality: Option<u8>) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::SetEmitterFinality {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::SetEmitterFinality {
+            params: cat_sol20::SetEmitterFinalityParams { chain, finality },
+        }
+        .data(),
+    }
+}
+
+pub fn set_emitter_policy_ix(
+    owner: &Pubkey,
+    chain: u64,
+    max_vaa_age: u32,
+    consistency_levels: &[u8],
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::SetEmitterPolicy {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::SetEmitterPolicy {
+            params: cat_sol20::SetEmitterPolicyParams {
+                chain,
+                max_vaa_age,
+                consistency_levels: consistency_levels.to_vec(),
+            },
+        }
+        .data(),
+    }
+}
+
+fn update_metadata_accounts(owner: &Pubkey) -> Vec<AccountMeta> {
+    cat_sol20::accounts::UpdateMetadata {
+        owner: *owner,
+        config: config_address(),
+        token_mint: mint_address(),
+        metadata_account: metadata_address(),
+        metadata_program: mpl_token_metadata::id(),
+    }
+    .to_account_metas(None)
+}
+
+pub fn update_metadata_ix(owner: &Pubkey, params: cat_sol20::UpdateMetadataParams) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: update_metadata_accounts(owner),
+        data: cat_sol20::instruction::UpdateMetadata { params }.data(),
+    }
+}
+
+pub fn set_metadata_immutable_ix(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: update_metadata_accounts(owner),
+        data: cat_sol20::instruction::SetMetadataImmutable {}.data(),
+    }
+}
+
+/// The token's Metaplex metadata.
+pub async fn metadata(context: &mut ProgramTestContext) -> mpl_token_metadata::state::Metadata {
+    use mpl_token_metadata::state::TokenMetadataAccount;
+
+    let account = context
+        .banks_client
+        .get_account(metadata_address())
+        .await
+        .unwrap()
+        .expect("metadata account should exist");
+    mpl_token_metadata::state::Metadata::safe_deserialize(&account.data)
+        .expect("metadata should deserialize")
+}
+
+pub fn approve_emitter_ix(owner: &Pubkey, chain: u64, address: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::ApproveEmitter {
+            owner: *owner,
+            config: config_address(),
+            approved_emitter: approved_emitter_address(chain),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::ApproveEmitter {
+            params: cat_sol20::ApproveEmitterParams { chain, address },
+        }
+        .data(),
+    }
+}
+
+/// `post_hello`. `sequence` is the sequence the core bridge will assign to
+/// the `Hello`.
+pub fn post_hello_ix(owner: &Pubkey, sequence: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::PostHello {
+            owner: *owner,
+            token_mint: mint_address(),
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: wormhole_test_guardian::core_bridge::bridge_address(),
+            wormhole_fee_collector: wormhole_test_guardian::core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(sequence + 1),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::PostHello {}.data(),
+    }
+}
+
+/// `receive_hello` of the posted `body`. `owner` gets the approval's rent.
+pub fn receive_hello_ix(payer: &Pubkey, owner: &Pubkey, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::ReceiveHello {
+            payer: *payer,
+            config: config_address(),
+            owner: *owner,
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            approved_emitter: approved_emitter_address(body.emitter_chain.into()),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::ReceiveHello {
+            params: cat_sol20::ReceiveHelloParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `attest_token`. `sequence` is the sequence the core bridge will assign to
+/// the attestation.
+pub fn attest_token_ix(payer: &Pubkey, sequence: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::AttestToken {
+            payer: *payer,
+            token_mint: mint_address(),
+            metadata_account: metadata_address(),
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: wormhole_test_guardian::core_bridge::bridge_address(),
+            wormhole_fee_collector: wormhole_test_guardian::core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(sequence + 1),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::AttestToken {}.data(),
+    }
+}
+
+/// `receive_attestation` of the posted `body`.
+pub fn receive_attestation_ix(payer: &Pubkey, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::ReceiveAttestation {
+            payer: *payer,
+            token_mint: mint_address(),
+            metadata_account: metadata_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            foreign_attestation: foreign_attestation_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::ReceiveAttestation {
+            params: cat_sol20::ReceiveAttestationParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `verify_query_signatures` for the secp256k1 instruction right before it.
+pub fn verify_query_signatures_ix(
+    payer: &Pubkey,
+    guardian_set_index: u32,
+    response: &[u8],
+) -> Instruction {
+    let response_hash = wormhole::query_response_hash(response);
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::VerifyQuerySignatures {
+            payer: *payer,
+            wormhole_program: CORE_BRIDGE_ID,
+            guardian_set: GuardianSetData::address(guardian_set_index),
+            query_signatures: query_signatures_address(response, payer),
+            instructions: sysvar::instructions::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::VerifyQuerySignatures {
+            params: cat_sol20::VerifyQuerySignaturesParams {
+                guardian_set_index,
+                response_hash,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `record_remote_supply` of `response`, whose signatures of guardian set
+/// `guardian_set_index` were verified.
+pub fn record_remote_supply_ix(
+    payer: &Pubkey,
+    guardian_set_index: u32,
+    chain: u16,
+    response: &[u8],
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::RecordRemoteSupply {
+            payer: *payer,
+            token_mint: mint_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            query_signatures: query_signatures_address(response, payer),
+            guardian_set: GuardianSetData::address(guardian_set_index),
+            foreign_emitter: foreign_emitter_address(chain.into()),
+            remote_supply: remote_supply_address(chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::RecordRemoteSupply {
+            params: cat_sol20::RecordRemoteSupplyParams {
+                chain,
+                response: response.to_vec(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `reconcile_supply` counting the remote supplies of `chains`.
+pub fn reconcile_supply_ix(owner: &Pubkey, chains: &[u64]) -> Instruction {
+    let mut accounts = cat_sol20::accounts::ReconcileSupply {
+        owner: *owner,
+        config: config_address(),
+        token_mint: mint_address(),
+    }
+    .to_account_metas(None);
+    accounts.extend(
+        chains
+            .iter()
+            .map(|chain| AccountMeta::new_readonly(remote_supply_address(*chain), false)),
+    );
+
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts,
+        data: cat_sol20::instruction::ReconcileSupply {}.data(),
+    }
+}
+
+/// Response to a query of `totalSupply()` and `decimals()` of
+/// [`EVM_EMITTER`] on [`FOREIGN_CHAIN`], at a block of `block_time`.
+pub fn supply_response(block_time: i64, supply: u128, decimals: u8) -> Vec<u8> {
+    let word = |value: u128| {
+        let mut word = vec![0u8; 32];
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        word
+    };
+    let call = |selector: [u8; 4]| EthCallData {
+        to: EVM_EMITTER[12..].try_into().unwrap(),
+        data: selector.to_vec(),
+    };
+
+    eth_call_response(
+        FOREIGN_CHAIN,
+        block_time as u64 / 12,
+        block_time as u64,
+        &[
+            (call(TOTAL_SUPPLY_SELECTOR), word(supply)),
+            (call(DECIMALS_SELECTOR), word(decimals.into())),
+        ],
+    )
+    .to_bytes()
+}
+
+/// Verify the signatures of `response` by the guardians at `signers`,
+/// batching them into as many transactions as needed.
+pub async fn verify_query_signatures(
+    context: &mut ProgramTestContext,
+    guardian_set: &GuardianSet,
+    response: &[u8],
+    signers: &[usize],
+) -> std::result::Result<(), BanksClientError> {
+    let payer = context.payer.pubkey();
+    for batch in signers.chunks(SIGNATURES_PER_TRANSACTION) {
+        let ixs = [
+            guardian_set.sign_query_response(response, batch, 0),
+            verify_query_signatures_ix(&payer, guardian_set.index, response),
+        ];
+        process(context, &ixs, &[]).await?;
+    }
+    Ok(())
+}
+
+/// Record the remote supply of `response` on [`FOREIGN_CHAIN`], signed by a
+/// quorum of the current guardian set.
+pub async fn record_remote_supply(
+    context: &mut ProgramTestContext,
+    wormhole: &LocalWormhole,
+    response: &[u8],
+) -> std::result::Result<(), BanksClientError> {
+    let guardian_set = &wormhole.guardian_set;
+    let signers = (0..guardian_set.quorum()).collect::<Vec<_>>();
+    verify_query_signatures(context, guardian_set, response, &signers).await?;
+
+    let payer = context.payer.pubkey();
+    let ix = record_remote_supply_ix(&payer, guardian_set.index, FOREIGN_CHAIN, response);
+    process(context, &[ix], &[]).await
+}
+
+/// `bridge_out` burning from `sender`'s ATA. `sequence` is the sequence the
+/// core bridge will assign to the outbound message.
+pub fn bridge_out_ix(
+    sender: &Pubkey,
+    sequence: u64,
+    params: cat_sol20::BridgeOutParams,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::BridgeOut {
+            owner: *sender,
+            ata_authority: *sender,
+            token_mint: mint_address(),
+            token_user_ata: ata(sender),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: wormhole_test_guardian::core_bridge::bridge_address(),
+            wormhole_fee_collector: wormhole_test_guardian::core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(sequence + 1),
+            outbound_transfer: outbound_transfer_address(sequence),
+            foreign_emitter: foreign_emitter_address(
+                wormhole_chain_id(params.recipient_chain)
+                    .unwrap_or_default()
+                    .into(),
+            ),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::BridgeOut { params }.data(),
+    }
+}
+
+/// `bridge_out_reusable` burning from `sender`'s ATA.
+pub fn bridge_out_reusable_ix(
+    sender: &Pubkey,
+    sequence: u64,
+    params: cat_sol20::BridgeOutParams,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::BridgeOutReusable {
+            owner: *sender,
+            ata_authority: *sender,
+            token_mint: mint_address(),
+            token_user_ata: ata(sender),
+            token_program: token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: wormhole_test_guardian::core_bridge::bridge_address(),
+            wormhole_fee_collector: wormhole_test_guardian::core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: reusable_message_address(sender),
+            outbound_transfer: outbound_transfer_address(sequence),
+            foreign_emitter: foreign_emitter_address(
+                wormhole_chain_id(params.recipient_chain)
+                    .unwrap_or_default()
+                    .into(),
+            ),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::BridgeOutReusable { params }.data(),
+    }
+}
+
+/// `bridge_out_with_payload` burning from `sender`'s ATA, see
+/// [`bridge_out_ix`].
+pub fn bridge_out_with_payload_ix(
+    sender: &Pubkey,
+    sequence: u64,
+    params: cat_sol20::BridgeOutParams,
+    data: Vec<u8>,
+) -> Instruction {
+    let mut ix = bridge_out_ix(sender, sequence, params.clone());
+    ix.data = cat_sol20::instruction::BridgeOutWithPayload { params, data }.data();
+    ix
+}
+
+/// `bridge_in` redeeming the posted `body` into `token_account`, owned by
+/// `recipient`.
+pub fn bridge_in_ix(
+    payer: &Pubkey,
+    recipient: &Pubkey,
+    token_account: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::BridgeIn {
+            owner: *payer,
+            ata_authority: *recipient,
+            token_mint: mint_address(),
+            token_user_ata: *token_account,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            received: received_address(body.emitter_chain, body.sequence),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::BridgeIn {
+            params: cat_sol20::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `bridge_in_with_payload` redeeming the posted `body` into
+/// `recipient_program`'s redeemer and calling it with `RECEIPT`.
+pub fn bridge_in_with_payload_ix(
+    payer: &Pubkey,
+    recipient_program: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    let redeemer = Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], recipient_program).0;
+    let mut accounts = cat_sol20::accounts::BridgeInWithPayload {
+        owner: *payer,
+        recipient_program: *recipient_program,
+        redeemer,
+        token_mint: mint_address(),
+        token_user_ata: ata(&redeemer),
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        config: config_address(),
+        payload_authority: payload_authority_address(),
+        wormhole_program: CORE_BRIDGE_ID,
+        posted: body.posted_vaa_address(),
+        received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+        received: received_address(body.emitter_chain, body.sequence),
+        foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(RECEIPT, false));
+
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts,
+        data: cat_sol20::instruction::BridgeInWithPayload {
+            params: cat_sol20::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `bridge_in_to_token_account` redeeming the posted `body` into
+/// `token_account`.
+pub fn bridge_in_to_token_account_ix(
+    payer: &Pubkey,
+    token_account: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::BridgeInToTokenAccount {
+            owner: *payer,
+            token_mint: mint_address(),
+            token_account: *token_account,
+            token_program: token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            received: received_address(body.emitter_chain, body.sequence),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::BridgeInToTokenAccount {
+            params: cat_sol20::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `refund` of the outbound transfer `sequence` to its `sender`, redeeming
+/// the posted refund `body`.
+pub fn refund_ix(payer: &Pubkey, sender: &Pubkey, sequence: u64, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::Refund {
+            owner: *payer,
+            ata_authority: *sender,
+            token_mint: mint_address(),
+            token_user_ata: ata(sender),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            config: config_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            received: received_address(body.emitter_chain, body.sequence),
+            refunded: refunded_address(sequence),
+            outbound_transfer: outbound_transfer_address(sequence),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::Refund {
+            params: cat_sol20::RefundParams {
+                vaa_hash: body.body_hash(),
+                sequence,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `refund_ix` passing the program ID for the outbound transfer account of
+/// `sequence`, as clients pass an optional account they leave out.
+pub fn without_outbound_transfer(mut ix: Instruction, sequence: u64) -> Instruction {
+    for meta in &mut ix.accounts {
+        if meta.pubkey == outbound_transfer_address(sequence) {
+            *meta = AccountMeta::new_readonly(ix.program_id, false);
+        }
+    }
+    ix
+}
+
+/// `close_received` closing the received account of the redeemed `body`,
+/// signed by `signer` and sending its rent to `rent_recipient`.
+pub fn close_received_ix(signer: &Pubkey, rent_recipient: &Pubkey, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::CloseReceived {
+            signer: *signer,
+            config: config_address(),
+            rent_recipient: *rent_recipient,
+            received: received_address(body.emitter_chain, body.sequence),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::CloseReceived {
+            params: cat_sol20::CloseReceivedParams {
+                emitter_chain: body.emitter_chain,
+                sequence: body.sequence,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `close_outbound_transfer` closing the outbound transfer account
+/// `outbound_transfer`, signed by `signer` and sending its rent to
+/// `rent_recipient`.
+pub fn close_outbound_transfer_ix(
+    signer: &Pubkey,
+    rent_recipient: &Pubkey,
+    outbound_transfer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::CloseOutboundTransfer {
+            signer: *signer,
+            config: config_address(),
+            rent_recipient: *rent_recipient,
+            outbound_transfer: *outbound_transfer,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::CloseOutboundTransfer {}.data(),
+    }
+}
+
+/// `close_legacy_received` closing the legacy received account of the
+/// redeemed `body`, sending its rent to `rent_recipient`.
+pub fn close_legacy_received_ix(
+    owner: &Pubkey,
+    rent_recipient: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20::ID,
+        accounts: cat_sol20::accounts::CloseLegacyReceived {
+            owner: *owner,
+            config: config_address(),
+            rent_recipient: *rent_recipient,
+            received: received_address(body.emitter_chain, body.sequence),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20::instruction::CloseLegacyReceived {
+            params: cat_sol20::CloseLegacyReceivedParams {
+                emitter_chain: body.emitter_chain,
+                sequence: body.sequence,
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// The foreign emitter on `emitter_chain` refunding the outbound transfer
+/// `sequence`, which carried `payload`.
+pub fn refund_body(
+    emitter_chain: u16,
+    vaa_sequence: u64,
+    sequence: u64,
+    payload: CrossChainStruct,
+) -> VaaBody {
+    VaaBody::new(
+        emitter_chain,
+        FOREIGN_EMITTER,
+        vaa_sequence,
+        &CATSOLStructs::Refund { sequence, payload },
+    )
+    .with_timestamp(1_700_000_000)
+    .with_consistency_level(1)
+}
+
+/// A transfer of `amount` (8 decimals) from the foreign emitter to
+/// `recipient` on `dest_chain`.
+pub fn transfer_body(sequence: u64, amount: u64, recipient: &Pubkey, dest_chain: u64) -> VaaBody {
+    let payload = CATSOLStructs::CrossChainPayload {
+        payload: CrossChainStruct {
+            amount: U256::from(amount),
+            token_decimals: 18,
+            source_token_address: FOREIGN_EMITTER,
+            source_user_address: [0xee; 32],
+            source_token_chain: U256::from(FOREIGN_CAT_CHAIN),
+            dest_token_address: emitter_address().to_bytes(),
+            dest_user_address: recipient.to_bytes(),
+            dest_token_chain: U256::from(dest_chain),
+        },
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &payload)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// A transfer of `amount` (8 decimals) from the foreign emitter to
+/// `recipient_program`, carrying `data`.
+pub fn transfer_with_payload_body(
+    sequence: u64,
+    amount: u64,
+    recipient_program: &Pubkey,
+    data: &[u8],
+) -> VaaBody {
+    let transfer = transfer_body(sequence, amount, recipient_program, CONVENTIONAL_SOLANA_ID);
+    let payload = match CATSOLStructs::deserialize(&mut &transfer.payload[..]).unwrap() {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => unreachable!(),
+    };
+    let message = CATSOLStructs::TransferWithPayload {
+        payload,
+        data: data.to_vec(),
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// What the receiver was last called with, if anything.
+pub async fn receipt(context: &mut ProgramTestContext) -> Option<ReceivePayload> {
+    let account = context
+        .banks_client
+        .get_account(RECEIPT)
+        .await
+        .unwrap()
+        .unwrap();
+    if account.data.iter().all(|byte| *byte == 0) {
+        return None;
+    }
+    Some(ReceivePayload::deserialize(&mut &account.data[..]).unwrap())
+}
+
+/// A transfer of `amount` (8 decimals) from the foreign emitter, flagged
+/// with how `recipient` should be read.
+pub fn transfer_to_body(
+    sequence: u64,
+    amount: u64,
+    recipient: &Pubkey,
+    recipient_type: RecipientType,
+) -> VaaBody {
+    let transfer = transfer_body(sequence, amount, recipient, CONVENTIONAL_SOLANA_ID);
+    let payload = match CATSOLStructs::deserialize(&mut &transfer.payload[..]).unwrap() {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => unreachable!(),
+    };
+    let message = CATSOLStructs::Transfer {
+        payload,
+        recipient_type,
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// A `Hello` posted by `emitter` on `FOREIGN_CHAIN`, naming `named_emitter`.
+pub fn hello_body(sequence: u64, emitter: [u8; 32], named_emitter: [u8; 32]) -> VaaBody {
+    let message = CATSOLStructs::Hello {
+        emitter: named_emitter,
+        chain: U256::from(FOREIGN_CAT_CHAIN),
+        decimals: 18,
+        version: 1,
+    };
+    VaaBody::new(FOREIGN_CHAIN, emitter, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// The foreign emitter attesting a deployment named `name` with `symbol`.
+pub fn attest_body(sequence: u64, symbol: &str, name: &str) -> VaaBody {
+    let message = CATSOLStructs::Attest {
+        token_address: [0xab; 32],
+        token_chain: U256::from(FOREIGN_CAT_CHAIN),
+        decimals: 18,
+        symbol: utils_cat::attest_bytes(symbol),
+        name: utils_cat::attest_bytes(name),
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// New token account for `mint` owned by `owner`, at an address that is not
+/// an ATA, e.g. a program's vault.
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &account.pubkey(),
+            rent.minimum_balance(token::spl_token::state::Account::LEN),
+            token::spl_token::state::Account::LEN as u64,
+            &token::ID,
+        ),
+        token::spl_token::instruction::initialize_account3(
+            &token::ID,
+            &account.pubkey(),
+            mint,
+            owner,
+        )
+        .unwrap(),
+    ];
+    process(context, &ixs, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Close the empty `token_account`, signed by its `owner`.
+pub async fn close_token_account(
+    context: &mut ProgramTestContext,
+    token_account: &Pubkey,
+    owner: &Keypair,
+) {
+    let payer = context.payer.pubkey();
+    let ix = token::spl_token::instruction::close_account(
+        &token::ID,
+        token_account,
+        &payer,
+        &owner.pubkey(),
+        &[],
+    )
+    .unwrap();
+    process(context, &[ix], &[owner]).await.unwrap();
+}
+
+/// Freeze `token_account` in place, as its mint's freeze authority would.
+pub async fn freeze(context: &mut ProgramTestContext, token_account: &Pubkey) {
+    let mut account = context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut state = token::spl_token::state::Account::unpack(&account.data).unwrap();
+    state.state = token::spl_token::state::AccountState::Frozen;
+    token::spl_token::state::Account::pack(state, &mut account.data).unwrap();
+    context.set_account(token_account, &account.into());
+}
+
+/// New mint with `decimals`, owned by the context payer.
+pub async fn create_mint(context: &mut ProgramTestContext, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(token::spl_token::state::Mint::LEN),
+            token::spl_token::state::Mint::LEN as u64,
+            &token::ID,
+        ),
+        token::spl_token::instruction::initialize_mint2(
+            &token::ID,
+            &mint.pubkey(),
+            &payer,
+            None,
+            decimals,
+        )
+        .unwrap(),
+    ];
+    process(context, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// New keypair holding `lamports`.
+pub async fn funded_keypair(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    let ix = system_instruction::transfer(&context.payer.pubkey(), &keypair.pubkey(), lamports);
+    process(context, &[ix], &[]).await.unwrap();
+    keypair
+}
+
+/// Stand in for a message redeemed by an earlier version of the program:
+/// create its `Received` account, paid for by `payer`.
+pub async fn set_received(context: &mut ProgramTestContext, body: &VaaBody, payer: &Pubkey) {
+    let received = Received {
+        wormhole_message_hash: body.body_hash(),
+        payer: *payer,
+        emitter_chain: body.emitter_chain,
+        sequence: body.sequence,
+        amount: 1_000,
+        recipient: Pubkey::new_unique(),
+    };
+    let mut data = Vec::with_capacity(Received::MAXIMUM_SIZE);
+    received.try_serialize(&mut data).unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = solana_sdk::account::Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: cat_sol20::ID,
+        ..Default::default()
+    };
+    context.set_account(
+        &received_address(body.emitter_chain, body.sequence),
+        &account.into(),
+    );
+}
+
+/// Stand in for a message redeemed before the received bitmap: create its
+/// legacy `Received` account.
+pub async fn set_legacy_received(context: &mut ProgramTestContext, body: &VaaBody) {
+    // Only `wormhole_message_hash` after the discriminator.
+    let data = [&Received::DISCRIMINATOR[..], &body.body_hash()].concat();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = solana_sdk::account::Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: cat_sol20::ID,
+        ..Default::default()
+    };
+    context.set_account(
+        &received_address(body.emitter_chain, body.sequence),
+        &account.into(),
+    );
+}
+
+/// Stand in for an account created by an earlier version of the program: cut
+/// its data down to `len` bytes.
+pub async fn truncate_account(context: &mut ProgramTestContext, address: Pubkey, len: usize) {
+    let mut account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    account.data.truncate(len);
+    account.lamports = rent.minimum_balance(len);
+    context.set_account(&address, &account.into());
+}
+
+/// Whether (`chain`, `sequence`) is marked in its received bitmap.
+pub async fn is_received(context: &mut ProgramTestContext, chain: u16, sequence: u64) -> bool {
+    account::<ReceivedBitmap>(context, received_bitmap_address(chain, sequence))
+        .await
+        .is_received(sequence)
+}
+
+/// Current cluster time.
+pub async fn unix_timestamp(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+/// Move the cluster time `seconds` forward.
+pub async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Process `ixs` and return the `Redeemed` events they emitted.
+pub async fn process_redeemed(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<Vec<Redeemed>, BanksClientError> {
+    process_events(context, ixs, signers).await
+}
+
+/// Process `ixs` and return the `E` events they emitted.
+pub async fn process_events<E: AnchorDeserialize + anchor_lang::Discriminator>(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<Vec<E>, BanksClientError> {
+    // This runs the transaction on the bank directly, which can race the
+    // unlocking of the previous transaction's accounts, so retry a few times.
+    const MAX_ATTEMPTS: usize = 10;
+    let mut attempts = 0;
+    let outcome = loop {
+        let blockhash = context.get_new_latest_blockhash().await?;
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        let outcome = context
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await?;
+        if outcome.result != Err(TransactionError::AccountInUse) {
+            break outcome;
+        }
+        attempts += 1;
+        assert!(
+            attempts < MAX_ATTEMPTS,
+            "transaction still hit AccountInUse after {MAX_ATTEMPTS} attempts"
+        );
+    };
+    outcome.result?;
+
+    let logs = outcome.metadata.map(|m| m.log_messages).unwrap_or_default();
+    Ok(program_data(&logs)
+        .into_iter()
+        .filter(|data| data.starts_with(&E::DISCRIMINATOR))
+        .map(|data| E::try_from_slice(&data[8..]).unwrap())
+        .collect())
+}
+
+/// Copy the posted VAA of `body` to the address of `vaa_hash`, as if the core
+/// bridge had posted it under that hash.
+pub async fn repost_under_hash(
+    context: &mut ProgramTestContext,
+    body: &VaaBody,
+    vaa_hash: [u8; 32],
+) -> Pubkey {
+    let account = context
+        .banks_client
+        .get_account(body.posted_vaa_address())
+        .await
+        .unwrap()
+        .unwrap();
+    let address = Pubkey::find_program_address(
+        &[wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash],
+        &CORE_BRIDGE_ID,
+    )
+    .0;
+    context.set_account(&address, &account.into());
+    address
+}
+
+pub async fn account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account does not exist");
+    T::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+pub async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    account::<token::TokenAccount>(context, address)
+        .await
+        .amount
+}
+
+pub async fn mint_supply(context: &mut ProgramTestContext) -> u64 {
+    account::<token::Mint>(context, mint_address()).await.supply
+}
+
+/// Message this program posted for `seed`.
+pub async fn posted(context: &mut ProgramTestContext, seed: u64) -> wormhole::PostedVaaData {
+    account(context, message_address(seed)).await
+}
+
+/// Payload of the message this program posted for `seed`.
+pub async fn posted_message(context: &mut ProgramTestContext, seed: u64) -> CATSOLStructs {
+    CATSOLStructs::try_from_slice(&posted(context, seed).await.payload).unwrap()
+}
+
+pub fn assert_program_error(
+    result: std::result::Result<(), BanksClientError>,
+    expected: ErrorFactory,
+) {
+    assert_instruction_error(result, InstructionError::Custom(expected.into()));
+}
+
+pub fn assert_instruction_error(
+    result: std::result::Result<(), BanksClientError>,
+    expected: InstructionError,
+) {
+    match result.unwrap_err() {
+        BanksClientError::TransactionError(TransactionError::InstructionError(_, err)) => {
+            assert_eq!(err, expected)
+        }
+        err => panic!("unexpected error: {err:?}"),
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    chains::is_emitter_chain,
+    error::ErrorFactory,
+    state::{ApprovedEmitter, Config},
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct ApproveEmitterParams {
+    pub chain: u64,
+    pub address: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(params: ApproveEmitterParams)]
+pub struct ApproveEmitter<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            ApprovedEmitter::SEED_PREFIX,
+            &params.chain.to_le_bytes()[..]
+        ],
+        bump,
+        space = ApprovedEmitter::MAXIMUM_SIZE,
+    )]
+    pub approved_emitter: Account<'info, ApprovedEmitter>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ApproveEmitter<'_> {
+    pub fn approve_emitter(
+        ctx: Context<ApproveEmitter>,
+        params: &ApproveEmitterParams,
+    ) -> Result<()> {
+        // Same rules as `register_emitter`.
+        require!(
+            is_emitter_chain(params.chain) && !params.address.iter().all(|&x| x == 0),
+            ErrorFactory::InvalidForeignEmitter,
+        );
+
+        let approved = &mut ctx.accounts.approved_emitter;
+        approved.chain = params.chain;
+        approved.address = params.address;
+        Ok(())
+    }
+}
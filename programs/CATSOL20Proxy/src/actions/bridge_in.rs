@@ -6,10 +6,11 @@ use anchor_spl::{
 use wormhole_anchor_sdk::wormhole;
 
 use crate::{
-    cat_struct::CATSOLStructs,
+    cat_struct::{CATSOLStructs, RecipientType},
     constants::*,
     error::ErrorFactory,
-    state::{Config, ForeignEmitter, Received},
+    events::Redeemed,
+    state::{Config, ForeignEmitter, Received, ReceivedBitmap},
     utils_cat::*,
 };
 
@@ -29,9 +30,13 @@ pub struct BridgeIn<'info> {
     #[account(mut)]
     pub token_mint: Box<Account<'info, Mint>>,
 
-    // Token Account. Its an Associated Token Account that will hold the
-    // tokens that are bridged out
-    #[account(mut)]
+    // Token Account. The recipient wallet's Associated Token Account. Closed
+    // accounts fail to load.
+    #[account(
+        mut,
+        constraint = token_user_ata.mint == token_mint.key() @ ErrorFactory::InvalidTokenAccountMint,
+        constraint = !token_user_ata.is_frozen() @ ErrorFactory::InvalidATAAccount,
+    )]
     pub token_user_ata: Account<'info, TokenAccount>,
 
     // Token Mint ATA. Its an Associated Token Account owned by the Program that will hold the locked tokens
@@ -74,26 +79,39 @@ pub struct BridgeIn<'info> {
     pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
 
     #[account(
-        init,
+        init_if_needed,
         payer = owner,
+        seeds = [
+            ReceivedBitmap::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &ReceivedBitmap::index(posted.sequence()).to_le_bytes()[..]
+        ],
+        bump,
+        space = ReceivedBitmap::MAXIMUM_SIZE,
+        constraint = !received_bitmap.is_received(posted.sequence()) @ ErrorFactory::AlreadyReceived
+    )]
+    /// Received bitmap covering the message's sequence. The message's bit is
+    /// set on redemption, preventing replay.
+    pub received_bitmap: Box<Account<'info, ReceivedBitmap>>,
+
+    #[account(
         seeds = [
             Received::SEED_PREFIX,
             &posted.emitter_chain().to_le_bytes()[..],
             &posted.sequence().to_le_bytes()[..]
         ],
         bump,
-        space = Received::MAXIMUM_SIZE
+        constraint = received.data_is_empty() @ ErrorFactory::AlreadyReceived
     )]
-    /// Received account. [`receive_message`](crate::receive_message) will
-    /// deserialize the Wormhole message's payload and save it to this account.
-    /// This account cannot be overwritten, and will prevent Wormhole message
-    /// replay with the same sequence.
-    pub received: Account<'info, Received>,
+    /// CHECK: Received account of the message. Only messages redeemed before
+    /// the received bitmap have one, and those cannot be redeemed again.
+    pub received: UncheckedAccount<'info>,
 
     #[account(
         seeds = [
             ForeignEmitter::SEED_PREFIX,
-            &posted.emitter_chain().to_le_bytes()[..]
+            // Emitters are registered under a u64 chain ID.
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
         ],
         bump,
         constraint = foreign_emitter.verify(posted.emitter_address()) @ ErrorFactory::InvalidForeignEmitter
@@ -110,62 +128,81 @@ pub struct BridgeIn<'info> {
 impl BridgeIn<'_> {
     pub fn bridge_in(ctx: Context<BridgeIn>, params: BridgeInParams) -> Result<()> {
         let posted_message = &ctx.accounts.posted;
-
-        if let CATSOLStructs::CrossChainPayload { payload } = posted_message.data() {
-
-            let dest_chain: u64 = payload.dest_token_chain.into();
-            require!(
-                dest_chain == wormhole::CHAIN_ID_SOLANA as u64,
-                ErrorFactory::InvalidDestinationChain
-            );
-
-            let ata_address = associated_token::get_associated_token_address(
-                &Pubkey::from(payload.dest_user_address),
-                &ctx.accounts.token_mint.key(),
-            );
-
-            // Check if the ATA address is the same as the one in the payload
-            require_keys_eq!(
-                ata_address,
-                ctx.accounts.token_user_ata.key(),
-                ErrorFactory::MisMatchdATAAddress
-            );
-
-            // Normalize the amount by converting it back from the standard 8 decimals to the token's decimals
-            let amount_u64: u64 = payload.amount.into();
-            let decimals = ctx.accounts.token_mint.decimals;
-            let normalized_amount = denormalize_amount(amount_u64, decimals);
-
-            // Mint the tokens
-            let cpi_program = ctx.accounts.token_program.to_account_info();
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.token_mint_ata.to_account_info(),
-                to: ctx.accounts.token_user_ata.to_account_info(),
-                authority: ctx.accounts.token_mint_ata.to_account_info(),
-            };
-
-            let bump = ctx.bumps.token_mint_ata;
-
-            let cpi_signer_seeds = &[
-                b"cat_sol_proxy".as_ref(),
-                &ctx.accounts.token_mint.key().to_bytes(),
-                &[bump],
-            ];
-
-            let cpi_signer = &[&cpi_signer_seeds[..]];
-
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, cpi_signer);
-
-            transfer(cpi_ctx, normalized_amount)?;
-
-            //Save keccak256 hash.
-            let received = &mut ctx.accounts.received;
-            received.wormhole_message_hash = params.vaa_hash;
-
-            // Done
-            Ok(())
-        } else {
-            Err(ErrorFactory::InvalidMessage.into())
-        }
+        ctx.accounts.foreign_emitter.check_policy(posted_message)?;
+        require!(
+            wormhole::posted_vaa_hash(&posted_message.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        let payload = match posted_message.data() {
+            CATSOLStructs::CrossChainPayload { payload }
+            | CATSOLStructs::Transfer {
+                payload,
+                recipient_type: RecipientType::Wallet,
+            } => payload,
+            CATSOLStructs::Transfer { .. } => return Err(ErrorFactory::InvalidRecipientType.into()),
+            _ => return Err(ErrorFactory::InvalidMessage.into()),
+        };
+
+        let dest_chain: u64 = payload.dest_token_chain.into();
+        require!(
+            dest_chain == CONVENTIONAL_SOLANA_ID,
+            ErrorFactory::InvalidDestinationChain
+        );
+
+        let ata_address = associated_token::get_associated_token_address(
+            &Pubkey::from(payload.dest_user_address),
+            &ctx.accounts.token_mint.key(),
+        );
+
+        // Check if the ATA address is the same as the one in the payload
+        require_keys_eq!(
+            ata_address,
+            ctx.accounts.token_user_ata.key(),
+            ErrorFactory::MisMatchdATAAddress
+        );
+
+        // Normalize the amount by converting it back from the standard 8 decimals to the token's decimals
+        let amount_u64: u64 = payload.amount.into();
+        let decimals = ctx.accounts.token_mint.decimals;
+        let normalized_amount = denormalize_amount(amount_u64, decimals);
+
+        // Mint the tokens
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.token_mint_ata.to_account_info(),
+            to: ctx.accounts.token_user_ata.to_account_info(),
+            authority: ctx.accounts.token_mint_ata.to_account_info(),
+        };
+
+        let bump = ctx.bumps.token_mint_ata;
+
+        let cpi_signer_seeds = &[
+            b"cat_sol_proxy".as_ref(),
+            &ctx.accounts.token_mint.key().to_bytes(),
+            &[bump],
+        ];
+
+        let cpi_signer = &[&cpi_signer_seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, cpi_signer);
+
+        transfer(cpi_ctx, normalized_amount)?;
+
+        // Mark the message as redeemed.
+        ctx.accounts.received_bitmap.mark_received(
+            ctx.accounts.posted.emitter_chain(),
+            ctx.accounts.posted.sequence(),
+        )?;
+        emit!(Redeemed {
+            emitter_chain: posted_message.emitter_chain(),
+            sequence: posted_message.sequence(),
+            vaa_hash: params.vaa_hash,
+            amount: normalized_amount,
+            recipient: ctx.accounts.token_user_ata.key(),
+        });
+
+        // Done
+        Ok(())
     }
 }
@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+use wormhole_anchor_sdk::{token_bridge, wormhole};
+
+use crate::{
+    actions::BridgeInParams,
+    cat_struct::CATSOLStructs,
+    constants::*,
+    error::ErrorFactory,
+    events::Redeemed,
+    state::{Config, ForeignEmitter},
+    utils_cat::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: BridgeInParams)]
+pub struct BridgeInPortal<'info> {
+    /// Payer of the Token Bridge claim and of the redeemer's ATA.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    /// Token Mint. The token that is unlocked from the Token Bridge custody.
+    #[account(constraint = config.native_token == token_mint.key())]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Token Account. The recipient wallet's Associated Token Account.
+    #[account(
+        mut,
+        constraint = token_user_ata.mint == token_mint.key() @ ErrorFactory::InvalidTokenAccountMint,
+    )]
+    pub token_user_ata: Box<Account<'info, TokenAccount>>,
+
+    /// Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Verified Token Bridge transfer. Read-only.
+    pub posted: Box<Account<'info, token_bridge::PostedTransferWithPayload>>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        constraint = foreign_emitter.verify(posted.data().from_address()) @ ErrorFactory::InvalidForeignEmitter
+    )]
+    /// Foreign emitter account. The CAT deployment registered for the
+    /// transfer's chain must be the one that sent it through the Token Bridge.
+    /// Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// Token Bridge program.
+    pub token_bridge_program: Program<'info, token_bridge::program::TokenBridge>,
+
+    #[account(
+        seeds = [token_bridge::Config::SEED_PREFIX],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// Token Bridge config. Read-only.
+    pub token_bridge_config: Box<Account<'info, token_bridge::Config>>,
+
+    #[account(
+        mut,
+        seeds = [
+            posted.emitter_address().as_ref(),
+            &posted.emitter_chain().to_be_bytes(),
+            &posted.sequence().to_be_bytes()
+        ],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge claim account. The Token Bridge creates it, so a
+    /// transfer is completed once.
+    pub token_bridge_claim: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            &posted.emitter_chain().to_be_bytes(),
+            posted.emitter_address().as_ref()
+        ],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// Registration of the Token Bridge that sent the transfer. Read-only.
+    pub token_bridge_foreign_endpoint: Box<Account<'info, token_bridge::EndpointRegistration>>,
+
+    #[account(
+        mut,
+        seeds = [token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge custody account of the mint.
+    pub token_bridge_custody: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [token_bridge::SEED_PREFIX_CUSTODY_SIGNER],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge custody signer. Read-only.
+    pub token_bridge_custody_signer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [token_bridge::SEED_PREFIX_REDEEMER],
+        bump,
+    )]
+    /// CHECK: This program's redeemer PDA. The Token Bridge transfer is to
+    /// this program, so it completes only with the redeemer's signature.
+    pub token_bridge_redeemer: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_bridge_redeemer,
+    )]
+    /// The redeemer's ATA. The Token Bridge unlocks the tokens here before
+    /// they are forwarded to the recipient.
+    pub token_bridge_redeemer_ata: Box<Account<'info, TokenAccount>>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+    // Associated Token Program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl BridgeInPortal<'_> {
+    /// Complete a Token Bridge transfer sent by a CAT deployment, and forward
+    /// the unlocked tokens to the recipient of its CAT payload.
+    pub fn bridge_in_portal(ctx: Context<BridgeInPortal>, params: BridgeInParams) -> Result<()> {
+        let posted = &ctx.accounts.posted;
+        ctx.accounts.foreign_emitter.check_policy(posted)?;
+        require!(
+            wormhole::posted_vaa_hash(&posted.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        let transfer_data = posted.data();
+        require!(
+            transfer_data.to() == crate::ID && transfer_data.mint() == ctx.accounts.token_mint.key(),
+            ErrorFactory::InvalidPortalTransfer
+        );
+
+        let payload = match CATSOLStructs::try_from_slice(transfer_data.data()) {
+            Ok(CATSOLStructs::CrossChainPayload { payload }) => payload,
+            _ => return Err(ErrorFactory::InvalidMessage.into()),
+        };
+
+        let dest_chain: u64 = payload.dest_token_chain.into();
+        require!(
+            dest_chain == CONVENTIONAL_SOLANA_ID,
+            ErrorFactory::InvalidDestinationChain
+        );
+
+        // The Token Bridge amount is what was locked on the other side.
+        let amount_u64: u64 = payload.amount.into();
+        require!(
+            amount_u64 == transfer_data.amount(),
+            ErrorFactory::InvalidPortalTransfer
+        );
+
+        require_keys_eq!(
+            associated_token::get_associated_token_address(
+                &Pubkey::from(payload.dest_user_address),
+                &ctx.accounts.token_mint.key(),
+            ),
+            ctx.accounts.token_user_ata.key(),
+            ErrorFactory::MisMatchdATAAddress
+        );
+
+        let redeemer_seeds: &[&[u8]] = &[
+            token_bridge::SEED_PREFIX_REDEEMER,
+            &[ctx.bumps.token_bridge_redeemer],
+        ];
+
+        token_bridge::complete_transfer_native_with_payload(CpiContext::new_with_signer(
+            ctx.accounts.token_bridge_program.to_account_info(),
+            token_bridge::CompleteTransferNativeWithPayload {
+                payer: ctx.accounts.owner.to_account_info(),
+                config: ctx.accounts.token_bridge_config.to_account_info(),
+                vaa: ctx.accounts.posted.to_account_info(),
+                claim: ctx.accounts.token_bridge_claim.to_account_info(),
+                foreign_endpoint: ctx.accounts.token_bridge_foreign_endpoint.to_account_info(),
+                to: ctx.accounts.token_bridge_redeemer_ata.to_account_info(),
+                redeemer: ctx.accounts.token_bridge_redeemer.to_account_info(),
+                custody: ctx.accounts.token_bridge_custody.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                custody_signer: ctx.accounts.token_bridge_custody_signer.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+            },
+            &[redeemer_seeds],
+        ))?;
+
+        // Forward the unlocked tokens to the recipient.
+        let amount = denormalize_amount(amount_u64, ctx.accounts.token_mint.decimals);
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.token_bridge_redeemer_ata.to_account_info(),
+                    to: ctx.accounts.token_user_ata.to_account_info(),
+                    authority: ctx.accounts.token_bridge_redeemer.to_account_info(),
+                },
+                &[redeemer_seeds],
+            ),
+            amount,
+        )?;
+
+        emit!(Redeemed {
+            emitter_chain: posted.emitter_chain(),
+            sequence: posted.sequence(),
+            vaa_hash: params.vaa_hash,
+            amount,
+            recipient: ctx.accounts.token_user_ata.key(),
+        });
+
+        // Done
+        Ok(())
+    }
+}
@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+};
+use anchor_spl::{
+    associated_token::{self, AssociatedToken},
+    token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    actions::BridgeInParams,
+    cat_struct::CATSOLStructs,
+    constants::*,
+    error::ErrorFactory,
+    events::Redeemed,
+    state::{Config, ForeignEmitter, Received, ReceivedBitmap},
+    utils_cat::*,
+};
+
+/// Instruction the recipient program of a transfer with payload is invoked
+/// with, once the tokens are in its redeemer's ATA.
+///
+/// Accounts, in order:
+/// 0. `[signer]` This program's payload authority PDA, with seed
+///    `payload_authority`. Proves the call comes from the bridge.
+/// 1. `[]` Token mint.
+/// 2. `[writable]` The redeemer's ATA, holding the bridged tokens.
+/// 3. `[]` The redeemer, a PDA of the recipient program with seed `redeemer`.
+/// 4. The remaining accounts of `bridge_in_with_payload`, as passed.
+///
+/// The data is the Anchor discriminator of `receive_payload` followed by this
+/// struct, so an Anchor program can take it as `receive_payload(ctx, args)`.
+#[derive(Clone, Debug, PartialEq, Eq, AnchorDeserialize, AnchorSerialize)]
+pub struct ReceivePayload {
+    /// Tokens delivered, in the token's decimals.
+    pub amount: u64,
+    /// Wormhole chain ID the transfer came from.
+    pub source_chain: u16,
+    /// Sender on the source chain.
+    pub sender: [u8; 32],
+    /// Application data of the transfer.
+    pub data: Vec<u8>,
+}
+
+impl ReceivePayload {
+    pub fn discriminator() -> [u8; 8] {
+        let mut discriminator = [0u8; 8];
+        discriminator.copy_from_slice(&hash::hash(b"global:receive_payload").to_bytes()[..8]);
+        discriminator
+    }
+
+    pub fn instruction_data(&self) -> Result<Vec<u8>> {
+        let mut data = Self::discriminator().to_vec();
+        self.serialize(&mut data)?;
+        Ok(data)
+    }
+}
+
+#[derive(Accounts)]
+#[instruction(params: BridgeInParams)]
+pub struct BridgeInWithPayload<'info> {
+    /// Owner will initialize an account that tracks his own payloads
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: Recipient program. Must be the `dest_user_address` of the transfer.
+    #[account(executable)]
+    pub recipient_program: UncheckedAccount<'info>,
+
+    /// CHECK: Redeemer. The recipient program's PDA that owns the bridged tokens.
+    #[account(
+        seeds = [SEED_PREFIX_REDEEMER],
+        bump,
+        seeds::program = recipient_program.key()
+    )]
+    pub redeemer: UncheckedAccount<'info>,
+
+    /// Token Mint. The token that is unlocked.
+    #[account(mut)]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Token Account. The redeemer's Associated Token Account that will hold
+    // the tokens that are unlocked.
+    #[account(mut)]
+    pub token_user_ata: Account<'info, TokenAccount>,
+
+    // Token Mint ATA. Its an Associated Token Account owned by the Program that will hold the locked tokens
+    #[account(
+        mut,
+        seeds = [SEED_PREFIX_LOCK, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = token_mint_ata,
+    )]
+    pub token_mint_ata: Account<'info, TokenAccount>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+    // Associated Token Program
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [SEED_PREFIX_PAYLOAD_AUTHORITY],
+        bump
+    )]
+    /// CHECK: Payload authority. Signs the call into the recipient program,
+    /// and signs nothing else.
+    pub payload_authority: UncheckedAccount<'info>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// Verified Wormhole message account. The Wormhole program verified
+    /// signatures and posted the account data here. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            ReceivedBitmap::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &ReceivedBitmap::index(posted.sequence()).to_le_bytes()[..]
+        ],
+        bump,
+        space = ReceivedBitmap::MAXIMUM_SIZE,
+        constraint = !received_bitmap.is_received(posted.sequence()) @ ErrorFactory::AlreadyReceived
+    )]
+    /// Received bitmap covering the message's sequence. The message's bit is
+    /// set on redemption, preventing replay.
+    pub received_bitmap: Box<Account<'info, ReceivedBitmap>>,
+
+    #[account(
+        seeds = [
+            Received::SEED_PREFIX,
+            &posted.emitter_chain().to_le_bytes()[..],
+            &posted.sequence().to_le_bytes()[..]
+        ],
+        bump,
+        constraint = received.data_is_empty() @ ErrorFactory::AlreadyReceived
+    )]
+    /// CHECK: Received account of the message. Only messages redeemed before
+    /// the received bitmap have one, and those cannot be redeemed again.
+    pub received: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            // Emitters are registered under a u64 chain ID.
+            &(posted.emitter_chain() as u64).to_le_bytes()[..]
+        ],
+        bump,
+        constraint = foreign_emitter.verify(posted.emitter_address()) @ ErrorFactory::InvalidForeignEmitter
+    )]
+    /// Foreign emitter account. The posted message's `emitter_address` must
+    /// agree with the one we have registered for this message's `emitter_chain`
+    /// (chain ID). Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+
+impl<'info> BridgeInWithPayload<'info> {
+    pub fn bridge_in_with_payload(
+        ctx: Context<'_, '_, '_, 'info, BridgeInWithPayload<'info>>,
+        params: BridgeInParams,
+    ) -> Result<()> {
+        let posted_message = &ctx.accounts.posted;
+        ctx.accounts.foreign_emitter.check_policy(posted_message)?;
+        require!(
+            wormhole::posted_vaa_hash(&posted_message.to_account_info())? == params.vaa_hash,
+            ErrorFactory::InvalidVaaHash
+        );
+
+        if let CATSOLStructs::TransferWithPayload { payload, data } = posted_message.data() {
+            let dest_chain: u64 = payload.dest_token_chain.into();
+            require!(
+                dest_chain == CONVENTIONAL_SOLANA_ID,
+                ErrorFactory::InvalidDestinationChain
+            );
+
+            require_keys_eq!(
+                Pubkey::from(payload.dest_user_address),
+                ctx.accounts.recipient_program.key(),
+                ErrorFactory::InvalidRecipientProgram
+            );
+
+            let ata_address = associated_token::get_associated_token_address(
+                &ctx.accounts.redeemer.key(),
+                &ctx.accounts.token_mint.key(),
+            );
+
+            // Check if the ATA address is the redeemer's
+            require_keys_eq!(
+                ata_address,
+                ctx.accounts.token_user_ata.key(),
+                ErrorFactory::MisMatchdATAAddress
+            );
+
+            // Normalize the amount by converting it back from the standard 8 decimals to the token's decimals
+            let amount_u64: u64 = payload.amount.into();
+            let decimals = ctx.accounts.token_mint.decimals;
+            let normalized_amount = denormalize_amount(amount_u64, decimals);
+
+            // Unlock the tokens to the redeemer
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.token_mint_ata.to_account_info(),
+                to: ctx.accounts.token_user_ata.to_account_info(),
+                authority: ctx.accounts.token_mint_ata.to_account_info(),
+            };
+
+            let bump = ctx.bumps.token_mint_ata;
+
+            let cpi_signer_seeds = &[
+                b"cat_sol_proxy".as_ref(),
+                &ctx.accounts.token_mint.key().to_bytes(),
+                &[bump],
+            ];
+
+            let cpi_signer = &[&cpi_signer_seeds[..]];
+
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, cpi_signer);
+
+            transfer(cpi_ctx, normalized_amount)?;
+
+            // Mark the message as redeemed.
+            ctx.accounts.received_bitmap.mark_received(
+                ctx.accounts.posted.emitter_chain(),
+                ctx.accounts.posted.sequence(),
+            )?;
+            emit!(Redeemed {
+                emitter_chain: posted_message.emitter_chain(),
+                sequence: posted_message.sequence(),
+                vaa_hash: params.vaa_hash,
+                amount: normalized_amount,
+                recipient: ctx.accounts.token_user_ata.key(),
+            });
+
+            // Hand the payload to the recipient program
+            let receive_payload = ReceivePayload {
+                amount: normalized_amount,
+                source_chain: posted_message.emitter_chain(),
+                sender: payload.source_user_address,
+                data: data.clone(),
+            };
+
+            let mut accounts = vec![
+                AccountMeta::new_readonly(ctx.accounts.payload_authority.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.token_mint.key(), false),
+                AccountMeta::new(ctx.accounts.token_user_ata.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.redeemer.key(), false),
+            ];
+            let mut account_infos = vec![
+                ctx.accounts.payload_authority.to_account_info(),
+                ctx.accounts.token_mint.to_account_info(),
+                ctx.accounts.token_user_ata.to_account_info(),
+                ctx.accounts.redeemer.to_account_info(),
+                ctx.accounts.recipient_program.to_account_info(),
+            ];
+            for account in ctx.remaining_accounts {
+                accounts.push(if account.is_writable {
+                    AccountMeta::new(account.key(), account.is_signer)
+                } else {
+                    AccountMeta::new_readonly(account.key(), account.is_signer)
+                });
+                account_infos.push(account.clone());
+            }
+
+            invoke_signed(
+                &Instruction {
+                    program_id: ctx.accounts.recipient_program.key(),
+                    accounts,
+                    data: receive_payload.instruction_data()?,
+                },
+                &account_infos,
+                &[&[SEED_PREFIX_PAYLOAD_AUTHORITY.as_ref(), &[ctx.bumps.payload_authority]]],
+            )?;
+
+            // Done
+            Ok(())
+        } else {
+            Err(ErrorFactory::InvalidMessage.into())
+        }
+    }
+}
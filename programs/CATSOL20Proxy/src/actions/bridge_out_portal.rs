@@ -0,0 +1,281 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{approve, Approve, Mint, Token, TokenAccount};
+use wormhole_anchor_sdk::{token_bridge, wormhole};
+
+use crate::{
+    actions::BridgeOutParams,
+    cat_struct::{CATSOLStructs, CrossChainStruct, U256},
+    chains::{emitter_chain_key, wormhole_chain_id},
+    constants::*,
+    error::ErrorFactory,
+    state::{Config, ForeignEmitter, OutboundStatus, OutboundTransfer},
+    utils_cat::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: BridgeOutParams)]
+pub struct BridgeOutPortal<'info> {
+    #[account(mut)]
+    /// Owner of the tokens. Pays the Wormhole fee and the message rent through
+    /// the Token Bridge.
+    pub owner: Signer<'info>,
+
+    /// Token Mint. The token that is Will be bridged out
+    #[account(
+        mut,
+        constraint = config.native_token == token_mint.key()
+    )]
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Token Account. The owner's token account the tokens are taken from.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = owner,
+    )]
+    pub token_user_ata: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+    )]
+    /// Config account. Wormhole PDAs specified in the config are checked
+    /// against the Wormhole accounts in this context. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &emitter_chain_key(params.recipient_chain)[..]
+        ],
+        bump,
+        constraint = wormhole_chain_id(params.recipient_chain).map(u64::from) == Some(foreign_emitter.chain)
+    )]
+    /// Foreign Emitter account of the recipient chain. The CAT deployment
+    /// there is the Token Bridge recipient. Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// Token Bridge program.
+    pub token_bridge_program: Program<'info, token_bridge::program::TokenBridge>,
+
+    #[account(
+        seeds = [token_bridge::Config::SEED_PREFIX],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// Token Bridge config. Read-only.
+    pub token_bridge_config: Box<Account<'info, token_bridge::Config>>,
+
+    #[account(
+        mut,
+        seeds = [token_mint.key().as_ref()],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge custody account of the mint. The Token Bridge
+    /// creates it on the first transfer.
+    pub token_bridge_custody: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [token_bridge::SEED_PREFIX_AUTHORITY_SIGNER],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge authority signer. Approved as delegate of the
+    /// owner's token account for the transferred amount.
+    pub token_bridge_authority_signer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [token_bridge::SEED_PREFIX_CUSTODY_SIGNER],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge custody signer. Read-only.
+    pub token_bridge_custody_signer: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [token_bridge::SEED_PREFIX_EMITTER],
+        bump,
+        seeds::program = token_bridge_program
+    )]
+    /// CHECK: Token Bridge emitter. Read-only.
+    pub token_bridge_emitter: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [token_bridge::SEED_PREFIX_SENDER],
+        bump,
+    )]
+    /// CHECK: This program's sender PDA. Signs the transfer, so the Token
+    /// Bridge records this program as the sender.
+    pub token_bridge_sender: UncheckedAccount<'info>,
+
+    /// Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        mut,
+        address = token_bridge_config.wormhole_bridge @ ErrorFactory::InvalidWormholeConfig
+    )]
+    /// CHECK: Wormhole bridge data, checked against the Token Bridge config.
+    pub wormhole_bridge: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ ErrorFactory::InvalidWormholeFeeCollector
+    )]
+    /// CHECK: Wormhole fee collector. The Token Bridge pays the fee.
+    pub wormhole_fee_collector: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            wormhole::SequenceTracker::SEED_PREFIX,
+            token_bridge_emitter.key().as_ref()
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// CHECK: Token Bridge emitter's sequence account. The core bridge
+    /// creates it on the Token Bridge's first message.
+    pub wormhole_sequence: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_PREFIX_PORTAL,
+            &sequence_value(&wormhole_sequence)?.to_le_bytes()[..]
+        ],
+        bump,
+    )]
+    /// CHECK: Wormhole Message of the Token Bridge transfer, seeded by its
+    /// sequence.
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            &OutboundTransfer::SEED_PREFIX[..],
+            &SEED_PREFIX_PORTAL[..],
+            &sequence_value(&wormhole_sequence)?.to_le_bytes()[..]
+        ],
+        bump,
+        space = OutboundTransfer::MAXIMUM_SIZE
+    )]
+    /// Outbound transfer account of the Token Bridge transfer. Seeded apart
+    /// from `bridge_out`'s, as the sequence is the Token Bridge emitter's.
+    /// Never marked refunded: the transfer comes back through the Token
+    /// Bridge (`bridge_in_portal`), not `refund`.
+    pub outbound_transfer: Box<Account<'info, OutboundTransfer>>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl BridgeOutPortal<'_> {
+    /// Lock `params.amount` in the Token Bridge custody instead of this
+    /// program's lock account. The Token Bridge transfers them to the CAT
+    /// deployment on the recipient chain, with the CAT payload attached.
+    pub fn bridge_out_portal(ctx: Context<BridgeOutPortal>, params: &BridgeOutParams) -> Result<()> {
+        // The Token Bridge only locks whole units of 8 decimals.
+        let decimals = ctx.accounts.token_mint.decimals;
+        let foreign_amount = normalize_amount(params.amount, decimals);
+        require!(foreign_amount > 0, ErrorFactory::AmountTooSmall);
+        let amount = denormalize_amount(foreign_amount, decimals);
+
+        approve(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Approve {
+                    to: ctx.accounts.token_user_ata.to_account_info(),
+                    delegate: ctx.accounts.token_bridge_authority_signer.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        // The destination token address tells the CAT deployment there which
+        // asset to release: its CAT token or the Portal wrapped asset. The
+        // source token address is this program, the Token Bridge sender, not
+        // its emitter, so `refund` rejects refunds of this transfer.
+        let payload = CrossChainStruct {
+            amount: U256::from(foreign_amount),
+            token_decimals: decimals,
+            source_token_address: crate::ID.to_bytes(),
+            source_user_address: ctx.accounts.owner.key().to_bytes(),
+            source_token_chain: U256::from(CONVENTIONAL_SOLANA_ID),
+            dest_token_address: params.recipient_contract,
+            dest_user_address: params.recipient,
+            dest_token_chain: U256::from(params.recipient_chain),
+        };
+        let mut encoded_payload: Vec<u8> = Vec::new();
+        CATSOLStructs::CrossChainPayload { payload }.serialize(&mut encoded_payload)?;
+
+        let sequence = sequence_value(&ctx.accounts.wormhole_sequence)?;
+        token_bridge::transfer_native_with_payload(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_bridge_program.to_account_info(),
+                token_bridge::TransferNativeWithPayload {
+                    payer: ctx.accounts.owner.to_account_info(),
+                    config: ctx.accounts.token_bridge_config.to_account_info(),
+                    from: ctx.accounts.token_user_ata.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    custody: ctx.accounts.token_bridge_custody.to_account_info(),
+                    authority_signer: ctx.accounts.token_bridge_authority_signer.to_account_info(),
+                    custody_signer: ctx.accounts.token_bridge_custody_signer.to_account_info(),
+                    wormhole_bridge: ctx.accounts.wormhole_bridge.to_account_info(),
+                    wormhole_message: ctx.accounts.wormhole_message.to_account_info(),
+                    wormhole_emitter: ctx.accounts.token_bridge_emitter.to_account_info(),
+                    wormhole_sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+                    wormhole_fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                    sender: ctx.accounts.token_bridge_sender.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    wormhole_program: ctx.accounts.wormhole_program.to_account_info(),
+                },
+                &[
+                    &[
+                        SEED_PREFIX_PORTAL,
+                        &sequence.to_le_bytes()[..],
+                        &[ctx.bumps.wormhole_message],
+                    ],
+                    &[
+                        token_bridge::SEED_PREFIX_SENDER,
+                        &[ctx.bumps.token_bridge_sender],
+                    ],
+                ],
+            ),
+            ctx.accounts.config.batch_id,
+            amount,
+            ctx.accounts.foreign_emitter.address,
+            ctx.accounts.foreign_emitter.chain as u16,
+            encoded_payload,
+            &crate::ID,
+        )?;
+
+        let outbound = &mut ctx.accounts.outbound_transfer;
+        outbound.sender = ctx.accounts.owner.key();
+        outbound.status = OutboundStatus::Pending;
+        outbound.sequence = sequence;
+        outbound.amount = amount;
+        outbound.recipient_chain = params.recipient_chain;
+        outbound.recipient = params.recipient;
+        outbound.timestamp = ctx.accounts.clock.unix_timestamp;
+        outbound.payer = ctx.accounts.owner.key();
+
+        // Done.
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{
+    chains::{emitter_chain_key, wormhole_chain_id},
+    cat_struct::{CATSOLStructs, CrossChainStruct, U256},
+    constants::*,
+    error::ErrorFactory,
+    state::{Config, ForeignEmitter, OutboundStatus, OutboundTransfer, WormholeEmitter},
+    utils_cat::*,
+    BridgeOutParams,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer};
+use wormhole_anchor_sdk::wormhole;
+
+#[derive(Accounts)]
+#[instruction(params: BridgeOutParams)]
+/// Context used to bridge out through the owner's reusable message account.
+/// Same as [`crate::BridgeOut`], without a new message account.
+pub struct BridgeOutReusable<'info> {
+    #[account(mut)]
+    /// Owner will pay Wormhole fee to post a message, and the reusable message
+    /// account on first use.
+    pub owner: Signer<'info>,
+
+    /// Token Mint. The token that is bridged out.
+    pub token_mint: Box<Account<'info, Mint>>,
+
+    // Token Account that holds the tokens that are bridged out. It must
+    // delegate the amount to the lock account.
+    #[account(mut)]
+    pub token_user_ata: Box<Account<'info, TokenAccount>>,
+
+    // Token Mint ATA. Its an Associated Token Account owned by the Program that will hold the locked tokens
+    #[account(
+        mut,
+        seeds = [SEED_PREFIX_LOCK, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = token_mint_ata,
+    )]
+    pub token_mint_ata: Account<'info, TokenAccount>,
+
+    // Solana SPL Token Program
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        seeds = [Config::SEED_PREFIX],
+        bump,
+        constraint = config.native_token == token_mint.key()
+    )]
+    /// Config account. Wormhole PDAs specified in the config are checked
+    /// against the Wormhole accounts in this context. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    /// Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        mut,
+        address = config.wormhole.bridge @ ErrorFactory::InvalidWormholeConfig
+    )]
+    /// Wormhole bridge data. [`wormhole::post_message_unreliable`] requires
+    /// this account be mutable.
+    pub wormhole_bridge: Account<'info, wormhole::BridgeData>,
+
+    #[account(
+        mut,
+        address = config.wormhole.fee_collector @ ErrorFactory::InvalidWormholeFeeCollector
+    )]
+    /// Wormhole fee collector. [`wormhole::post_message_unreliable`] requires
+    /// this account be mutable.
+    pub wormhole_fee_collector: Account<'info, wormhole::FeeCollector>,
+
+    #[account(
+        seeds = [WormholeEmitter::SEED_PREFIX],
+        bump,
+    )]
+    /// Program's emitter account. Read-only.
+    pub wormhole_emitter: Account<'info, WormholeEmitter>,
+
+    #[account(
+        mut,
+        address = config.wormhole.sequence @ ErrorFactory::InvalidWormholeSequence
+    )]
+    /// Emitter's sequence account. [`wormhole::post_message_unreliable`]
+    /// requires this account be mutable.
+    pub wormhole_sequence: Account<'info, wormhole::SequenceTracker>,
+
+    #[account(
+        mut,
+        seeds = [
+            SEED_PREFIX_REUSABLE,
+            owner.key().as_ref()
+        ],
+        bump,
+    )]
+    /// CHECK: Owner's reusable Wormhole Message. Holds the owner's previous
+    /// transfer, if any. [`wormhole::post_message_unreliable`] requires this
+    /// account be mutable.
+    pub wormhole_message: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [
+            OutboundTransfer::SEED_PREFIX,
+            &wormhole_sequence.value().to_le_bytes()[..]
+        ],
+        bump,
+        space = OutboundTransfer::MAXIMUM_SIZE
+    )]
+    /// Outbound transfer account of the message's sequence.
+    pub outbound_transfer: Box<Account<'info, OutboundTransfer>>,
+
+    #[account(
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &emitter_chain_key(params.recipient_chain)[..]
+        ],
+        bump,
+        constraint = wormhole_chain_id(params.recipient_chain).map(u64::from) == Some(foreign_emitter.chain)
+    )]
+    /// Foreign Emitter account should exist for the recipient chain, under
+    /// its Wormhole chain ID. Read-only.
+    pub foreign_emitter: Account<'info, ForeignEmitter>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// Clock sysvar.
+    pub clock: Sysvar<'info, Clock>,
+
+    /// Rent sysvar.
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl BridgeOutReusable<'_> {
+    pub fn bridge_out_reusable(
+        ctx: Context<BridgeOutReusable>,
+        params: &BridgeOutParams,
+    ) -> Result<()> {
+        // The previous message is overwritten, give guardians time to observe
+        // it first.
+        let message = &ctx.accounts.wormhole_message;
+        if !message.data_is_empty() {
+            let previous =
+                wormhole::PostedVaaData::try_deserialize(&mut &message.data.borrow()[..])?;
+            require!(
+                ctx.accounts.clock.unix_timestamp
+                    >= i64::from(previous.posted_timestamp()) + REUSABLE_MESSAGE_DELAY,
+                ErrorFactory::ReusableMessageInUse
+            );
+        }
+
+        // Pay the Fee
+        wormhole::pay_message_fee(
+            &ctx.accounts.wormhole_bridge,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.wormhole_fee_collector.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+
+        // Transfer the tokens
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.token_user_ata.to_account_info(),
+            to: ctx.accounts.token_mint_ata.to_account_info(),
+            authority: ctx.accounts.token_mint_ata.to_account_info(),
+        };
+        let bump = ctx.bumps.token_mint_ata;
+
+        let cpi_signer_seeds = &[
+            SEED_PREFIX_LOCK.as_ref(),
+            &ctx.accounts.token_mint.key().to_bytes(),
+            &[bump],
+        ];
+        let cpi_signer = &[&cpi_signer_seeds[..]];
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, cpi_signer);
+
+        let balance_before = ctx.accounts.token_mint_ata.amount;
+
+        transfer(cpi_ctx, params.amount)?;
+
+        // Reload the account to get the updated balance
+        ctx.accounts.token_mint_ata.reload()?;
+        let amount_transferred = ctx.accounts.token_mint_ata.amount - balance_before;
+
+        // Normalize the amount to a Standard 8 decimals
+        let decimals = ctx.accounts.token_mint.decimals;
+        let foreign_amount = normalize_amount(amount_transferred, decimals);
+
+        // Plain transfers all have the same size, so the account can be
+        // posted to again.
+        let payload = CrossChainStruct {
+            amount: U256::from(foreign_amount),
+            token_decimals: decimals,
+            source_token_address: ctx.accounts.wormhole_emitter.key().to_bytes(),
+            source_user_address: ctx.accounts.token_user_ata.owner.to_bytes(),
+            source_token_chain: U256::from(CONVENTIONAL_SOLANA_ID), // Solana's Chain ID
+            dest_token_address: params.recipient_contract,
+            dest_user_address: params.recipient,
+            dest_token_chain: U256::from(params.recipient_chain),
+        };
+        let mut encoded_payload: Vec<u8> = Vec::new();
+        CATSOLStructs::CrossChainPayload { payload }.serialize(&mut encoded_payload)?;
+
+        // Record the transfer under its sequence.
+        let outbound = &mut ctx.accounts.outbound_transfer;
+        outbound.sender = ctx.accounts.token_user_ata.owner;
+        outbound.status = OutboundStatus::Pending;
+        outbound.sequence = ctx.accounts.wormhole_sequence.value();
+        outbound.amount = params.amount;
+        outbound.recipient_chain = params.recipient_chain;
+        outbound.recipient = params.recipient;
+        outbound.timestamp = ctx.accounts.clock.unix_timestamp;
+        outbound.payer = ctx.accounts.owner.key();
+
+        let wormhole_emitter = &ctx.accounts.wormhole_emitter;
+        let config = &ctx.accounts.config;
+
+        wormhole::post_message_unreliable(
+            CpiContext::new_with_signer(
+                ctx.accounts.wormhole_program.to_account_info(),
+                wormhole::PostMessage {
+                    config: ctx.accounts.wormhole_bridge.to_account_info(),
+                    message: ctx.accounts.wormhole_message.to_account_info(),
+                    emitter: wormhole_emitter.to_account_info(),
+                    sequence: ctx.accounts.wormhole_sequence.to_account_info(),
+                    payer: ctx.accounts.owner.to_account_info(),
+                    fee_collector: ctx.accounts.wormhole_fee_collector.to_account_info(),
+                    clock: ctx.accounts.clock.to_account_info(),
+                    rent: ctx.accounts.rent.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                },
+                &[
+                    &[
+                        SEED_PREFIX_REUSABLE,
+                        ctx.accounts.owner.key().as_ref(),
+                        &[ctx.bumps.wormhole_message],
+                    ],
+                    &[wormhole::SEED_PREFIX_EMITTER, &[wormhole_emitter.bump]],
+                ],
+            ),
+            config.batch_id,
+            encoded_payload,
+            config
+                .message_finality(&ctx.accounts.foreign_emitter, params.amount)
+                .into(),
+        )?;
+
+        // Done.
+        Ok(())
+    }
+}
@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::{
+    cat_struct::CATSOLStructs,
+    error::ErrorFactory,
+    state::{Config, Received, ReceivedBitmap},
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct CloseLegacyReceivedParams {
+    pub emitter_chain: u16,
+    pub sequence: u64,
+    /// Hash of the redeemed message, as stored in the legacy account.
+    pub vaa_hash: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(params: CloseLegacyReceivedParams)]
+pub struct CloseLegacyReceived<'info> {
+    /// Owner of the program. Pays for the received bitmap if it is new.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: Receives the rent of the closed account. Legacy accounts do not
+    /// record their payer, so the owner passes the account that paid for it,
+    /// looked up from the redeem transaction.
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            Received::SEED_PREFIX,
+            &params.emitter_chain.to_le_bytes()[..],
+            &params.sequence.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// CHECK: Legacy received account to close. It predates `Received::payer`
+    /// and does not deserialize as a `Received`, so it is checked by hand.
+    pub received: UncheckedAccount<'info>,
+
+    // Wormhole program.
+    pub wormhole_program: Program<'info, wormhole::program::Wormhole>,
+
+    #[account(
+        seeds = [
+            wormhole::SEED_PREFIX_POSTED_VAA,
+            &params.vaa_hash
+        ],
+        bump,
+        seeds::program = wormhole_program
+    )]
+    /// The redeemed Wormhole message. Its timestamp starts the retention
+    /// period. Read-only.
+    pub posted: Account<'info, wormhole::PostedVaa<CATSOLStructs>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [
+            ReceivedBitmap::SEED_PREFIX,
+            &params.emitter_chain.to_le_bytes()[..],
+            &ReceivedBitmap::index(params.sequence).to_le_bytes()[..]
+        ],
+        bump,
+        space = ReceivedBitmap::MAXIMUM_SIZE
+    )]
+    /// Received bitmap the message is moved to.
+    pub received_bitmap: Box<Account<'info, ReceivedBitmap>>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+
+impl CloseLegacyReceived<'_> {
+    pub fn close_legacy_received(ctx: Context<CloseLegacyReceived>, params: &CloseLegacyReceivedParams) -> Result<()> {
+        {
+            let data = ctx.accounts.received.try_borrow_data()?;
+            require!(
+                data.len() == Received::LEGACY_SIZE && data[..8] == Received::DISCRIMINATOR,
+                ErrorFactory::NotLegacyReceived
+            );
+            require!(data[8..] == params.vaa_hash, ErrorFactory::InvalidVaaHash);
+        }
+
+        let age = Clock::get()?.unix_timestamp - ctx.accounts.posted.timestamp() as i64;
+        require!(
+            age >= Received::RETENTION_PERIOD,
+            ErrorFactory::RetentionPeriodNotOver
+        );
+
+        // Keep the message from being replayed once the account is gone.
+        ctx.accounts.received_bitmap.mark_received(params.emitter_chain, params.sequence)?;
+
+        // Close the account, the same way Anchor's `close` constraint does.
+        let received = ctx.accounts.received.to_account_info();
+        let rent_recipient = ctx.accounts.rent_recipient.to_account_info();
+        let rent = received.lamports();
+        **received.try_borrow_mut_lamports()? = 0;
+        **rent_recipient.try_borrow_mut_lamports()? += rent;
+        received.assign(&System::id());
+        received.realloc(0, false)?;
+
+        // Done
+        Ok(())
+    }
+}
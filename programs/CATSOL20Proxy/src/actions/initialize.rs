@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program;
 use anchor_spl::{
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
@@ -161,6 +160,9 @@ impl Initialize<'_> {
         // so this value is stored as u8.
         config.finality = wormhole::Finality::Confirmed as u8;
 
+        // Zero means no amount forces Finalized.
+        config.finalized_threshold = 0;
+
         // Storing the BumpSeed for the Wormhole Emitter
         ctx.accounts.wormhole_emitter.bump = ctx.bumps.wormhole_emitter;
 
@@ -168,28 +170,19 @@ impl Initialize<'_> {
         // by posting a message to the Wormhole program.
         {
             // Pay the Fee
-            let fee = ctx.accounts.wormhole_bridge.fee();
-            if fee > 0 {
-                solana_program::program::invoke(
-                    &solana_program::system_instruction::transfer(
-                        &ctx.accounts.owner.key(),
-                        &ctx.accounts.wormhole_fee_collector.key(),
-                        fee,
-                    ),
-                    &ctx.accounts.to_account_infos(),
-                )?;
-            }
+            wormhole::pay_message_fee(
+                &ctx.accounts.wormhole_bridge,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.wormhole_fee_collector.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
 
             let wormhole_emitter = &ctx.accounts.wormhole_emitter;
             let config = &ctx.accounts.config;
 
             let mut payload: Vec<u8> = Vec::new();
-            CATSOLStructs::serialize(
-                &&CATSOLStructs::Alive {
-                    program_id: *ctx.program_id,
-                },
-                &mut payload,
-            )?;
+            CATSOLStructs::hello(&wormhole_emitter.key(), ctx.accounts.token_mint.decimals)
+                .serialize(&mut payload)?;
 
             wormhole::post_message(
                 CpiContext::new_with_signer(
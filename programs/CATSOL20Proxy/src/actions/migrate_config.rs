@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorFactory,
+    state::Config,
+    utils_cat::*
+};
+
+#[derive(Accounts)]
+/// Context used to grow a [`Config`] account created by an earlier version of
+/// the program to the current layout. Fields added since then are zeroed.
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    /// Owner of the program set in the [`Config`] account. Pays for the
+    /// larger account.
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    /// CHECK: Config account. It only deserializes once grown, so the owner
+    /// is checked by the handler.
+    pub config: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateConfig<'_> {
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        let config = ctx.accounts.config.to_account_info();
+        grow_account(
+            &config,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            Config::MAXIMUM_SIZE,
+        )?;
+
+        let data = config.try_borrow_data()?;
+        let config = Config::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(config.owner, ctx.accounts.owner.key(), ErrorFactory::OwnerOnly);
+
+        // Done
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorFactory,
+    state::{Config, ForeignEmitter},
+    utils_cat::*
+};
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct MigrateEmitterParams {
+    /// Chain the emitter is registered under.
+    pub chain: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(params: MigrateEmitterParams)]
+/// Context used to grow a [`ForeignEmitter`] account created by an earlier
+/// version of the program to the current layout. Fields added since then are
+/// zeroed.
+pub struct MigrateEmitter<'info> {
+    #[account(mut)]
+    /// Owner of the program set in the [`Config`] account. Pays for the
+    /// larger account.
+    pub owner: Signer<'info>,
+
+    #[account(
+        has_one = owner @ ErrorFactory::OwnerOnly,
+        seeds = [Config::SEED_PREFIX],
+        bump
+    )]
+    /// Config account. Must be migrated first. Read-only.
+    pub config: Box<Account<'info, Config>>,
+
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [
+            ForeignEmitter::SEED_PREFIX,
+            &params.chain.to_le_bytes()[..]
+        ],
+        bump
+    )]
+    /// CHECK: Foreign emitter account. It only deserializes once grown, which
+    /// the handler checks.
+    pub foreign_emitter: UncheckedAccount<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateEmitter<'_> {
+    pub fn migrate_emitter(ctx: Context<MigrateEmitter>, _params: &MigrateEmitterParams) -> Result<()> {
+        let foreign_emitter = ctx.accounts.foreign_emitter.to_account_info();
+        grow_account(
+            &foreign_emitter,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ForeignEmitter::MAXIMUM_SIZE,
+        )?;
+
+        let data = foreign_emitter.try_borrow_data()?;
+        ForeignEmitter::try_deserialize(&mut &data[..])?;
+
+        // Done
+        Ok(())
+    }
+}
@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
 use crate::{
+    chains::is_emitter_chain,
     error::ErrorFactory,
     state::{Config, ForeignEmitter}
 };
 
-use wormhole_anchor_sdk::wormhole;
 
 #[derive(Clone, AnchorSerialize, AnchorDeserialize)]
 pub struct RegisterEmitterParams {
@@ -47,10 +47,11 @@ impl RegisterEmitter<'_> {
     ) -> Result<()> {
         let chain = params.chain;
         let address = params.address;
-        // Foreign emitter cannot share the same Wormhole Chain ID as the
-        // Solana Wormhole program's. And cannot register a zero address.
+        // Foreign emitter must be on a chain in the registry, and cannot share
+        // the same Wormhole Chain ID as the Solana Wormhole program's. And
+        // cannot register a zero address.
         require!(
-            chain > 0 && chain != wormhole::CHAIN_ID_SOLANA as u64 as u64 && !address.iter().all(|&x| x == 0),
+            is_emitter_chain(chain) && !address.iter().all(|&x| x == 0),
             ErrorFactory::InvalidForeignEmitter,
         );
 
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_lang::{prelude::Pubkey, AnchorDeserialize, AnchorSerialize};
 use std::io::{self, Read, Write};
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
 pub struct CrossChainStruct {
     pub amount: U256,
     pub token_decimals: u8,
@@ -14,7 +14,7 @@ pub struct CrossChainStruct {
     pub dest_token_chain: U256,
 }
 
-#[derive(PartialEq, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+#[derive(PartialEq, AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Eq)]
 pub struct U256 {
     pub bytes: [u8; 32],
 }
@@ -35,86 +35,291 @@ impl Into<u64> for U256 {
     }
 }
 
-#[derive(Clone)]
+impl CrossChainStruct {
+    /// Encoded size of a [`CrossChainStruct`].
+    pub const LEN: usize = 32 // amount
+        + 1 // token_decimals
+        + 32 // source_token_address
+        + 32 // source_user_address
+        + 32 // source_token_chain
+        + 32 // dest_token_address
+        + 32 // dest_user_address
+        + 32 // dest_token_chain
+    ;
+
+    fn write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        self.amount.serialize(writer)?;
+        writer.write_all(&self.token_decimals.to_le_bytes())?;
+        writer.write_all(&self.source_token_address)?;
+        writer.write_all(&self.source_user_address)?;
+        self.source_token_chain.serialize(writer)?;
+        writer.write_all(&self.dest_token_address)?;
+        writer.write_all(&self.dest_user_address)?;
+        self.dest_token_chain.serialize(writer)?;
+        Ok(())
+    }
+
+    fn read(bytes: &mut &[u8]) -> io::Result<Self> {
+        let mut amount_bytes = [0u8; 32];
+        bytes.read_exact(&mut amount_bytes)?;
+        let amount = U256 { bytes: amount_bytes };
+
+        let mut token_decimals_bytes = [0u8; 1];
+        bytes.read_exact(&mut token_decimals_bytes)?;
+        let token_decimals = u8::from_le_bytes(token_decimals_bytes);
+
+        let mut source_token_address = [0u8; 32];
+        bytes.read_exact(&mut source_token_address)?;
+
+        let mut source_user_address = [0u8; 32];
+        bytes.read_exact(&mut source_user_address)?;
+
+        let mut source_token_chain_bytes = [0u8; 32];
+        bytes.read_exact(&mut source_token_chain_bytes)?;
+        let source_token_chain = U256 {
+            bytes: source_token_chain_bytes,
+        };
+
+        let mut dest_token_address = [0u8; 32];
+        bytes.read_exact(&mut dest_token_address)?;
+
+        let mut dest_user_address = [0u8; 32];
+        bytes.read_exact(&mut dest_user_address)?;
+
+        let mut dest_token_chain_bytes = [0u8; 32];
+        bytes.read_exact(&mut dest_token_chain_bytes)?;
+        let dest_token_chain = U256 {
+            bytes: dest_token_chain_bytes,
+        };
+
+        Ok(CrossChainStruct {
+            amount,
+            token_decimals,
+            source_token_address,
+            source_user_address,
+            source_token_chain,
+            dest_token_address,
+            dest_user_address,
+            dest_token_chain,
+        })
+    }
+}
+
+/// Payload ID of a [`CATSOLStructs::Refund`].
+pub const PAYLOAD_ID_REFUND: u8 = 1;
+
+/// Payload ID of a [`CATSOLStructs::TransferWithPayload`].
+pub const PAYLOAD_ID_TRANSFER_WITH_PAYLOAD: u8 = 2;
+
+/// Payload ID of a [`CATSOLStructs::Transfer`].
+pub const PAYLOAD_ID_TRANSFER: u8 = 3;
+
+/// Payload ID of a [`CATSOLStructs::Attest`].
+pub const PAYLOAD_ID_ATTEST: u8 = 4;
+
+/// Payload ID of a [`CATSOLStructs::Hello`].
+pub const PAYLOAD_ID_HELLO: u8 = 5;
+
+/// Version of the [`CATSOLStructs::Hello`] this program posts.
+pub const HELLO_VERSION: u8 = 1;
+
+/// How the `dest_user_address` of a [`CATSOLStructs::Transfer`] is read.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecipientType {
+    /// A wallet. Tokens go to its Associated Token Account.
+    Wallet,
+    /// A token account, e.g. a program-owned vault or a multisig account.
+    TokenAccount,
+}
+
+/// Cross-chain messages.
+///
+/// `CrossChainPayload` predates payload IDs and is told apart by its size
+/// ([`CrossChainStruct::LEN`] bytes). Every other variant starts with a
+/// one-byte payload ID and must never encode to that size.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CATSOLStructs {
-    Alive { program_id: Pubkey },
     CrossChainPayload { payload: CrossChainStruct },
+    /// Sent back by a foreign emitter that could not deliver the transfer
+    /// posted with `sequence`. `payload` is that transfer, as received.
+    Refund { sequence: u64, payload: CrossChainStruct },
+    /// A transfer to a program rather than a wallet. `dest_user_address` is
+    /// the recipient program, which is invoked with `data` once the tokens
+    /// are delivered.
+    TransferWithPayload {
+        payload: CrossChainStruct,
+        data: Vec<u8>,
+    },
+    /// A `CrossChainPayload` that says whether `dest_user_address` is a
+    /// wallet or a token account.
+    Transfer {
+        payload: CrossChainStruct,
+        recipient_type: RecipientType,
+    },
+    /// A deployment's token details, for other chains to check theirs
+    /// against. `symbol` and `name` are UTF-8, right-padded with zeros.
+    Attest {
+        token_address: [u8; 32],
+        token_chain: U256,
+        decimals: u8,
+        symbol: [u8; 32],
+        name: [u8; 32],
+    },
+    /// Handshake of a deployment: its Wormhole `emitter`, CAT `chain` ID,
+    /// token `decimals` and message `version`.
+    Hello {
+        emitter: [u8; 32],
+        chain: U256,
+        decimals: u8,
+        version: u8,
+    },
+}
+
+impl CATSOLStructs {
+    /// This program's [`CATSOLStructs::Hello`].
+    pub fn hello(emitter: &Pubkey, decimals: u8) -> Self {
+        CATSOLStructs::Hello {
+            emitter: emitter.to_bytes(),
+            chain: U256::from(crate::CONVENTIONAL_SOLANA_ID),
+            decimals,
+            version: HELLO_VERSION,
+        }
+    }
 }
 
 impl AnchorSerialize for CATSOLStructs {
     fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
         match self {
-            CATSOLStructs::Alive { program_id } => program_id.serialize(writer),
-            CATSOLStructs::CrossChainPayload { payload } => {
-                payload.amount.serialize(writer)?;
-                writer.write_all(&payload.token_decimals.to_le_bytes())?;
-                writer.write_all(&payload.source_token_address)?;
-                writer.write_all(&payload.source_user_address)?;
-                payload.source_token_chain.serialize(writer)?;
-                writer.write_all(&payload.dest_token_address)?;
-                writer.write_all(&payload.dest_user_address)?;
-                payload.dest_token_chain.serialize(writer)?;
-                Ok(())
+            CATSOLStructs::CrossChainPayload { payload } => payload.write(writer),
+            CATSOLStructs::Refund { sequence, payload } => {
+                writer.write_all(&[PAYLOAD_ID_REFUND])?;
+                writer.write_all(&sequence.to_be_bytes())?;
+                payload.write(writer)
+            }
+            CATSOLStructs::TransferWithPayload { payload, data } => {
+                let len = u32::try_from(data.len())
+                    .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Payload too large"))?;
+                writer.write_all(&[PAYLOAD_ID_TRANSFER_WITH_PAYLOAD])?;
+                payload.write(writer)?;
+                writer.write_all(&len.to_be_bytes())?;
+                writer.write_all(data)
+            }
+            CATSOLStructs::Transfer {
+                payload,
+                recipient_type,
+            } => {
+                writer.write_all(&[PAYLOAD_ID_TRANSFER])?;
+                payload.write(writer)?;
+                recipient_type.serialize(writer)
+            }
+            CATSOLStructs::Attest {
+                token_address,
+                token_chain,
+                decimals,
+                symbol,
+                name,
+            } => {
+                writer.write_all(&[PAYLOAD_ID_ATTEST])?;
+                writer.write_all(token_address)?;
+                token_chain.serialize(writer)?;
+                writer.write_all(&[*decimals])?;
+                writer.write_all(symbol)?;
+                writer.write_all(name)
+            }
+            CATSOLStructs::Hello {
+                emitter,
+                chain,
+                decimals,
+                version,
+            } => {
+                writer.write_all(&[PAYLOAD_ID_HELLO])?;
+                writer.write_all(emitter)?;
+                chain.serialize(writer)?;
+                writer.write_all(&[*decimals, *version])
             }
         }
     }
 }
 
-// Should have a Discriminator field, but we don't need it in this case as Alive is only sent once.
 impl AnchorDeserialize for CATSOLStructs {
     fn deserialize(bytes: &mut &[u8]) -> io::Result<Self> {
-        if bytes.len() == 32 {
-            // Assume this is an Alive variant, as it's the size of a Pubkey
-            let program_id = Pubkey::deserialize(bytes)?;
-            Ok(CATSOLStructs::Alive { program_id })
-        } else {
-            // Assume this is a CrossChainPayload variant otherwise
-            let mut amount_bytes = [0u8; 32];
-            bytes.read_exact(&mut amount_bytes)?;
-            let amount = U256 { bytes: amount_bytes };
-
-            let mut token_decimals_bytes = [0u8; 1];
-            bytes.read_exact(&mut token_decimals_bytes)?;
-            let token_decimals = u8::from_le_bytes(token_decimals_bytes);
-
-            let mut source_token_address = [0u8; 32];
-            bytes.read_exact(&mut source_token_address)?;
-
-            let mut source_user_address = [0u8; 32];
-            bytes.read_exact(&mut source_user_address)?;
-
-            let mut source_token_chain_bytes = [0u8; 32];
-            bytes.read_exact(&mut source_token_chain_bytes)?;
-            let source_token_chain = U256 {
-                bytes: source_token_chain_bytes,
-            };
-
-            let mut dest_token_address = [0u8; 32];
-            bytes.read_exact(&mut dest_token_address)?;
-
-            let mut dest_user_address = [0u8; 32];
-            bytes.read_exact(&mut dest_user_address)?;
-
-            let mut dest_token_chain_bytes = [0u8; 32];
-            bytes.read_exact(&mut dest_token_chain_bytes)?;
-            let dest_token_chain = U256 {
-                bytes: dest_token_chain_bytes,
-            };
-
-            let payload = CrossChainStruct {
-                amount,
-                token_decimals,
-                source_token_address,
-                source_user_address,
-                source_token_chain,
-                dest_token_address,
-                dest_user_address,
-                dest_token_chain,
-            };
-            Ok(CATSOLStructs::CrossChainPayload { payload })
+        match bytes.len() {
+            CrossChainStruct::LEN => {
+                let payload = CrossChainStruct::read(bytes)?;
+                Ok(CATSOLStructs::CrossChainPayload { payload })
+            }
+            _ => match u8::deserialize(bytes)? {
+                PAYLOAD_ID_REFUND => {
+                    let mut sequence_bytes = [0u8; 8];
+                    bytes.read_exact(&mut sequence_bytes)?;
+                    let sequence = u64::from_be_bytes(sequence_bytes);
+
+                    let payload = CrossChainStruct::read(bytes)?;
+                    Ok(CATSOLStructs::Refund { sequence, payload })
+                }
+                PAYLOAD_ID_TRANSFER_WITH_PAYLOAD => {
+                    let payload = CrossChainStruct::read(bytes)?;
+
+                    let mut len_bytes = [0u8; 4];
+                    bytes.read_exact(&mut len_bytes)?;
+                    let len = u32::from_be_bytes(len_bytes) as usize;
+
+                    // Check the length before allocating for it.
+                    if len > bytes.len() {
+                        return Err(io::ErrorKind::UnexpectedEof.into());
+                    }
+                    let (data, rest) = bytes.split_at(len);
+                    let data = data.to_vec();
+                    *bytes = rest;
+
+                    Ok(CATSOLStructs::TransferWithPayload { payload, data })
+                }
+                PAYLOAD_ID_TRANSFER => {
+                    let payload = CrossChainStruct::read(bytes)?;
+                    let recipient_type = RecipientType::deserialize(bytes)?;
+                    Ok(CATSOLStructs::Transfer {
+                        payload,
+                        recipient_type,
+                    })
+                }
+                PAYLOAD_ID_ATTEST => {
+                    let mut token_address = [0u8; 32];
+                    bytes.read_exact(&mut token_address)?;
+                    let token_chain = U256::deserialize(bytes)?;
+                    let decimals = u8::deserialize(bytes)?;
+                    let mut symbol = [0u8; 32];
+                    bytes.read_exact(&mut symbol)?;
+                    let mut name = [0u8; 32];
+                    bytes.read_exact(&mut name)?;
+                    Ok(CATSOLStructs::Attest {
+                        token_address,
+                        token_chain,
+                        decimals,
+                        symbol,
+                        name,
+                    })
+                }
+                PAYLOAD_ID_HELLO => {
+                    let mut emitter = [0u8; 32];
+                    bytes.read_exact(&mut emitter)?;
+                    let chain = U256::deserialize(bytes)?;
+                    let decimals = u8::deserialize(bytes)?;
+                    let version = u8::deserialize(bytes)?;
+                    Ok(CATSOLStructs::Hello {
+                        emitter,
+                        chain,
+                        decimals,
+                        version,
+                    })
+                }
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Unknown payload ID",
+                )),
+            },
         }
     }
-    
+
     fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
         let mut buf = Vec::new();
         reader.read_to_end(&mut buf)?;
@@ -1,10 +1,28 @@
-pub const CORE_BRIDGE_ADDRESS: &str = "Bridge1p5gheXUvJ6jGWGeCsgPKgnE3YgdGKRVCMY9o";
-
 // SEED Prefix for the Wormhole Message Account
 pub const SEED_PREFIX_SENT: &[u8; 4] = b"sent";
 
+// SEED Prefix for the Wormhole Message Account of a Token Bridge transfer
+pub const SEED_PREFIX_PORTAL: &[u8; 6] = b"portal";
+
+// SEED Prefix for a sender's reusable Wormhole Message Account
+pub const SEED_PREFIX_REUSABLE: &[u8; 8] = b"reusable";
+
+// Seconds a message stays in a reusable Wormhole Message Account before it
+// may be overwritten, so guardians observe it first. A message the guardians
+// miss in that window is lost once overwritten: the transfer can then only be
+// recovered by a refund from the destination chain.
+pub const REUSABLE_MESSAGE_DELAY: i64 = 60;
+
 // SEED Prefix for the Mint Account
 pub const SEED_PREFIX_LOCK: &'static [u8; 13] = b"cat_sol_proxy";
 
+// SEED Prefix for a recipient program's Redeemer Account, derived from the
+// recipient program. It owns the tokens of a transfer with payload.
+pub const SEED_PREFIX_REDEEMER: &[u8; 8] = b"redeemer";
+
+// SEED Prefix for the Payload Authority. It signs the call into a recipient
+// program, which checks it to know the call comes from this program.
+pub const SEED_PREFIX_PAYLOAD_AUTHORITY: &[u8; 17] = b"payload_authority";
+
 // Conventional Solana ID
 pub const CONVENTIONAL_SOLANA_ID: u64 = 17;
\ No newline at end of file
@@ -66,4 +66,83 @@ pub enum ErrorFactory {
 
     #[msg("AlreadyOwner: The account is already the owner.")]
     AlreadyOwner,
+
+    #[msg("InvalidRefund: The refunded transfer was not sent by this program to the refunding chain.")]
+    /// A refund must reference a transfer this program sent to the chain the refund comes from.
+    InvalidRefund,
+
+    #[msg("InvalidRecipientProgram: The recipient program is not the one named in the transfer.")]
+    /// A transfer with payload can only be delivered to the program in its `dest_user_address`.
+    InvalidRecipientProgram,
+
+    #[msg("InvalidRecipientType: The transfer's recipient type is not redeemed by this instruction.")]
+    /// Wallet recipients are redeemed to their ATA, token account recipients to the account itself.
+    InvalidRecipientType,
+
+    #[msg("InvalidTokenAccountMint: The token account does not hold the bridged token.")]
+    InvalidTokenAccountMint,
+
+    #[msg("AlreadyReceived: The message has already been redeemed.")]
+    AlreadyReceived,
+
+    #[msg("RetentionPeriodNotOver: The received account cannot be closed yet.")]
+    /// Received accounts are kept for `Received::RETENTION_PERIOD` after the message's timestamp.
+    RetentionPeriodNotOver,
+
+    #[msg("InvalidFinality: Finality must be Confirmed (0) or Finalized (1).")]
+    InvalidFinality,
+
+    #[msg("StaleVaa: The message is older than the emitter chain allows.")]
+    StaleVaa,
+
+    #[msg("InsufficientConsistencyLevel: The message's consistency level is not one the emitter chain's policy accepts.")]
+    InsufficientConsistencyLevel,
+
+    #[msg("InvalidVaaHash: The posted VAA does not hash to the given vaa_hash.")]
+    InvalidVaaHash,
+
+    #[msg("StaleAttestation: A newer attestation from this chain has been received.")]
+    StaleAttestation,
+
+    #[msg("AttestationMismatch: The attested symbol or name differs from this token's metadata.")]
+    AttestationMismatch,
+
+    #[msg("InvalidMetadata: The metadata account could not be read.")]
+    InvalidMetadata,
+
+    #[msg("EmitterNotApproved: The Hello's emitter was not approved by the owner.")]
+    EmitterNotApproved,
+
+    #[msg("InvalidHello: The Hello does not name the emitter and chain that posted it.")]
+    InvalidHello,
+
+    #[msg("ReusableMessageInUse: The previous message in the reusable message account may not be observed yet.")]
+    ReusableMessageInUse,
+
+    #[msg("AmountTooSmall: The amount is zero once truncated to 8 decimals.")]
+    AmountTooSmall,
+
+    #[msg("InvalidPortalTransfer: The Token Bridge transfer does not match its CAT payload.")]
+    InvalidPortalTransfer,
+
+    #[msg("InvalidRentRecipient: The rent recipient is not the account that paid for the closed account.")]
+    InvalidRentRecipient,
+
+    #[msg("NotLegacyReceived: The received account is not a legacy account. Close it with close_received.")]
+    NotLegacyReceived,
+
+    #[msg("TooManyConsistencyLevels: An emitter policy accepts at most MAX_CONSISTENCY_LEVELS consistency levels.")]
+    TooManyConsistencyLevels,
+
+    #[msg("TransferNotPending: The outbound transfer is not pending.")]
+    TransferNotPending,
+
+    #[msg("OutboundTransferMismatch: The refund does not match the recorded outbound transfer.")]
+    OutboundTransferMismatch,
+
+    #[msg("PayerOrOwnerOnly: Only the payer of the account or the owner of the program can close it.")]
+    PayerOrOwnerOnly,
+
+    #[msg("OutboundTransferNotSettled: The outbound transfer is pending and its retention period is not over.")]
+    OutboundTransferNotSettled,
 }
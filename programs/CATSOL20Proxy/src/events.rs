@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[event]
+/// Emitted for every redeemed message. The received bitmap only records that
+/// a sequence was redeemed; this is the record of what it delivered.
+pub struct Redeemed {
+    /// Emitter chain of the message.
+    pub emitter_chain: u16,
+    /// Sequence of the message.
+    pub sequence: u64,
+    /// Keccak256 hash of the VAA body, recomputed from the posted account.
+    pub vaa_hash: [u8; 32],
+    /// Tokens delivered, in the token's decimals.
+    pub amount: u64,
+    /// Token account the tokens went to.
+    pub recipient: Pubkey,
+}
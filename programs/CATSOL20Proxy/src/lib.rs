@@ -9,12 +9,18 @@ pub use cat_struct::*;
 pub mod error;
 pub use error::*;
 
+pub mod events;
+pub use events::*;
+
 pub mod state;
 pub use state::*;
 
 pub mod constants;
 pub use constants::*;
 
+pub mod chains;
+pub use chains::*;
+
 pub mod utils;
 pub use utils::*;
 
@@ -40,7 +46,100 @@ pub mod cat_sol20_proxy {
         BridgeOut::bridge_out(ctx, &params)
     }
 
+    pub fn bridge_out_with_payload(
+        ctx: Context<BridgeOut>,
+        params: BridgeOutParams,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        BridgeOut::bridge_out_with_payload(ctx, &params, data)
+    }
+
+    pub fn bridge_out_reusable(
+        ctx: Context<BridgeOutReusable>,
+        params: BridgeOutParams,
+    ) -> Result<()> {
+        BridgeOutReusable::bridge_out_reusable(ctx, &params)
+    }
+
     pub fn bridge_in(ctx: Context<BridgeIn>, params: BridgeInParams) -> Result<()> {
         BridgeIn::bridge_in(ctx, params)
     }
+
+    pub fn bridge_in_with_payload<'info>(
+        ctx: Context<'_, '_, '_, 'info, BridgeInWithPayload<'info>>,
+        params: BridgeInParams,
+    ) -> Result<()> {
+        BridgeInWithPayload::bridge_in_with_payload(ctx, params)
+    }
+
+    pub fn bridge_in_to_token_account(
+        ctx: Context<BridgeInToTokenAccount>,
+        params: BridgeInParams,
+    ) -> Result<()> {
+        BridgeInToTokenAccount::bridge_in_to_token_account(ctx, params)
+    }
+
+    pub fn bridge_out_portal(ctx: Context<BridgeOutPortal>, params: BridgeOutParams) -> Result<()> {
+        BridgeOutPortal::bridge_out_portal(ctx, &params)
+    }
+
+    pub fn bridge_in_portal(ctx: Context<BridgeInPortal>, params: BridgeInParams) -> Result<()> {
+        BridgeInPortal::bridge_in_portal(ctx, params)
+    }
+
+    pub fn refund(ctx: Context<Refund>, params: RefundParams) -> Result<()> {
+        Refund::refund(ctx, params)
+    }
+
+    pub fn close_received(ctx: Context<CloseReceived>, params: CloseReceivedParams) -> Result<()> {
+        CloseReceived::close_received(ctx, &params)
+    }
+
+    pub fn close_legacy_received(ctx: Context<CloseLegacyReceived>, params: CloseLegacyReceivedParams) -> Result<()> {
+        CloseLegacyReceived::close_legacy_received(ctx, &params)
+    }
+
+    pub fn close_outbound_transfer(ctx: Context<CloseOutboundTransfer>) -> Result<()> {
+        CloseOutboundTransfer::close_outbound_transfer(ctx)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        MigrateConfig::migrate_config(ctx)
+    }
+
+    pub fn migrate_emitter(ctx: Context<MigrateEmitter>, params: MigrateEmitterParams) -> Result<()> {
+        MigrateEmitter::migrate_emitter(ctx, &params)
+    }
+
+    pub fn set_message_params(ctx: Context<SetMessageParams>, params: SetMessageParamsParams) -> Result<()> {
+        SetMessageParams::set_message_params(ctx, &params)
+    }
+
+    pub fn set_emitter_finality(ctx: Context<SetEmitterFinality>, params: SetEmitterFinalityParams) -> Result<()> {
+        SetEmitterFinality::set_emitter_finality(ctx, &params)
+    }
+
+    pub fn set_emitter_policy(ctx: Context<SetEmitterPolicy>, params: SetEmitterPolicyParams) -> Result<()> {
+        SetEmitterPolicy::set_emitter_policy(ctx, &params)
+    }
+
+    pub fn attest_token(ctx: Context<AttestToken>) -> Result<()> {
+        AttestToken::attest_token(ctx)
+    }
+
+    pub fn receive_attestation(ctx: Context<ReceiveAttestation>, params: ReceiveAttestationParams) -> Result<()> {
+        ReceiveAttestation::receive_attestation(ctx, &params)
+    }
+
+    pub fn approve_emitter(ctx: Context<ApproveEmitter>, params: ApproveEmitterParams) -> Result<()> {
+        ApproveEmitter::approve_emitter(ctx, &params)
+    }
+
+    pub fn post_hello(ctx: Context<PostHello>) -> Result<()> {
+        PostHello::post_hello(ctx)
+    }
+
+    pub fn receive_hello(ctx: Context<ReceiveHello>, params: ReceiveHelloParams) -> Result<()> {
+        ReceiveHello::receive_hello(ctx, &params)
+    }
 }
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+/// Approved emitter account. An emitter the owner expects a `Hello` from.
+/// Redeeming that `Hello` registers the emitter and closes this account.
+pub struct ApprovedEmitter {
+    /// Emitter chain. Cannot equal `1` (Solana's Chain ID).
+    pub chain: u64,
+    /// Emitter address. Cannot be zero address.
+    pub address: [u8; 32],
+}
+
+impl ApprovedEmitter {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 8 // chain
+        + 32 // address
+    ;
+    /// AKA `b"approved_emitter"`.
+    pub const SEED_PREFIX: &'static [u8; 16] = b"approved_emitter";
+}
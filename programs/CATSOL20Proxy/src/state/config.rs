@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole::Finality;
+
+use crate::state::ForeignEmitter;
 
 #[derive(Default, AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 /// Wormhole program related addresses.
@@ -27,14 +30,17 @@ pub struct Config {
     pub owner: Pubkey,
     /// Wormhole program's relevant addresses.
     pub wormhole: WormholeAddresses,
-    /// AKA batch_id. Just zero, but saving this information in this account
-    /// anyway.
+    /// AKA batch_id. Zero unless changed with `set_message_params`.
     pub batch_id: u32,
     /// AKA consistency level. u8 representation of Solana's
-    /// [Finality](wormhole_anchor_sdk::wormhole::Finality).
+    /// [Finality](wormhole_anchor_sdk::wormhole::Finality). Default for
+    /// destinations without their own.
     pub finality: u8,
     ///  Native token
     pub native_token: Pubkey,
+    /// Transfers of at least this amount (in the token's decimals) are
+    /// published as Finalized, whatever the destination. Zero disables it.
+    pub finalized_threshold: u64,
 }
 
 impl Config {
@@ -44,7 +50,17 @@ impl Config {
         + 4 // batch_id
         + 1 // finality
         + 32 // native_tokens
+        + 8 // finalized_threshold
     ;
     /// AKA `b"config"`.
     pub const SEED_PREFIX: &'static [u8; 6] = b"config";
+
+    /// Finality to publish a transfer of `amount` to `foreign_emitter`'s chain
+    /// with.
+    pub fn message_finality(&self, foreign_emitter: &ForeignEmitter, amount: u64) -> u8 {
+        if self.finalized_threshold > 0 && amount >= self.finalized_threshold {
+            return Finality::Finalized as u8;
+        }
+        foreign_emitter.finality.unwrap_or(self.finality)
+    }
 }
@@ -1,20 +1,38 @@
 use anchor_lang::prelude::*;
+use wormhole_anchor_sdk::wormhole;
+
+use crate::error::ErrorFactory;
 
 #[account]
 #[derive(Default)]
 /// Foreign emitter account data.
 pub struct ForeignEmitter {
-    /// Emitter chain. Cannot equal `1` (Solana's Chain ID).
+    /// Emitter's Wormhole chain ID. Must be in the chain registry, and cannot
+    /// equal `1` (Solana's Chain ID).
     pub chain: u64,
     /// Emitter address. Cannot be zero address.
     pub address: [u8; 32],
+    /// Finality of messages sent to this chain, overriding the config's.
+    pub finality: Option<u8>,
+    /// Maximum age in seconds of messages redeemed from this chain, measured
+    /// from the VAA's timestamp. Zero means no limit.
+    pub max_vaa_age: u32,
+    /// Consistency levels accepted from this chain. Empty accepts any.
+    /// Levels are chain-specific codes, not ranks: on EVM chains `200` is
+    /// instant, `201` safe and other values finalized.
+    pub consistency_levels: Vec<u8>,
 }
 
 impl ForeignEmitter {
     pub const MAXIMUM_SIZE: usize = 8 // discriminator
         + 8 // chain
         + 32 // address
+        + 1 + 1 // finality
+        + 4 // max_vaa_age
+        + 4 + Self::MAX_CONSISTENCY_LEVELS // consistency_levels
     ;
+    /// Most consistency levels a chain's policy can accept.
+    pub const MAX_CONSISTENCY_LEVELS: usize = 8;
     /// AKA `b"foreign_emitter"`.
     pub const SEED_PREFIX: &'static [u8; 15] = b"foreign_emitter";
 
@@ -23,4 +41,23 @@ impl ForeignEmitter {
     pub fn verify(&self, address: &[u8; 32]) -> bool {
         *address == self.address
     }
+
+    /// Check a message from this chain against its age and consistency level
+    /// policy.
+    pub fn check_policy<D: AnchorSerialize + AnchorDeserialize>(
+        &self,
+        posted: &wormhole::PostedVaa<D>,
+    ) -> Result<()> {
+        require!(
+            self.consistency_levels.is_empty()
+                || self.consistency_levels.contains(&posted.finality()),
+            ErrorFactory::InsufficientConsistencyLevel
+        );
+
+        if self.max_vaa_age > 0 {
+            let age = Clock::get()?.unix_timestamp - posted.timestamp() as i64;
+            require!(age <= self.max_vaa_age as i64, ErrorFactory::StaleVaa);
+        }
+        Ok(())
+    }
 }
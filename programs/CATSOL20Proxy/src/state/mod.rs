@@ -1,9 +1,19 @@
+pub use approved_emitter::*;
 pub use config::*;
+pub use foreign_attestation::*;
 pub use foreign_emitter::*;
+pub use outbound_transfer::*;
 pub use received::*;
+pub use received_bitmap::*;
+pub use refunded::*;
 pub use wormhole_emitter::*;
 
+pub mod approved_emitter;
 pub mod config;
+pub mod foreign_attestation;
 pub mod foreign_emitter;
+pub mod outbound_transfer;
 pub mod received;
+pub mod received_bitmap;
+pub mod refunded;
 pub mod wormhole_emitter;
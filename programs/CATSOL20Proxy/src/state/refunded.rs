@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug)]
+/// Refunded account. Exists once the outbound transfer with the sequence in
+/// its seeds has been refunded.
+pub struct Refunded {
+    /// Keccak256 hash of the verified Wormhole refund message.
+    pub wormhole_message_hash: [u8; 32],
+}
+
+impl Refunded {
+    pub const MAXIMUM_SIZE: usize = 8 // discriminator
+        + 32 // wormhole_message_hash
+    ;
+    /// AKA `b"refunded"`.
+    pub const SEED_PREFIX: &'static [u8; 8] = b"refunded";
+}
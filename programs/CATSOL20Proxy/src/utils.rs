@@ -1,4 +1,10 @@
 pub mod utils_cat {
+    use anchor_lang::prelude::*;
+    use mpl_token_metadata::state::{Metadata, TokenMetadataAccount};
+    use wormhole_anchor_sdk::wormhole;
+
+    use crate::error::ErrorFactory;
+
     pub fn normalize_amount(amount: u64, decimals: u8) -> u64 {
         if decimals > 8 {
             amount / 10u64.pow((decimals - 8).into())
@@ -14,4 +20,59 @@ pub mod utils_cat {
             amount
         }
     }
+
+    /// Grow `account` to `space` bytes, zeroing the new bytes. `payer` tops
+    /// up its rent. Used to migrate accounts to a newer layout.
+    pub fn grow_account<'info>(
+        account: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        space: usize,
+    ) -> Result<()> {
+        if account.data_len() >= space {
+            return Ok(());
+        }
+
+        let rent = Rent::get()?.minimum_balance(space);
+        if rent > account.lamports() {
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: payer.clone(),
+                to: account.clone(),
+            };
+            let cpi_ctx = CpiContext::new(system_program.clone(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, rent - account.lamports())?;
+        }
+        account.realloc(space, true).map_err(Into::into)
+    }
+
+    /// Current value of a core bridge sequence tracker: the sequence of the
+    /// emitter's next message. Zero if the emitter has not posted yet.
+    pub fn sequence_value(sequence: &AccountInfo) -> Result<u64> {
+        if sequence.data_is_empty() {
+            return Ok(0);
+        }
+        let data = sequence.try_borrow_data()?;
+        Ok(wormhole::SequenceTracker::try_deserialize(&mut &data[..])?.value())
+    }
+
+    /// `value` as an attested symbol or name: UTF-8, cut to 32 bytes and
+    /// right-padded with zeros. Metaplex's own zero padding is dropped first.
+    pub fn attest_bytes(value: &str) -> [u8; 32] {
+        let value = value.trim_end_matches('\0').as_bytes();
+        let len = value.len().min(32);
+        let mut bytes = [0u8; 32];
+        bytes[..len].copy_from_slice(&value[..len]);
+        bytes
+    }
+
+    /// Attested `(symbol, name)` of a Metaplex metadata account. Both are zero
+    /// if the mint has no metadata.
+    pub fn metadata_attest_bytes(metadata: &AccountInfo) -> Result<([u8; 32], [u8; 32])> {
+        if metadata.data_is_empty() {
+            return Ok(([0u8; 32], [0u8; 32]));
+        }
+        let metadata = Metadata::safe_deserialize(&metadata.try_borrow_data()?)
+            .map_err(|_| ErrorFactory::InvalidMetadata)?;
+        Ok((attest_bytes(&metadata.data.symbol), attest_bytes(&metadata.data.name)))
+    }
 }
@@ -0,0 +1,1629 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use cat_sol20_proxy::{
+    utils_cat::*, BridgeOut, CATSOLStructs, Config, CrossChainStruct, ErrorFactory,
+    ForeignAttestation, ForeignEmitter, OutboundStatus, OutboundTransfer, ReceivePayload,
+    RecipientType, Refunded, CONVENTIONAL_SOLANA_ID, HELLO_VERSION, REUSABLE_MESSAGE_DELAY, U256,
+};
+use common::*;
+use solana_sdk::{
+    instruction::InstructionError,
+    signature::{Keypair, Signer},
+    system_instruction,
+};
+use wormhole_anchor_sdk::{
+    token_bridge::{self, TransferWithPayload},
+    wormhole,
+};
+use wormhole_test_guardian::{
+    core_bridge::{self, CONSISTENCY_LEVEL_CONFIRMED, CONSISTENCY_LEVEL_FINALIZED},
+    TokenBridgeError,
+};
+
+const ONE: u64 = 1_000_000_000;
+
+async fn funded_sender(proxy: &mut Proxy, amount: u64) -> Keypair {
+    let sender = funded_keypair(&mut proxy.context, ONE).await;
+    proxy.fund(&sender.pubkey(), amount).await;
+    sender
+}
+
+#[tokio::test]
+async fn initialize_sets_up_config_and_lock_account() {
+    let mut proxy = Proxy::start().await;
+
+    let config: Config = account(&mut proxy.context, config_address()).await;
+    assert_eq!(config.owner, proxy.owner());
+    assert_eq!(config.native_token, proxy.mint);
+    assert_eq!(config.wormhole.sequence, sequence_address());
+
+    let lock_address = proxy.lock();
+    let lock: TokenAccount = account(&mut proxy.context, lock_address).await;
+    assert_eq!(lock.owner, lock_address);
+    assert_eq!(lock.mint, proxy.mint);
+    assert_eq!(lock.amount, 0);
+
+    let hello = CATSOLStructs::Hello {
+        emitter: emitter_address().to_bytes(),
+        chain: U256::from(CONVENTIONAL_SOLANA_ID),
+        decimals: DECIMALS,
+        version: HELLO_VERSION,
+    };
+    assert_eq!(posted_message(&mut proxy.context, 1).await, hello);
+}
+
+#[tokio::test]
+async fn transfer_ownership_hands_over_owner_only_instructions() {
+    let mut proxy = Proxy::start().await;
+    let old_owner = proxy.owner();
+    let new_owner = funded_keypair(&mut proxy.context, ONE).await;
+
+    let ix = transfer_ownership_ix(&old_owner, &old_owner);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AlreadyOwner);
+
+    let ix = transfer_ownership_ix(&old_owner, &new_owner.pubkey());
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+
+    let ix = register_emitter_ix(&old_owner, 2, FOREIGN_EMITTER);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = register_emitter_ix(&new_owner.pubkey(), 2, FOREIGN_EMITTER);
+    process(&mut proxy.context, &[ix], &[&new_owner])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn register_emitter_validates_chain_and_address() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+
+    // Unknown to the chain registry, Solana's, a zero address, and chain 2
+    // with bits above the u16 Wormhole chain ID set.
+    for (chain, address) in [
+        (0, [1; 32]),
+        (3, [1; 32]),
+        (1, [1; 32]),
+        (2, [0; 32]),
+        (1 << 16 | 2, [1; 32]),
+    ] {
+        let ix = register_emitter_ix(&owner, chain, address);
+        let result = process(&mut proxy.context, &[ix], &[]).await;
+        assert_program_error(result, ErrorFactory::InvalidForeignEmitter);
+    }
+
+    proxy.register_emitter(2, FOREIGN_EMITTER).await;
+    let emitter: ForeignEmitter = account(&mut proxy.context, foreign_emitter_address(2)).await;
+    assert_eq!(emitter.chain, 2);
+    assert_eq!(emitter.address, FOREIGN_EMITTER);
+}
+
+#[tokio::test]
+async fn bridge_out_locks_tokens_and_posts_normalized_amount() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 5 * ONE).await;
+    let sender_ata = proxy.ata(&sender.pubkey());
+
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(1_234_567_891))
+        .await
+        .unwrap();
+
+    assert_eq!(proxy.balance(sender_ata).await, 5 * ONE - 1_234_567_891);
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, 1_234_567_891);
+
+    let payload = match posted_message(&mut proxy.context, 2).await {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => panic!("expected CrossChainPayload"),
+    };
+    assert_eq!(Into::<u64>::into(payload.amount), 123_456_789);
+    assert_eq!(payload.token_decimals, DECIMALS);
+    assert_eq!(payload.source_token_address, emitter_address().to_bytes());
+    assert_eq!(payload.source_user_address, sender.pubkey().to_bytes());
+    assert_eq!(
+        Into::<u64>::into(payload.dest_token_chain),
+        FOREIGN_CAT_CHAIN
+    );
+    assert_eq!(payload.dest_user_address, [0xee; 32]);
+}
+
+#[tokio::test]
+async fn bridge_out_costs_its_estimate_and_tops_up_the_fee_collector() {
+    let mut proxy = Proxy::start_with_fee(5_000).await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 5 * ONE).await;
+    let rent = proxy.context.banks_client.get_rent().await.unwrap();
+    let fee_collector = core_bridge::fee_collector_address();
+
+    let bridge = proxy.wormhole.bridge_data(&mut proxy.context).await;
+    let collector_lamports = proxy.lamports(fee_collector).await;
+    let estimate = BridgeOut::estimate_cost(&bridge, collector_lamports, &rent, None);
+    assert_eq!(estimate.fee, 5_000);
+    assert_eq!(
+        estimate.message_rent,
+        rent.minimum_balance(wormhole::message_account_size(CrossChainStruct::LEN))
+    );
+    assert_eq!(
+        estimate.account_rent,
+        rent.minimum_balance(OutboundTransfer::MAXIMUM_SIZE)
+    );
+
+    let before = proxy.lamports(sender.pubkey()).await;
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(ONE))
+        .await
+        .unwrap();
+    assert_eq!(
+        before - proxy.lamports(sender.pubkey()).await,
+        estimate.total()
+    );
+    assert_eq!(
+        proxy.lamports(fee_collector).await,
+        collector_lamports + 5_000
+    );
+
+    // Lamports already sent to the fee collector count towards the next fee.
+    let payer = proxy.owner();
+    process(
+        &mut proxy.context,
+        &[system_instruction::transfer(&payer, &fee_collector, 5_000)],
+        &[],
+    )
+    .await
+    .unwrap();
+    let bridge = proxy.wormhole.bridge_data(&mut proxy.context).await;
+    let collector_lamports = proxy.lamports(fee_collector).await;
+    let estimate = BridgeOut::estimate_cost(&bridge, collector_lamports, &rent, None);
+    assert_eq!(estimate.fee, 0);
+
+    let before = proxy.lamports(sender.pubkey()).await;
+    proxy
+        .bridge_out(&sender, 2, bridge_out_params(ONE))
+        .await
+        .unwrap();
+    assert_eq!(
+        before - proxy.lamports(sender.pubkey()).await,
+        estimate.total()
+    );
+    assert_eq!(proxy.lamports(fee_collector).await, collector_lamports);
+}
+
+#[tokio::test]
+async fn bridge_out_requires_delegation_to_lock_account() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, ONE).await;
+    let sender_ata = proxy.ata(&sender.pubkey());
+
+    let ix = bridge_out_ix(
+        &sender.pubkey(),
+        &proxy.mint,
+        &sender_ata,
+        1,
+        bridge_out_params(ONE),
+    );
+    let result = process(&mut proxy.context, &[ix], &[&sender]).await;
+    assert!(result.is_err());
+    assert_eq!(proxy.balance(sender_ata).await, ONE);
+}
+
+#[tokio::test]
+async fn bridge_in_unlocks_denormalized_amount() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 5 * ONE).await;
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(5 * ONE))
+        .await
+        .unwrap();
+
+    let recipient = Pubkey::new_unique();
+    let body = transfer_body(0, 123_456_789, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy.bridge_in(&recipient, &body).await.unwrap();
+
+    let recipient_ata = proxy.ata(&recipient);
+    assert_eq!(proxy.balance(recipient_ata).await, 1_234_567_890);
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, 5 * ONE - 1_234_567_890);
+
+    // The received bitmap alone keeps the message from being replayed.
+    assert!(is_received(&mut proxy.context, body.emitter_chain, body.sequence).await);
+    assert!(proxy
+        .context
+        .banks_client
+        .get_account(received_address(body.emitter_chain, body.sequence))
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_replay() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 5 * ONE).await;
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(5 * ONE))
+        .await
+        .unwrap();
+
+    let recipient = Pubkey::new_unique();
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy.bridge_in(&recipient, &body).await.unwrap();
+    let result = proxy.bridge_in(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+
+    // A different VAA reusing the sequence is rejected as well.
+    let body = transfer_body(0, 200, &recipient, CONVENTIONAL_SOLANA_ID).with_nonce(1);
+    let result = proxy.bridge_in(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+
+    let recipient_ata = proxy.ata(&recipient);
+    assert_eq!(proxy.balance(recipient_ata).await, 1_000);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_wrong_destination_chain() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+
+    let recipient = Pubkey::new_unique();
+    // Wormhole's chain ID for Solana, not its CAT chain ID.
+    let body = transfer_body(0, 100, &recipient, wormhole::CHAIN_ID_SOLANA.into());
+    let result = proxy.bridge_in(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidDestinationChain);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_mismatched_ata() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+
+    let recipient = Pubkey::new_unique();
+    let thief = Pubkey::new_unique();
+    let thief_ata = proxy.ata(&thief);
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let payer = proxy.owner();
+    let ixs = [
+        create_ata_ix(&payer, &thief, &proxy.mint),
+        bridge_in_ix(&payer, &proxy.mint, &thief_ata, &body),
+    ];
+    let result = process(&mut proxy.context, &ixs, &[]).await;
+    assert_program_error(result, ErrorFactory::MisMatchdATAAddress);
+}
+
+#[tokio::test]
+async fn bridge_in_rejects_unregistered_emitter() {
+    let mut proxy = Proxy::start().await;
+    proxy.register_emitter(FOREIGN_CHAIN.into(), [1; 32]).await;
+
+    let recipient = Pubkey::new_unique();
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    let result = proxy.bridge_in(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidForeignEmitter);
+}
+
+#[tokio::test]
+async fn custody_equals_locked_minus_unlocked() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+
+    let mut locked = 0;
+    let mut unlocked = 0;
+    let mut outbound_sequence = 1;
+    let mut inbound_sequence = 0;
+
+    let senders = [
+        funded_sender(&mut proxy, 10 * ONE).await,
+        funded_sender(&mut proxy, 10 * ONE).await,
+    ];
+    let recipient = Pubkey::new_unique();
+
+    for (i, amount) in [3 * ONE + 7, 2 * ONE, 123_456_789, 4 * ONE + 55]
+        .into_iter()
+        .enumerate()
+    {
+        proxy
+            .bridge_out(
+                &senders[i % 2],
+                outbound_sequence,
+                bridge_out_params(amount),
+            )
+            .await
+            .unwrap();
+        outbound_sequence += 1;
+        locked += amount;
+
+        // Bring back the normalized amount of every other transfer.
+        if i % 2 == 1 {
+            let normalized = normalize_amount(amount, DECIMALS);
+            let body = transfer_body(
+                inbound_sequence,
+                normalized,
+                &recipient,
+                CONVENTIONAL_SOLANA_ID,
+            );
+            proxy.bridge_in(&recipient, &body).await.unwrap();
+            inbound_sequence += 1;
+            unlocked += denormalize_amount(normalized, DECIMALS);
+        }
+
+        let lock = proxy.lock();
+        assert_eq!(proxy.balance(lock).await, locked - unlocked);
+    }
+
+    // Redeeming more than is in custody fails and leaves custody untouched.
+    let body = transfer_body(
+        inbound_sequence,
+        normalize_amount(locked - unlocked, DECIMALS) + 1,
+        &recipient,
+        CONVENTIONAL_SOLANA_ID,
+    );
+    assert!(proxy.bridge_in(&recipient, &body).await.is_err());
+
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, locked - unlocked);
+
+    // Tokens only move between wallets and custody.
+    let mut total = proxy.balance(lock).await;
+    for wallet in [senders[0].pubkey(), senders[1].pubkey(), recipient] {
+        let ata = proxy.ata(&wallet);
+        total += proxy.balance(ata).await;
+    }
+    assert_eq!(total, 20 * ONE);
+}
+
+/// Bridge `amount` out of a new sender to `FOREIGN_CHAIN` as outbound
+/// sequence 1. Returns the sender's token account and the posted transfer.
+async fn bridge_out_to_foreign_chain(proxy: &mut Proxy, amount: u64) -> (Pubkey, CrossChainStruct) {
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(proxy, amount).await;
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(amount))
+        .await
+        .unwrap();
+
+    let payload = match posted_message(&mut proxy.context, 2).await {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => panic!("expected CrossChainPayload"),
+    };
+    (proxy.ata(&sender.pubkey()), payload)
+}
+
+#[tokio::test]
+async fn refund_unlocks_to_original_sender_once() {
+    let mut proxy = Proxy::start().await;
+    let (sender_ata, payload) = bridge_out_to_foreign_chain(&mut proxy, 1_234_567_891).await;
+    let lock = proxy.lock();
+
+    let body = refund_body(0, 1, payload.clone());
+    proxy.refund(&sender_ata, 1, &body).await.unwrap();
+
+    // Dust locked on the way out stays locked.
+    assert_eq!(proxy.balance(sender_ata).await, 1_234_567_890);
+    assert_eq!(proxy.balance(lock).await, 1);
+    let refunded: Refunded = account(&mut proxy.context, refunded_address(1)).await;
+    assert_eq!(refunded.wormhole_message_hash, body.body_hash());
+
+    // A second refund message for the same transfer is rejected.
+    let body = refund_body(1, 1, payload);
+    assert!(proxy.refund(&sender_ata, 1, &body).await.is_err());
+    assert_eq!(proxy.balance(lock).await, 1);
+}
+
+#[tokio::test]
+async fn refund_rejects_transfers_it_did_not_send() {
+    let mut proxy = Proxy::start().await;
+    let (sender_ata, payload) = bridge_out_to_foreign_chain(&mut proxy, ONE).await;
+
+    // Sent to a chain other than the one refunding it.
+    let mut elsewhere = payload.clone();
+    elsewhere.dest_token_chain = U256::from(1397u64);
+    let result = proxy
+        .refund(&sender_ata, 1, &refund_body(0, 1, elsewhere))
+        .await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+
+    // Sent by another program.
+    let mut foreign = payload.clone();
+    foreign.source_token_address = [0x42; 32];
+    let result = proxy
+        .refund(&sender_ata, 1, &refund_body(1, 1, foreign))
+        .await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+
+    // Sequence in the instruction differs from the message.
+    let body = refund_body(2, 1, payload);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let ix = refund_ix(&proxy.owner(), &proxy.mint, &sender_ata, 7, &body);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, ONE);
+}
+
+#[tokio::test]
+async fn refund_must_match_the_outbound_transfer() {
+    let mut proxy = Proxy::start().await;
+    let (sender_ata, payload) = bridge_out_to_foreign_chain(&mut proxy, 1_234_567_891).await;
+    let other = funded_sender(&mut proxy, 0).await;
+    let other_ata = proxy.ata(&other.pubkey());
+
+    // More than was sent.
+    let mut more = payload.clone();
+    more.amount = U256::from(123_456_790u64);
+    let result = proxy.refund(&sender_ata, 1, &refund_body(0, 1, more)).await;
+    assert_program_error(result, ErrorFactory::OutboundTransferMismatch);
+
+    // From another wallet.
+    let mut other_sender = payload.clone();
+    other_sender.source_user_address = other.pubkey().to_bytes();
+    let result = proxy
+        .refund(&other_ata, 1, &refund_body(1, 1, other_sender))
+        .await;
+    assert_program_error(result, ErrorFactory::OutboundTransferMismatch);
+
+    // Refunded by another chain the transfer claims it went to.
+    proxy.register_emitter(4, FOREIGN_EMITTER).await;
+    let mut elsewhere = payload.clone();
+    elsewhere.dest_token_chain = U256::from(1397u64);
+    let body = wormhole::VaaBody::new(
+        4,
+        FOREIGN_EMITTER,
+        0,
+        &CATSOLStructs::Refund {
+            sequence: 1,
+            payload: elsewhere,
+        },
+    );
+    let result = proxy.refund(&sender_ata, 1, &body).await;
+    assert_program_error(result, ErrorFactory::OutboundTransferMismatch);
+
+    // Leaving the record out does not skip the checks.
+    let body = refund_body(2, 1, payload);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let ix = refund_ix(&proxy.owner(), &proxy.mint, &sender_ata, 1, &body);
+    let result = process(&mut proxy.context, &[without_outbound_transfer(ix, 1)], &[]).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::ConstraintSeeds.into()),
+    );
+    let outbound: OutboundTransfer =
+        account(&mut proxy.context, outbound_transfer_address(1)).await;
+    assert_eq!(outbound.status, OutboundStatus::Pending);
+
+    assert_eq!(proxy.balance(sender_ata).await, 0);
+
+    // Transfers sent before outbound transfer accounts have none.
+    proxy.context.set_account(
+        &outbound_transfer_address(1),
+        &solana_sdk::account::AccountSharedData::default(),
+    );
+    let ix = refund_ix(&proxy.owner(), &proxy.mint, &sender_ata, 1, &body);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    assert_eq!(proxy.balance(sender_ata).await, 1_234_567_890);
+}
+
+#[tokio::test]
+async fn refund_only_pays_original_sender() {
+    let mut proxy = Proxy::start().await;
+    let (_, payload) = bridge_out_to_foreign_chain(&mut proxy, ONE).await;
+    let thief = funded_sender(&mut proxy, 0).await;
+    let thief_ata = proxy.ata(&thief.pubkey());
+
+    let result = proxy
+        .refund(&thief_ata, 1, &refund_body(0, 1, payload))
+        .await;
+    assert_program_error(result, ErrorFactory::MisMatchdATAAddress);
+}
+
+#[tokio::test]
+async fn refunds_and_transfers_are_not_interchangeable() {
+    let mut proxy = Proxy::start().await;
+    let (sender_ata, payload) = bridge_out_to_foreign_chain(&mut proxy, ONE).await;
+
+    let refund = refund_body(0, 1, payload);
+    let owner = proxy.owner();
+    let result = proxy.bridge_in(&owner, &refund).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+
+    let transfer = transfer_body(1, 100, &owner, CONVENTIONAL_SOLANA_ID);
+    let result = proxy.refund(&sender_ata, 1, &transfer).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+}
+
+/// Lock `amount` by bridging it out of a new sender.
+async fn lock_tokens(proxy: &mut Proxy, amount: u64) {
+    let sender = funded_sender(proxy, amount).await;
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(amount))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn bridge_out_with_payload_posts_app_data() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, ONE).await;
+
+    proxy
+        .bridge_out_with_payload(&sender, 1, bridge_out_params(ONE), b"swap".to_vec())
+        .await
+        .unwrap();
+
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, ONE);
+    match posted_message(&mut proxy.context, 2).await {
+        CATSOLStructs::TransferWithPayload { payload, data } => {
+            assert_eq!(Into::<u64>::into(payload.amount), 100_000_000);
+            assert_eq!(data, b"swap");
+        }
+        _ => panic!("expected TransferWithPayload"),
+    }
+}
+
+#[tokio::test]
+async fn bridge_in_with_payload_delivers_to_recipient_program() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let lock = proxy.lock();
+
+    let body = transfer_with_payload_body(0, 12_345, &RECEIVER_ID, b"deposit");
+    proxy
+        .bridge_in_with_payload(&RECEIVER_ID, &body)
+        .await
+        .unwrap();
+
+    let redeemer_ata = proxy.ata(&redeemer_address());
+    assert_eq!(proxy.balance(redeemer_ata).await, 123_450);
+    assert_eq!(proxy.balance(lock).await, ONE - 123_450);
+    assert_eq!(
+        receipt(&mut proxy.context).await,
+        Some(ReceivePayload {
+            amount: 123_450,
+            source_chain: FOREIGN_CHAIN,
+            sender: [0xee; 32],
+            data: b"deposit".to_vec(),
+        })
+    );
+
+    // The bit for (chain, sequence) is already set.
+    let result = proxy.bridge_in_with_payload(&RECEIVER_ID, &body).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+}
+
+#[tokio::test]
+async fn bridge_in_with_payload_reverts_when_recipient_fails() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let lock = proxy.lock();
+
+    let body = transfer_with_payload_body(0, 12_345, &RECEIVER_ID, b"reject");
+    let result = proxy.bridge_in_with_payload(&RECEIVER_ID, &body).await;
+    assert_instruction_error(result, InstructionError::Custom(RECEIVER_REJECTED));
+
+    assert_eq!(proxy.balance(lock).await, ONE);
+    assert_eq!(receipt(&mut proxy.context).await, None);
+}
+
+#[tokio::test]
+async fn bridge_in_with_payload_requires_named_recipient_program() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+
+    // Named recipient is another program.
+    let body = transfer_with_payload_body(0, 12_345, &anchor_spl::token::ID, b"deposit");
+    let result = proxy.bridge_in_with_payload(&RECEIVER_ID, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidRecipientProgram);
+
+    // Plain transfers go through `bridge_in` and vice versa.
+    let body = transfer_body(1, 12_345, &RECEIVER_ID, CONVENTIONAL_SOLANA_ID);
+    let result = proxy.bridge_in_with_payload(&RECEIVER_ID, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+
+    let owner = proxy.owner();
+    let body = transfer_with_payload_body(2, 12_345, &owner, b"deposit");
+    let result = proxy.bridge_in(&owner, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidMessage);
+}
+
+#[tokio::test]
+async fn bridge_out_portal_locks_in_token_bridge_custody() {
+    let mut proxy = Proxy::start().await;
+    proxy.init_token_bridge().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 5 * ONE).await;
+    let sender_ata = proxy.ata(&sender.pubkey());
+
+    proxy
+        .bridge_out_portal(&sender, 0, bridge_out_params(1_234_567_891))
+        .await
+        .unwrap();
+
+    // Only whole units of 8 decimals are taken, into the Token Bridge custody.
+    assert_eq!(proxy.balance(sender_ata).await, 5 * ONE - 1_234_567_890);
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, 0);
+    let custody = token_bridge::custody_address(&proxy.mint);
+    assert_eq!(proxy.balance(custody).await, 1_234_567_890);
+
+    let message: wormhole::PostedVaaData =
+        account(&mut proxy.context, portal_message_address(0)).await;
+    assert_eq!(
+        *message.emitter_address(),
+        token_bridge::emitter_address().to_bytes()
+    );
+    let transfer = TransferWithPayload::deserialize(&mut &message.payload[..]).unwrap();
+    assert_eq!(transfer.amount(), 123_456_789);
+    assert_eq!(transfer.mint(), proxy.mint);
+    assert_eq!(*transfer.to_address(), FOREIGN_EMITTER);
+    assert_eq!(transfer.to_chain(), FOREIGN_CHAIN);
+    assert_eq!(*transfer.from_address(), cat_sol20_proxy::ID.to_bytes());
+    let payload = match CATSOLStructs::try_from_slice(transfer.data()).unwrap() {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => panic!("expected CrossChainPayload"),
+    };
+    assert_eq!(Into::<u64>::into(payload.amount), 123_456_789);
+    assert_eq!(payload.source_token_address, cat_sol20_proxy::ID.to_bytes());
+    assert_eq!(payload.source_user_address, sender.pubkey().to_bytes());
+    assert_eq!(payload.dest_token_address, FOREIGN_EMITTER);
+    assert_eq!(payload.dest_user_address, [0xee; 32]);
+
+    // The transfer is recorded under the Token Bridge sequence, with the
+    // amount actually taken.
+    let outbound: OutboundTransfer =
+        account(&mut proxy.context, portal_outbound_transfer_address(0)).await;
+    assert_eq!(outbound.sender, sender.pubkey());
+    assert_eq!(outbound.status, OutboundStatus::Pending);
+    assert_eq!(outbound.sequence, 0);
+    assert_eq!(outbound.amount, 1_234_567_890);
+    assert_eq!(outbound.recipient_chain, FOREIGN_CAT_CHAIN);
+    assert_eq!(outbound.recipient, [0xee; 32]);
+
+    // Each transfer gets the message and outbound transfer accounts of its
+    // sequence, apart from those of `bridge_out`.
+    proxy
+        .bridge_out_portal(&sender, 1, bridge_out_params(ONE))
+        .await
+        .unwrap();
+    assert_eq!(proxy.balance(custody).await, 1_234_567_890 + ONE);
+    let outbound: OutboundTransfer =
+        account(&mut proxy.context, portal_outbound_transfer_address(1)).await;
+    assert_eq!((outbound.sequence, outbound.amount), (1, ONE));
+    assert!(proxy
+        .context
+        .banks_client
+        .get_account(outbound_transfer_address(1))
+        .await
+        .unwrap()
+        .is_none());
+
+    let result = proxy
+        .bridge_out_portal(&sender, 2, bridge_out_params(9))
+        .await;
+    assert_program_error(result, ErrorFactory::AmountTooSmall);
+
+    // The tokens are in the Token Bridge custody, so the transfer comes back
+    // through the Token Bridge, not `refund`.
+    let result = proxy
+        .refund(&sender_ata, 0, &refund_body(0, 0, payload))
+        .await;
+    assert_program_error(result, ErrorFactory::InvalidRefund);
+}
+
+#[tokio::test]
+async fn bridge_in_portal_forwards_unlocked_tokens_once() {
+    let mut proxy = Proxy::start().await;
+    proxy.init_token_bridge().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, ONE).await;
+    proxy
+        .bridge_out_portal(&sender, 0, bridge_out_params(ONE))
+        .await
+        .unwrap();
+
+    let recipient = Keypair::new().pubkey();
+    let mint = proxy.mint;
+    let body = portal_transfer_body(0, &mint, 12_345, &recipient, FOREIGN_EMITTER);
+    proxy.bridge_in_portal(&recipient, &body).await.unwrap();
+
+    let recipient_ata = proxy.ata(&recipient);
+    assert_eq!(proxy.balance(recipient_ata).await, 123_450);
+    let custody = token_bridge::custody_address(&mint);
+    assert_eq!(proxy.balance(custody).await, ONE - 123_450);
+    let redeemer_ata = proxy.ata(&token_bridge::redeemer_address(&cat_sol20_proxy::ID));
+    assert_eq!(proxy.balance(redeemer_ata).await, 0);
+
+    // The Token Bridge claims the transfer.
+    let result = proxy.bridge_in_portal(&recipient, &body).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(TokenBridgeError::AlreadyClaimed as u32),
+    );
+}
+
+#[tokio::test]
+async fn bridge_in_portal_requires_the_registered_deployment_and_token_bridge() {
+    let mut proxy = Proxy::start().await;
+    proxy.init_token_bridge().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, ONE).await;
+    proxy
+        .bridge_out_portal(&sender, 0, bridge_out_params(ONE))
+        .await
+        .unwrap();
+    let recipient = Keypair::new().pubkey();
+    let mint = proxy.mint;
+
+    // Sent through the Token Bridge by someone other than the CAT deployment.
+    let body = portal_transfer_body(0, &mint, 12_345, &recipient, [0x99; 32]);
+    let result = proxy.bridge_in_portal(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidForeignEmitter);
+
+    // Emitted by an unregistered Token Bridge.
+    let mut body = portal_transfer_body(1, &mint, 12_345, &recipient, FOREIGN_EMITTER);
+    body.emitter_address = [0x55; 32];
+    let result = proxy.bridge_in_portal(&recipient, &body).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::AccountNotInitialized.into()),
+    );
+}
+
+#[tokio::test]
+async fn bridge_in_to_token_account_unlocks_to_any_token_account() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+
+    // A vault owned by some program's PDA, not an ATA.
+    let vault_owner = Pubkey::find_program_address(&[b"vault"], &RECEIVER_ID).0;
+    let mint = proxy.mint;
+    let vault = create_token_account(&mut proxy.context, &mint, &vault_owner).await;
+
+    let body = transfer_to_body(0, 100, &vault, RecipientType::TokenAccount);
+    proxy.bridge_in_to(&vault, &body).await.unwrap();
+    assert_eq!(proxy.balance(vault).await, 1_000);
+}
+
+#[tokio::test]
+async fn bridge_in_honors_recipient_type() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+
+    // Flagged wallets redeem like legacy transfers.
+    let recipient = Pubkey::new_unique();
+    let body = transfer_to_body(0, 100, &recipient, RecipientType::Wallet);
+    proxy.bridge_in(&recipient, &body).await.unwrap();
+    let recipient_ata = proxy.ata(&recipient);
+    assert_eq!(proxy.balance(recipient_ata).await, 1_000);
+
+    // Token accounts only redeem through `bridge_in_to_token_account`...
+    let mint = proxy.mint;
+    let vault = create_token_account(&mut proxy.context, &mint, &recipient).await;
+    let body = transfer_to_body(1, 100, &vault, RecipientType::TokenAccount);
+    let result = proxy.bridge_in(&vault, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidRecipientType);
+
+    // ...and wallets never do.
+    let body = transfer_body(2, 100, &vault, CONVENTIONAL_SOLANA_ID);
+    let result = proxy.bridge_in_to(&vault, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidRecipientType);
+    assert_eq!(proxy.balance(vault).await, 0);
+}
+
+#[tokio::test]
+async fn bridge_in_to_token_account_validates_the_account() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let vault_owner = Keypair::new();
+    let owner = vault_owner.pubkey();
+    let mint = proxy.mint;
+
+    // Another mint.
+    let other_mint = create_mint(&mut proxy.context, DECIMALS).await;
+    let wrong_mint = create_token_account(&mut proxy.context, &other_mint, &owner).await;
+    let body = transfer_to_body(0, 100, &wrong_mint, RecipientType::TokenAccount);
+    let result = proxy.bridge_in_to(&wrong_mint, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidTokenAccountMint);
+
+    // Frozen.
+    let vault = create_token_account(&mut proxy.context, &mint, &owner).await;
+    freeze(&mut proxy.context, &vault).await;
+    let body = transfer_to_body(1, 100, &vault, RecipientType::TokenAccount);
+    let result = proxy.bridge_in_to(&vault, &body).await;
+    assert_program_error(result, ErrorFactory::InvalidATAAccount);
+
+    // Closed.
+    let closed = create_token_account(&mut proxy.context, &mint, &owner).await;
+    close_token_account(&mut proxy.context, &closed, &vault_owner).await;
+    let body = transfer_to_body(2, 100, &closed, RecipientType::TokenAccount);
+    let result = proxy.bridge_in_to(&closed, &body).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::AccountNotInitialized.into()),
+    );
+
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, ONE);
+}
+
+#[tokio::test]
+async fn close_received_returns_rent_to_the_payer() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let owner = proxy.owner();
+    let mint = proxy.mint;
+    let recipient = Pubkey::new_unique();
+    let recipient_ata = proxy.ata(&recipient);
+    let relayer = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+
+    // Redeemed just now: still within the retention period.
+    let now = unix_timestamp(&mut proxy.context).await;
+    let recent =
+        transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID).with_timestamp(now as u32);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &recent)
+        .await
+        .unwrap();
+    let ixs = [
+        create_ata_ix(&relayer.pubkey(), &recipient, &mint),
+        bridge_in_ix(&relayer.pubkey(), &mint, &recipient_ata, &recent),
+    ];
+    process(&mut proxy.context, &ixs, &[&relayer])
+        .await
+        .unwrap();
+    set_received(&mut proxy.context, &recent, &relayer.pubkey()).await;
+
+    let ix = close_received_ix(&relayer.pubkey(), &relayer.pubkey(), &recent);
+    let result = process(&mut proxy.context, &[ix], &[&relayer]).await;
+    assert_program_error(result, ErrorFactory::RetentionPeriodNotOver);
+
+    // Redeemed long ago.
+    let body = transfer_body(1, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let ix = bridge_in_ix(&relayer.pubkey(), &mint, &recipient_ata, &body);
+    process(&mut proxy.context, &[ix], &[&relayer])
+        .await
+        .unwrap();
+    set_received(&mut proxy.context, &body, &relayer.pubkey()).await;
+    let address = received_address(body.emitter_chain, body.sequence);
+    let rent = proxy.lamports(address).await;
+
+    let intruder = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+    let ix = close_received_ix(&intruder.pubkey(), &relayer.pubkey(), &body);
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::PayerOrOwnerOnly);
+
+    // The rent only goes back to the payer.
+    let ix = close_received_ix(&owner, &owner, &body);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRentRecipient);
+
+    // The payer closes its own account.
+    let before = proxy.lamports(relayer.pubkey()).await;
+    let ix = close_received_ix(&relayer.pubkey(), &relayer.pubkey(), &body);
+    process(&mut proxy.context, &[ix], &[&relayer])
+        .await
+        .unwrap();
+
+    assert!(proxy
+        .context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(proxy.lamports(relayer.pubkey()).await, before + rent);
+
+    // The message stays redeemed.
+    let result = proxy.bridge_in(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+    assert_eq!(proxy.balance(recipient_ata).await, 2_000);
+
+    // The owner closes it for the payer too.
+    let body = transfer_body(2, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    set_received(&mut proxy.context, &body, &relayer.pubkey()).await;
+    let ix = close_received_ix(&owner, &relayer.pubkey(), &body);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+}
+
+#[tokio::test]
+async fn close_legacy_received_reclaims_rent_after_retention_period() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let owner = proxy.owner();
+    let recipient = Pubkey::new_unique();
+    let rent_recipient = Pubkey::new_unique();
+
+    // Redeemed just now: still within the retention period.
+    let now = unix_timestamp(&mut proxy.context).await;
+    let recent =
+        transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID).with_timestamp(now as u32);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &recent)
+        .await
+        .unwrap();
+    set_legacy_received(&mut proxy.context, &recent).await;
+
+    let ix = close_legacy_received_ix(&owner, &rent_recipient, &recent);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::RetentionPeriodNotOver);
+
+    // A legacy account blocks redemption, though its bit was never set.
+    let result = proxy.bridge_in(&recipient, &recent).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+
+    // Redeemed long ago.
+    let body = transfer_body(1, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    set_legacy_received(&mut proxy.context, &body).await;
+    let legacy = received_address(body.emitter_chain, body.sequence);
+    let rent = proxy.lamports(legacy).await;
+
+    // Legacy accounts do not load as `Received`.
+    let ix = close_received_ix(&owner, &rent_recipient, &body);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_instruction_error(
+        result,
+        InstructionError::Custom(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into()),
+    );
+
+    let intruder = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+    let ix = close_legacy_received_ix(&intruder.pubkey(), &rent_recipient, &body);
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = close_legacy_received_ix(&owner, &rent_recipient, &body);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+
+    assert!(proxy
+        .context
+        .banks_client
+        .get_account(legacy)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(proxy.lamports(rent_recipient).await, rent);
+
+    // The message stays redeemed.
+    assert!(is_received(&mut proxy.context, body.emitter_chain, body.sequence).await);
+    let result = proxy.bridge_in(&recipient, &body).await;
+    assert_program_error(result, ErrorFactory::AlreadyReceived);
+    let lock = proxy.lock();
+    assert_eq!(proxy.balance(lock).await, ONE);
+
+    // Accounts that record their payer close through `close_received`.
+    let body = transfer_body(2, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    set_received(&mut proxy.context, &body, &owner).await;
+    let ix = close_legacy_received_ix(&owner, &rent_recipient, &body);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::NotLegacyReceived);
+}
+
+#[tokio::test]
+async fn migrate_grows_config_and_emitters_from_earlier_layouts() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+    let ix = register_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let before: Config = account(&mut proxy.context, config_address()).await;
+
+    // Accounts created before the fields appended since the first release.
+    truncate_account(
+        &mut proxy.context,
+        config_address(),
+        Config::MAXIMUM_SIZE - 8,
+    )
+    .await;
+    let emitter = foreign_emitter_address(FOREIGN_CHAIN.into());
+    truncate_account(&mut proxy.context, emitter, 8 + 8 + 32).await;
+
+    let not_deserialized =
+        || InstructionError::Custom(anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into());
+    let ix = set_message_params_ix(&owner, 7, 1, 0);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_instruction_error(result, not_deserialized());
+
+    let intruder = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+    let ix = migrate_config_ix(&intruder.pubkey());
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = migrate_config_ix(&owner);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let config: Config = account(&mut proxy.context, config_address()).await;
+    assert_eq!(config.owner, owner);
+    assert!(config.wormhole == before.wormhole);
+    assert_eq!(config.finality, before.finality);
+    assert_eq!(config.native_token, before.native_token);
+    assert_eq!(config.finalized_threshold, 0);
+
+    // Emitters still need their own migration.
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(1));
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_instruction_error(result, not_deserialized());
+
+    let ix = migrate_emitter_ix(&intruder.pubkey(), FOREIGN_CHAIN.into());
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let ix = migrate_emitter_ix(&owner, FOREIGN_CHAIN.into());
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let foreign_emitter: ForeignEmitter = account(&mut proxy.context, emitter).await;
+    assert_eq!(foreign_emitter.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(foreign_emitter.address, FOREIGN_EMITTER);
+    assert_eq!(foreign_emitter.finality, None);
+    assert_eq!(foreign_emitter.max_vaa_age, 0);
+
+    // Migrated accounts work again, and migrating them again changes nothing.
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(1));
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let ixs = [
+        migrate_config_ix(&owner),
+        migrate_emitter_ix(&owner, FOREIGN_CHAIN.into()),
+    ];
+    process(&mut proxy.context, &ixs, &[]).await.unwrap();
+    let foreign_emitter: ForeignEmitter = account(&mut proxy.context, emitter).await;
+    assert_eq!(foreign_emitter.finality, Some(1));
+}
+
+#[tokio::test]
+async fn bridge_out_finality_follows_destination_and_amount() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 10 * ONE).await;
+
+    let intruder = funded_keypair(&mut proxy.context, ONE).await;
+    let ix = set_message_params_ix(&intruder.pubkey(), 7, 1, ONE);
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(32));
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidFinality);
+
+    let ix = set_message_params_ix(&owner, 7, 0, ONE);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let config: Config = account(&mut proxy.context, config_address()).await;
+    assert_eq!(config.finalized_threshold, ONE);
+
+    // Below the threshold: the config's finality, then the emitter's.
+    proxy
+        .bridge_out(&sender, 1, bridge_out_params(ONE - 1))
+        .await
+        .unwrap();
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), Some(1));
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    proxy
+        .bridge_out(&sender, 2, bridge_out_params(ONE - 1))
+        .await
+        .unwrap();
+
+    // At the threshold: Finalized even without the emitter's override.
+    let ix = set_emitter_finality_ix(&owner, FOREIGN_CHAIN.into(), None);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    proxy
+        .bridge_out(&sender, 3, bridge_out_params(ONE))
+        .await
+        .unwrap();
+
+    let expected = [
+        CONSISTENCY_LEVEL_CONFIRMED,
+        CONSISTENCY_LEVEL_FINALIZED,
+        CONSISTENCY_LEVEL_FINALIZED,
+    ];
+    for (sequence, consistency_level) in (1..).zip(expected) {
+        let message = posted(&mut proxy.context, sequence + 1).await;
+        assert_eq!(message.finality(), consistency_level);
+        assert_eq!(message.batch_id(), 7);
+    }
+}
+
+#[tokio::test]
+async fn bridge_in_enforces_emitter_age_and_consistency_policy() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let recipient = Pubkey::new_unique();
+
+    // Finalized only.
+    let ix = set_emitter_policy_ix(&owner, FOREIGN_CHAIN.into(), 3_600, &[EVM_FINALIZED]);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+
+    let now = unix_timestamp(&mut proxy.context).await;
+    // An instant message carries a higher level than a finalized one.
+    let instant = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+        .with_timestamp(now as u32)
+        .with_consistency_level(EVM_INSTANT);
+    let result = proxy.bridge_in(&recipient, &instant).await;
+    assert_program_error(result, ErrorFactory::InsufficientConsistencyLevel);
+
+    let stale = transfer_body(1, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+        .with_timestamp((now - 3_601) as u32)
+        .with_consistency_level(EVM_FINALIZED);
+    let result = proxy.bridge_in(&recipient, &stale).await;
+    assert_program_error(result, ErrorFactory::StaleVaa);
+
+    // Instant only. A finalized message carries a lower level than an
+    // instant one, and is rejected too.
+    let ix = set_emitter_policy_ix(&owner, FOREIGN_CHAIN.into(), 0, &[EVM_INSTANT]);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let result = proxy.bridge_in(&recipient, &stale).await;
+    assert_program_error(result, ErrorFactory::InsufficientConsistencyLevel);
+    proxy.bridge_in(&recipient, &instant).await.unwrap();
+
+    // Instant or stronger.
+    let ix = set_emitter_policy_ix(
+        &owner,
+        FOREIGN_CHAIN.into(),
+        0,
+        &[EVM_INSTANT, EVM_SAFE, EVM_FINALIZED],
+    );
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    proxy.bridge_in(&recipient, &stale).await.unwrap();
+
+    // Lifting the policy accepts any level.
+    let ix = set_emitter_policy_ix(&owner, FOREIGN_CHAIN.into(), 0, &[]);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let confirmed = transfer_body(2, 100, &recipient, CONVENTIONAL_SOLANA_ID)
+        .with_consistency_level(CONSISTENCY_LEVEL_CONFIRMED);
+    proxy.bridge_in(&recipient, &confirmed).await.unwrap();
+
+    let recipient_ata = proxy.ata(&recipient);
+    assert_eq!(proxy.balance(recipient_ata).await, 3_000);
+}
+
+#[tokio::test]
+async fn bridge_in_binds_vaa_hash_and_emits_redeemed() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    lock_tokens(&mut proxy, ONE).await;
+    let recipient = Pubkey::new_unique();
+    let recipient_ata = proxy.ata(&recipient);
+
+    let body = transfer_body(0, 100, &recipient, CONVENTIONAL_SOLANA_ID);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let create_ata = create_ata_ix(&owner, &recipient, &proxy.mint);
+
+    // A posted account found through a hash its contents don't have.
+    let forged_hash = [0x42; 32];
+    let forged = repost_under_hash(&mut proxy.context, &body, forged_hash).await;
+    let mut ix = bridge_in_ix(&owner, &proxy.mint, &recipient_ata, &body);
+    ix.data[8..40].copy_from_slice(&forged_hash);
+    for meta in &mut ix.accounts {
+        if meta.pubkey == body.posted_vaa_address() {
+            meta.pubkey = forged;
+        }
+    }
+    let result = process(&mut proxy.context, &[create_ata.clone(), ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidVaaHash);
+
+    let ix = bridge_in_ix(&owner, &proxy.mint, &recipient_ata, &body);
+    let events = process_redeemed(&mut proxy.context, &[create_ata, ix], &[])
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].emitter_chain, FOREIGN_CHAIN);
+    assert_eq!(events[0].sequence, 0);
+    assert_eq!(events[0].vaa_hash, body.body_hash());
+    assert_eq!(events[0].amount, 1_000);
+    assert_eq!(events[0].recipient, recipient_ata);
+}
+
+#[tokio::test]
+async fn attest_token_posts_any_mint() {
+    let mut proxy = Proxy::start().await;
+    let payer = proxy.owner();
+    // Not the native token, and without metadata.
+    let mint = create_mint(&mut proxy.context, 6).await;
+
+    process(
+        &mut proxy.context,
+        &[attest_token_ix(&payer, &mint, 1)],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let expected = CATSOLStructs::Attest {
+        token_address: mint.to_bytes(),
+        token_chain: U256::from(CONVENTIONAL_SOLANA_ID),
+        decimals: 6,
+        symbol: [0; 32],
+        name: [0; 32],
+    };
+    assert_eq!(posted_message(&mut proxy.context, 2).await, expected);
+}
+
+#[tokio::test]
+async fn receive_attestation_checks_foreign_deployment() {
+    let mut proxy = Proxy::start().await;
+    let payer = proxy.owner();
+    let mint = proxy.mint;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+
+    // The native mint has no metadata, so only blank names match.
+    let named = attest_body(4, "CAT", "CAT Token");
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &named)
+        .await
+        .unwrap();
+    let ix = receive_attestation_ix(&payer, &mint, &named);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::AttestationMismatch);
+
+    let body = attest_body(5, "", "");
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let ix = receive_attestation_ix(&payer, &mint, &body);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let attestation: ForeignAttestation = account(
+        &mut proxy.context,
+        foreign_attestation_address(FOREIGN_CHAIN.into()),
+    )
+    .await;
+    assert_eq!(attestation.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(attestation.sequence, 5);
+    assert_eq!(attestation.token_address, [0xab; 32]);
+    assert_eq!(attestation.decimals, 18);
+
+    let ix = receive_attestation_ix(&payer, &mint, &body);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::StaleAttestation);
+}
+
+#[tokio::test]
+async fn post_hello_is_owner_only_for_the_native_mint() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+    let mint = proxy.mint;
+
+    let intruder = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+    let ix = post_hello_ix(&intruder.pubkey(), &mint, 1);
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::OwnerOnly);
+
+    let other_mint = create_mint(&mut proxy.context, 6).await;
+    let ix = post_hello_ix(&owner, &other_mint, 1);
+    assert!(process(&mut proxy.context, &[ix], &[]).await.is_err());
+
+    process(&mut proxy.context, &[post_hello_ix(&owner, &mint, 1)], &[])
+        .await
+        .unwrap();
+    assert_eq!(
+        posted_message(&mut proxy.context, 2).await,
+        posted_message(&mut proxy.context, 1).await
+    );
+}
+
+#[tokio::test]
+async fn receive_hello_registers_approved_emitters_only() {
+    let mut proxy = Proxy::start().await;
+    let owner = proxy.owner();
+    let payer = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+
+    let body = hello_body(0, FOREIGN_EMITTER, FOREIGN_EMITTER);
+    proxy
+        .wormhole
+        .post_vaa(&mut proxy.context, &body)
+        .await
+        .unwrap();
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &body);
+    assert!(process(&mut proxy.context, &[ix], &[&payer]).await.is_err());
+
+    let ix = approve_emitter_ix(&owner, FOREIGN_CHAIN.into(), [0xcb; 32]);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &body);
+    let result = process(&mut proxy.context, &[ix], &[&payer]).await;
+    assert_program_error(result, ErrorFactory::EmitterNotApproved);
+
+    // Approving again replaces the address.
+    let ix = approve_emitter_ix(&owner, FOREIGN_CHAIN.into(), FOREIGN_EMITTER);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    let ix = receive_hello_ix(&payer.pubkey(), &owner, &body);
+    process(&mut proxy.context, &[ix], &[&payer]).await.unwrap();
+
+    let emitter: ForeignEmitter = account(
+        &mut proxy.context,
+        foreign_emitter_address(FOREIGN_CHAIN.into()),
+    )
+    .await;
+    assert_eq!(emitter.chain, u64::from(FOREIGN_CHAIN));
+    assert_eq!(emitter.address, FOREIGN_EMITTER);
+}
+
+#[tokio::test]
+async fn outbound_transfers_are_indexed_by_sequence_until_refunded() {
+    let mut proxy = Proxy::start().await;
+    let (sender_ata, payload) = bridge_out_to_foreign_chain(&mut proxy, 1_234_567_891).await;
+    let sender = Pubkey::from(payload.source_user_address);
+
+    let outbound: OutboundTransfer =
+        account(&mut proxy.context, outbound_transfer_address(1)).await;
+    assert_eq!(outbound.sender, sender);
+    assert_eq!(outbound.status, OutboundStatus::Pending);
+    assert_eq!(outbound.sequence, 1);
+    assert_eq!(outbound.amount, 1_234_567_891);
+    assert_eq!(outbound.recipient_chain, FOREIGN_CAT_CHAIN);
+    assert_eq!(outbound.recipient, [0xee; 32]);
+    assert!(outbound.timestamp > 0);
+
+    // The pending filters match the account, and stop matching once it is
+    // refunded.
+    let matches = |data: &[u8], sender: &Pubkey| {
+        OutboundTransfer::pending_filters(sender)
+            .iter()
+            .all(|(offset, bytes)| data[*offset..].starts_with(bytes))
+    };
+    let data = proxy
+        .context
+        .banks_client
+        .get_account(outbound_transfer_address(1))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(matches(&data, &sender));
+    assert!(!matches(&data, &Pubkey::new_unique()));
+
+    let body = refund_body(0, 1, payload);
+    proxy.refund(&sender_ata, 1, &body).await.unwrap();
+
+    let outbound: OutboundTransfer =
+        account(&mut proxy.context, outbound_transfer_address(1)).await;
+    assert_eq!(outbound.status, OutboundStatus::Refunded);
+    let data = proxy
+        .context
+        .banks_client
+        .get_account(outbound_transfer_address(1))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert!(!matches(&data, &sender));
+}
+
+#[tokio::test]
+async fn close_outbound_transfer_returns_rent_once_settled() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 2 * ONE).await;
+    let sender_ata = proxy.ata(&sender.pubkey());
+    for sequence in [1, 2] {
+        proxy
+            .bridge_out(&sender, sequence, bridge_out_params(ONE))
+            .await
+            .unwrap();
+    }
+    let refunded = outbound_transfer_address(1);
+    let outbound: OutboundTransfer = account(&mut proxy.context, refunded).await;
+    assert_eq!(outbound.payer, sender.pubkey());
+
+    // Pending: kept for the retention period.
+    let ix = close_outbound_transfer_ix(&sender.pubkey(), &sender.pubkey(), &refunded);
+    let result = process(&mut proxy.context, &[ix], &[&sender]).await;
+    assert_program_error(result, ErrorFactory::OutboundTransferNotSettled);
+
+    // Refunded: closed at once, by its payer or the owner.
+    let payload = match posted_message(&mut proxy.context, 2).await {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => panic!("expected CrossChainPayload"),
+    };
+    proxy
+        .refund(&sender_ata, 1, &refund_body(0, 1, payload))
+        .await
+        .unwrap();
+
+    let intruder = funded_keypair(&mut proxy.context, 1_000_000_000).await;
+    let ix = close_outbound_transfer_ix(&intruder.pubkey(), &sender.pubkey(), &refunded);
+    let result = process(&mut proxy.context, &[ix], &[&intruder]).await;
+    assert_program_error(result, ErrorFactory::PayerOrOwnerOnly);
+    let owner = proxy.owner();
+    let ix = close_outbound_transfer_ix(&owner, &owner, &refunded);
+    let result = process(&mut proxy.context, &[ix], &[]).await;
+    assert_program_error(result, ErrorFactory::InvalidRentRecipient);
+
+    let rent = proxy.lamports(refunded).await;
+    let before = proxy.lamports(sender.pubkey()).await;
+    let ix = close_outbound_transfer_ix(&owner, &sender.pubkey(), &refunded);
+    process(&mut proxy.context, &[ix], &[]).await.unwrap();
+    assert!(proxy
+        .context
+        .banks_client
+        .get_account(refunded)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(proxy.lamports(sender.pubkey()).await, before + rent);
+
+    // Pending, after the retention period.
+    let pending = outbound_transfer_address(2);
+    advance_clock(&mut proxy.context, OutboundTransfer::RETENTION_PERIOD).await;
+    let ix = close_outbound_transfer_ix(&sender.pubkey(), &sender.pubkey(), &pending);
+    process(&mut proxy.context, &[ix], &[&sender])
+        .await
+        .unwrap();
+    assert!(proxy
+        .context
+        .banks_client
+        .get_account(pending)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn bridge_out_reusable_posts_every_transfer_to_one_account() {
+    let mut proxy = Proxy::start().await;
+    proxy
+        .register_emitter(FOREIGN_CHAIN.into(), FOREIGN_EMITTER)
+        .await;
+    let sender = funded_sender(&mut proxy, 3 * ONE).await;
+    let sender_ata = proxy.ata(&sender.pubkey());
+
+    let message = reusable_message_address(&sender.pubkey());
+    proxy
+        .bridge_out_reusable(&sender, 1, bridge_out_params(ONE))
+        .await
+        .unwrap();
+    let posted: wormhole::PostedVaaData = account(&mut proxy.context, message).await;
+    assert_eq!(posted.sequence(), 1);
+    assert!(matches!(
+        CATSOLStructs::try_from_slice(&posted.payload).unwrap(),
+        CATSOLStructs::CrossChainPayload { payload } if payload.source_user_address == sender.pubkey().to_bytes()
+    ));
+    let rent = proxy
+        .context
+        .banks_client
+        .get_balance(message)
+        .await
+        .unwrap();
+
+    // Guardians may not have observed the first transfer yet.
+    let result = proxy
+        .bridge_out_reusable(&sender, 2, bridge_out_params(ONE))
+        .await;
+    assert_program_error(result, ErrorFactory::ReusableMessageInUse);
+
+    advance_clock(&mut proxy.context, REUSABLE_MESSAGE_DELAY).await;
+    proxy
+        .bridge_out_reusable(&sender, 2, bridge_out_params(ONE))
+        .await
+        .unwrap();
+
+    // The second transfer overwrote the first, without a new message account.
+    let posted: wormhole::PostedVaaData = account(&mut proxy.context, message).await;
+    assert_eq!(posted.sequence(), 2);
+    assert_eq!(
+        proxy
+            .context
+            .banks_client
+            .get_balance(message)
+            .await
+            .unwrap(),
+        rent
+    );
+    // Both transfers are recorded.
+    for sequence in [1, 2] {
+        let outbound: OutboundTransfer =
+            account(&mut proxy.context, outbound_transfer_address(sequence)).await;
+        assert_eq!(outbound.sender, sender.pubkey());
+        assert_eq!(outbound.status, OutboundStatus::Pending);
+        assert_eq!(outbound.sequence, sequence);
+        assert_eq!(outbound.amount, ONE);
+        assert_eq!(outbound.recipient_chain, FOREIGN_CAT_CHAIN);
+    }
+    assert_eq!(proxy.balance(sender_ata).await, ONE);
+    assert_eq!(proxy.balance(proxy.lock()).await, 2 * ONE);
+}
@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use cat_sol20_proxy::{
+    utils_cat::*, CATSOLStructs, CrossChainStruct, RecipientType, PAYLOAD_ID_ATTEST,
+    PAYLOAD_ID_HELLO, PAYLOAD_ID_REFUND, PAYLOAD_ID_TRANSFER, PAYLOAD_ID_TRANSFER_WITH_PAYLOAD,
+    U256,
+};
+use proptest::{collection::vec, prelude::*};
+
+/// `10^(decimals - 8)` must fit in a u64.
+const MAX_DECIMALS: u8 = 27;
+
+fn u256() -> impl Strategy<Value = U256> {
+    any::<[u8; 32]>().prop_map(|bytes| U256 { bytes })
+}
+
+fn cross_chain_struct() -> impl Strategy<Value = CrossChainStruct> {
+    (
+        u256(),
+        any::<u8>(),
+        any::<[u8; 32]>(),
+        any::<[u8; 32]>(),
+        u256(),
+        any::<[u8; 32]>(),
+        any::<[u8; 32]>(),
+        u256(),
+    )
+        .prop_map(
+            |(
+                amount,
+                token_decimals,
+                source_token_address,
+                source_user_address,
+                source_token_chain,
+                dest_token_address,
+                dest_user_address,
+                dest_token_chain,
+            )| CrossChainStruct {
+                amount,
+                token_decimals,
+                source_token_address,
+                source_user_address,
+                source_token_chain,
+                dest_token_address,
+                dest_user_address,
+                dest_token_chain,
+            },
+        )
+}
+
+fn recipient_type() -> impl Strategy<Value = RecipientType> {
+    prop_oneof![
+        Just(RecipientType::Wallet),
+        Just(RecipientType::TokenAccount)
+    ]
+}
+
+fn message() -> impl Strategy<Value = CATSOLStructs> {
+    prop_oneof![
+        cross_chain_struct().prop_map(|payload| CATSOLStructs::CrossChainPayload { payload }),
+        (any::<u64>(), cross_chain_struct())
+            .prop_map(|(sequence, payload)| CATSOLStructs::Refund { sequence, payload }),
+        (cross_chain_struct(), vec(any::<u8>(), 0..256))
+            .prop_map(|(payload, data)| CATSOLStructs::TransferWithPayload { payload, data }),
+        (cross_chain_struct(), recipient_type()).prop_map(|(payload, recipient_type)| {
+            CATSOLStructs::Transfer {
+                payload,
+                recipient_type,
+            }
+        }),
+        (
+            any::<[u8; 32]>(),
+            u256(),
+            any::<u8>(),
+            any::<[u8; 32]>(),
+            any::<[u8; 32]>(),
+        )
+            .prop_map(|(token_address, token_chain, decimals, symbol, name)| {
+                CATSOLStructs::Attest {
+                    token_address,
+                    token_chain,
+                    decimals,
+                    symbol,
+                    name,
+                }
+            }),
+        (any::<[u8; 32]>(), u256(), any::<u8>(), any::<u8>()).prop_map(
+            |(emitter, chain, decimals, version)| CATSOLStructs::Hello {
+                emitter,
+                chain,
+                decimals,
+                version,
+            }
+        ),
+    ]
+}
+
+fn encode(message: &CATSOLStructs) -> Vec<u8> {
+    let mut encoded = Vec::new();
+    message.serialize(&mut encoded).unwrap();
+    encoded
+}
+
+proptest! {
+    #[test]
+    fn message_round_trips(message in message()) {
+        let encoded = encode(&message);
+        let expected_size = match &message {
+            CATSOLStructs::CrossChainPayload { .. } => CrossChainStruct::LEN,
+            CATSOLStructs::Refund { .. } => 1 + 8 + CrossChainStruct::LEN,
+            CATSOLStructs::TransferWithPayload { data, .. } => {
+                1 + CrossChainStruct::LEN + 4 + data.len()
+            }
+            CATSOLStructs::Transfer { .. } => 1 + CrossChainStruct::LEN + 1,
+            CATSOLStructs::Attest { .. } => 1 + 32 + 32 + 1 + 32 + 32,
+            CATSOLStructs::Hello { .. } => 1 + 32 + 32 + 1 + 1,
+        };
+        prop_assert_eq!(encoded.len(), expected_size);
+
+        let decoded = CATSOLStructs::deserialize(&mut &encoded[..]).unwrap();
+        prop_assert_eq!(decoded, message);
+    }
+
+    #[test]
+    fn deserialize_never_panics(bytes in vec(any::<u8>(), 0..512)) {
+        let _ = CATSOLStructs::deserialize(&mut &bytes[..]);
+    }
+
+    #[test]
+    fn deserialize_accepts_only_known_sizes(bytes in vec(any::<u8>(), 0..512)) {
+        let decoded = CATSOLStructs::deserialize(&mut &bytes[..]);
+        let short_id = matches!(bytes.first(), Some(&PAYLOAD_ID_ATTEST) | Some(&PAYLOAD_ID_HELLO));
+        if bytes.len() < CrossChainStruct::LEN && !short_id {
+            // Attest and Hello are the only messages shorter than a transfer.
+            prop_assert!(decoded.is_err());
+        } else if let Ok(decoded) = decoded {
+            // Whatever decodes re-encodes to the bytes it was read from.
+            let encoded = encode(&decoded);
+            prop_assert_eq!(&encoded[..], &bytes[..encoded.len()]);
+        }
+    }
+
+    #[test]
+    fn unknown_payload_ids_are_rejected(
+        id in any::<u8>().prop_filter("known ID", |id| {
+            ![
+                PAYLOAD_ID_REFUND,
+                PAYLOAD_ID_TRANSFER_WITH_PAYLOAD,
+                PAYLOAD_ID_TRANSFER,
+                PAYLOAD_ID_ATTEST,
+                PAYLOAD_ID_HELLO,
+            ]
+            .contains(id)
+        }),
+        rest in vec(any::<u8>(), 300),
+    ) {
+        let bytes = [&[id][..], &rest[..]].concat();
+        prop_assert!(CATSOLStructs::deserialize(&mut &bytes[..]).is_err());
+    }
+
+    #[test]
+    fn app_data_longer_than_the_message_is_rejected(
+        payload in cross_chain_struct(),
+        data in vec(any::<u8>(), 1..64),
+    ) {
+        let message = CATSOLStructs::TransferWithPayload { payload, data };
+        let encoded = encode(&message);
+        let truncated = &encoded[..encoded.len() - 1];
+        prop_assert!(CATSOLStructs::deserialize(&mut &truncated[..]).is_err());
+    }
+
+    #[test]
+    fn unknown_recipient_types_are_rejected(
+        payload in cross_chain_struct(),
+        flag in 2..=u8::MAX,
+    ) {
+        let message = CATSOLStructs::Transfer {
+            payload,
+            recipient_type: RecipientType::Wallet,
+        };
+        let mut encoded = encode(&message);
+        *encoded.last_mut().unwrap() = flag;
+        prop_assert!(CATSOLStructs::deserialize(&mut &encoded[..]).is_err());
+    }
+
+    #[test]
+    fn u256_keeps_u64_values(value in any::<u64>()) {
+        let converted: u64 = U256::from(value).into();
+        prop_assert_eq!(converted, value);
+    }
+
+    #[test]
+    fn normalize_then_denormalize_never_increases(
+        amount in any::<u64>(),
+        decimals in 0..=MAX_DECIMALS,
+    ) {
+        let normalized = normalize_amount(amount, decimals);
+        prop_assert!(normalized <= amount);
+
+        let round_trip = denormalize_amount(normalized, decimals);
+        prop_assert!(round_trip <= amount);
+        prop_assert!(amount - round_trip < 10u64.pow(decimals.saturating_sub(8).into()));
+    }
+
+    #[test]
+    fn denormalize_then_normalize_is_identity(
+        amount in any::<u64>(),
+        decimals in 0..=MAX_DECIMALS,
+    ) {
+        let scale = 10u64.pow(decimals.saturating_sub(8).into());
+        let amount = amount / scale;
+        prop_assert_eq!(
+            normalize_amount(denormalize_amount(amount, decimals), decimals),
+            amount
+        );
+    }
+}
@@ -0,0 +1,1683 @@
+#![allow(dead_code)]
+
+use anchor_lang::{
+    prelude::*,
+    solana_program::{entrypoint::ProgramResult, instruction::Instruction},
+    system_program, Discriminator, InstructionData,
+};
+use anchor_spl::{
+    associated_token,
+    token::{self, spl_token},
+};
+use cat_sol20_proxy::{
+    utils_cat, wormhole_chain_id, ApprovedEmitter, CATSOLStructs, Config, CrossChainStruct,
+    ErrorFactory, ForeignAttestation, ForeignEmitter, OutboundTransfer, ReceivePayload, Received,
+    ReceivedBitmap, RecipientType, Redeemed, Refunded, WormholeEmitter, CONVENTIONAL_SOLANA_ID,
+    SEED_PREFIX_LOCK, SEED_PREFIX_PAYLOAD_AUTHORITY, SEED_PREFIX_PORTAL, SEED_PREFIX_REDEEMER,
+    SEED_PREFIX_REUSABLE, SEED_PREFIX_SENT, U256,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::{
+    instruction::InstructionError,
+    program_pack::Pack,
+    signature::{Keypair, Signer},
+    system_instruction, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use wormhole_anchor_sdk::{
+    token_bridge::{self, program::ID as TOKEN_BRIDGE_ID, TransferWithMeta, TransferWithPayload},
+    wormhole::{self, program::ID as CORE_BRIDGE_ID},
+};
+use wormhole_test_guardian::{
+    capture_log_data, core_bridge, program_data, GuardianSet, LocalTokenBridge, LocalWormhole,
+    VaaBody,
+};
+
+pub use wormhole_test_guardian::process;
+
+pub const DECIMALS: u8 = 9;
+
+/// Foreign chain and emitter used by most tests.
+pub const FOREIGN_CHAIN: u16 = 2;
+/// CAT chain ID of `FOREIGN_CHAIN` (Ethereum devnet on Tilt).
+pub const FOREIGN_CAT_CHAIN: u64 = 1337;
+pub const FOREIGN_EMITTER: [u8; 32] = [0xca; 32];
+/// Consistency levels of an EVM `FOREIGN_CHAIN`.
+pub const EVM_INSTANT: u8 = 200;
+pub const EVM_SAFE: u8 = 201;
+pub const EVM_FINALIZED: u8 = 1;
+/// Token Bridge of `FOREIGN_CHAIN`.
+pub const FOREIGN_TOKEN_BRIDGE: [u8; 32] = [0xb7; 32];
+
+/// Recipient program for transfers with payload, and the account it records
+/// each call in.
+pub const RECEIVER_ID: Pubkey = Pubkey::new_from_array([0x7e; 32]);
+pub const RECEIPT: Pubkey = Pubkey::new_from_array([0x7f; 32]);
+/// Error the receiver fails with when the payload is `b"reject"`.
+pub const RECEIVER_REJECTED: u32 = 0xdead;
+
+// Anchor's `entry` ties the accounts slice to the account infos' lifetime,
+// which `processor!` cannot express. Tests leak the (tiny) slice instead.
+fn process_cat_sol20_proxy(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    cat_sol20_proxy::entry(program_id, accounts, data)
+}
+
+/// Stand-in recipient program. Checks that the bridge signed the call and
+/// already delivered the tokens, then writes the `ReceivePayload` it got into
+/// `RECEIPT`, its first extra account.
+fn process_receiver(_program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (discriminator, args) = data.split_at(8);
+    if discriminator != ReceivePayload::discriminator() {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let args =
+        ReceivePayload::try_from_slice(args).map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    if *accounts[0].key != payload_authority_address() || !accounts[0].is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    let token_account = spl_token::state::Account::unpack(&accounts[2].data.borrow())?;
+    if token_account.owner != redeemer_address() || token_account.amount < args.amount {
+        return Err(ProgramError::InsufficientFunds);
+    }
+    if args.data == b"reject" {
+        return Err(ProgramError::Custom(RECEIVER_REJECTED));
+    }
+
+    let mut receipt = accounts[4].try_borrow_mut_data()?;
+    args.serialize(&mut &mut receipt[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Program test with the proxy, the local core bridge and Token Bridge. SPL
+/// Token and the Associated Token program are built into `ProgramTest`.
+pub fn program_test() -> ProgramTest {
+    let mut program_test = ProgramTest::new(
+        "cat_sol20_proxy",
+        cat_sol20_proxy::ID,
+        processor!(process_cat_sol20_proxy),
+    );
+    program_test.add_program("receiver", RECEIVER_ID, processor!(process_receiver));
+    program_test.add_account(
+        RECEIPT,
+        solana_sdk::account::Account {
+            lamports: 1_000_000_000,
+            data: vec![0; 256],
+            owner: RECEIVER_ID,
+            ..Default::default()
+        },
+    );
+    LocalWormhole::add_program(&mut program_test);
+    LocalTokenBridge::add_program(&mut program_test);
+    program_test
+}
+
+/// A started validator with the core bridge and the proxy initialized for a
+/// fresh native mint. The context payer is both the program owner and the
+/// native mint's authority.
+pub struct Proxy {
+    pub context: ProgramTestContext,
+    pub wormhole: LocalWormhole,
+    pub mint: Pubkey,
+}
+
+impl Proxy {
+    pub async fn start() -> Self {
+        Self::start_with(LocalWormhole::new(GuardianSet::new(0, 19))).await
+    }
+
+    /// Same as [`Proxy::start`], with a core bridge charging `fee` lamports
+    /// per message.
+    pub async fn start_with_fee(fee: u64) -> Self {
+        Self::start_with(LocalWormhole::new(GuardianSet::new(0, 19)).with_fee(fee)).await
+    }
+
+    async fn start_with(wormhole: LocalWormhole) -> Self {
+        let mut context = program_test().start_with_context().await;
+        capture_log_data();
+        wormhole.initialize(&mut context).await.unwrap();
+
+        let mint = create_mint(&mut context, DECIMALS).await;
+        let owner = context.payer.pubkey();
+        process(&mut context, &[initialize_ix(&owner, &mint)], &[])
+            .await
+            .unwrap();
+
+        Self {
+            context,
+            wormhole,
+            mint,
+        }
+    }
+
+    pub fn owner(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    pub fn ata(&self, wallet: &Pubkey) -> Pubkey {
+        associated_token::get_associated_token_address(wallet, &self.mint)
+    }
+
+    pub fn lock(&self) -> Pubkey {
+        lock_address(&self.mint)
+    }
+
+    /// Create `wallet`'s ATA (if needed) and mint `amount` native tokens to
+    /// it.
+    pub async fn fund(&mut self, wallet: &Pubkey, amount: u64) {
+        let payer = self.owner();
+        let ixs = [
+            create_ata_ix(&payer, wallet, &self.mint),
+            spl_token::instruction::mint_to(
+                &token::ID,
+                &self.mint,
+                &self.ata(wallet),
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap(),
+        ];
+        process(&mut self.context, &ixs, &[]).await.unwrap();
+    }
+
+    pub async fn register_emitter(&mut self, chain: u64, address: [u8; 32]) {
+        let ix = register_emitter_ix(&self.owner(), chain, address);
+        process(&mut self.context, &[ix], &[]).await.unwrap();
+    }
+
+    /// Approve the lock account as delegate of `sender`'s ATA and bridge out
+    /// `params.amount`. `sequence` is the sequence the core bridge will
+    /// assign to the outbound message.
+    pub async fn bridge_out(
+        &mut self,
+        sender: &Keypair,
+        sequence: u64,
+        params: cat_sol20_proxy::BridgeOutParams,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = bridge_out_ix(
+            &sender.pubkey(),
+            &self.mint,
+            &self.ata(&sender.pubkey()),
+            sequence,
+            params.clone(),
+        );
+        self.approve_and_process(sender, params.amount, ix).await
+    }
+
+    /// Same as [`Proxy::bridge_out`], with `data` for the recipient program.
+    pub async fn bridge_out_with_payload(
+        &mut self,
+        sender: &Keypair,
+        sequence: u64,
+        params: cat_sol20_proxy::BridgeOutParams,
+        data: Vec<u8>,
+    ) -> std::result::Result<(), BanksClientError> {
+        let mut ix = bridge_out_ix(
+            &sender.pubkey(),
+            &self.mint,
+            &self.ata(&sender.pubkey()),
+            sequence,
+            params.clone(),
+        );
+        let amount = params.amount;
+        ix.data = cat_sol20_proxy::instruction::BridgeOutWithPayload { params, data }.data();
+        self.approve_and_process(sender, amount, ix).await
+    }
+
+    /// Same as [`Proxy::bridge_out`], through `sender`'s reusable message
+    /// account.
+    pub async fn bridge_out_reusable(
+        &mut self,
+        sender: &Keypair,
+        sequence: u64,
+        params: cat_sol20_proxy::BridgeOutParams,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = bridge_out_reusable_ix(
+            &sender.pubkey(),
+            &self.mint,
+            &self.ata(&sender.pubkey()),
+            sequence,
+            params.clone(),
+        );
+        self.approve_and_process(sender, params.amount, ix).await
+    }
+
+    async fn approve_and_process(
+        &mut self,
+        sender: &Keypair,
+        amount: u64,
+        ix: Instruction,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ixs = [
+            spl_token::instruction::approve(
+                &token::ID,
+                &self.ata(&sender.pubkey()),
+                &self.lock(),
+                &sender.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap(),
+            ix,
+        ];
+        process(&mut self.context, &ixs, &[sender]).await
+    }
+
+    /// Post `body` and redeem it into `recipient`'s ATA, creating the ATA
+    /// first.
+    pub async fn bridge_in(
+        &mut self,
+        recipient: &Pubkey,
+        body: &VaaBody,
+    ) -> std::result::Result<(), BanksClientError> {
+        if !self.is_posted(body).await {
+            self.wormhole.post_vaa(&mut self.context, body).await?;
+        }
+
+        let payer = self.owner();
+        let token_account = self.ata(recipient);
+        let ixs = [
+            create_ata_ix(&payer, recipient, &self.mint),
+            bridge_in_ix(&payer, &self.mint, &token_account, body),
+        ];
+        process(&mut self.context, &ixs, &[]).await
+    }
+
+    /// Post `body` and redeem it into `token_account` with
+    /// `bridge_in_to_token_account`.
+    pub async fn bridge_in_to(
+        &mut self,
+        token_account: &Pubkey,
+        body: &VaaBody,
+    ) -> std::result::Result<(), BanksClientError> {
+        if !self.is_posted(body).await {
+            self.wormhole.post_vaa(&mut self.context, body).await?;
+        }
+
+        let ix = bridge_in_to_token_account_ix(&self.owner(), &self.mint, token_account, body);
+        process(&mut self.context, &[ix], &[]).await
+    }
+
+    /// Post `body` and redeem it into `recipient_program`'s redeemer, creating
+    /// the redeemer's ATA first.
+    pub async fn bridge_in_with_payload(
+        &mut self,
+        recipient_program: &Pubkey,
+        body: &VaaBody,
+    ) -> std::result::Result<(), BanksClientError> {
+        if !self.is_posted(body).await {
+            self.wormhole.post_vaa(&mut self.context, body).await?;
+        }
+
+        let payer = self.owner();
+        let redeemer = Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], recipient_program).0;
+        let ixs = [
+            create_ata_ix(&payer, &redeemer, &self.mint),
+            bridge_in_with_payload_ix(&payer, &self.mint, recipient_program, body),
+        ];
+        process(&mut self.context, &ixs, &[]).await
+    }
+
+    /// Initialize the Token Bridge and register `FOREIGN_TOKEN_BRIDGE` with it.
+    pub async fn init_token_bridge(&mut self) {
+        LocalTokenBridge::initialize(&mut self.context)
+            .await
+            .unwrap();
+        LocalTokenBridge::register_chain(
+            &mut self.context,
+            &mut self.wormhole,
+            FOREIGN_CHAIN,
+            FOREIGN_TOKEN_BRIDGE,
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Bridge out `params.amount` through the Token Bridge. `sequence` is the
+    /// sequence the core bridge will assign to the Token Bridge's message.
+    pub async fn bridge_out_portal(
+        &mut self,
+        sender: &Keypair,
+        sequence: u64,
+        params: cat_sol20_proxy::BridgeOutParams,
+    ) -> std::result::Result<(), BanksClientError> {
+        let ix = bridge_out_portal_ix(
+            &sender.pubkey(),
+            &self.mint,
+            &self.ata(&sender.pubkey()),
+            sequence,
+            params,
+        );
+        process(&mut self.context, &[ix], &[sender]).await
+    }
+
+    /// Post the Token Bridge transfer `body` and redeem it into `recipient`'s
+    /// ATA, creating the ATA first.
+    pub async fn bridge_in_portal(
+        &mut self,
+        recipient: &Pubkey,
+        body: &VaaBody,
+    ) -> std::result::Result<(), BanksClientError> {
+        if !self.is_posted(body).await {
+            self.wormhole.post_vaa(&mut self.context, body).await?;
+        }
+
+        let payer = self.owner();
+        let ixs = [
+            create_ata_ix(&payer, recipient, &self.mint),
+            bridge_in_portal_ix(&payer, &self.mint, &self.ata(recipient), body),
+        ];
+        process(&mut self.context, &ixs, &[]).await
+    }
+
+    /// Post `body` and redeem it as a refund of outbound transfer `sequence`
+    /// into `token_account`.
+    pub async fn refund(
+        &mut self,
+        token_account: &Pubkey,
+        sequence: u64,
+        body: &VaaBody,
+    ) -> std::result::Result<(), BanksClientError> {
+        if !self.is_posted(body).await {
+            self.wormhole.post_vaa(&mut self.context, body).await?;
+        }
+
+        let ix = refund_ix(&self.owner(), &self.mint, token_account, sequence, body);
+        process(&mut self.context, &[ix], &[]).await
+    }
+
+    async fn is_posted(&mut self, body: &VaaBody) -> bool {
+        self.context
+            .banks_client
+            .get_account(body.posted_vaa_address())
+            .await
+            .unwrap()
+            .is_some()
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.context
+            .banks_client
+            .get_balance(address)
+            .await
+            .unwrap()
+    }
+
+    pub async fn balance(&mut self, address: Pubkey) -> u64 {
+        account::<token::TokenAccount>(&mut self.context, address)
+            .await
+            .amount
+    }
+}
+
+/// Create a native mint with `decimals`, owned by the context payer.
+pub async fn create_mint(context: &mut ProgramTestContext, decimals: u8) -> Pubkey {
+    let mint = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &token::ID,
+        ),
+        spl_token::instruction::initialize_mint2(
+            &token::ID,
+            &mint.pubkey(),
+            &payer,
+            None,
+            decimals,
+        )
+        .unwrap(),
+    ];
+    process(context, &ixs, &[&mint]).await.unwrap();
+    mint.pubkey()
+}
+
+/// New token account for `mint` owned by `owner`, at an address that is not
+/// an ATA, e.g. a program's vault.
+pub async fn create_token_account(
+    context: &mut ProgramTestContext,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let account = Keypair::new();
+    let payer = context.payer.pubkey();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let ixs = [
+        system_instruction::create_account(
+            &payer,
+            &account.pubkey(),
+            rent.minimum_balance(spl_token::state::Account::LEN),
+            spl_token::state::Account::LEN as u64,
+            &token::ID,
+        ),
+        spl_token::instruction::initialize_account3(&token::ID, &account.pubkey(), mint, owner)
+            .unwrap(),
+    ];
+    process(context, &ixs, &[&account]).await.unwrap();
+    account.pubkey()
+}
+
+/// Close the empty `token_account`, signed by its `owner`.
+pub async fn close_token_account(
+    context: &mut ProgramTestContext,
+    token_account: &Pubkey,
+    owner: &Keypair,
+) {
+    let payer = context.payer.pubkey();
+    let ix = spl_token::instruction::close_account(
+        &token::ID,
+        token_account,
+        &payer,
+        &owner.pubkey(),
+        &[],
+    )
+    .unwrap();
+    process(context, &[ix], &[owner]).await.unwrap();
+}
+
+/// Freeze `token_account` in place, as its mint's freeze authority would.
+pub async fn freeze(context: &mut ProgramTestContext, token_account: &Pubkey) {
+    let mut account = context
+        .banks_client
+        .get_account(*token_account)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut state = spl_token::state::Account::unpack(&account.data).unwrap();
+    state.state = spl_token::state::AccountState::Frozen;
+    spl_token::state::Account::pack(state, &mut account.data).unwrap();
+    context.set_account(token_account, &account.into());
+}
+
+/// Idempotent `CreateAssociatedTokenAccount`.
+pub fn create_ata_ix(payer: &Pubkey, wallet: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: associated_token::ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(
+                associated_token::get_associated_token_address(wallet, mint),
+                false,
+            ),
+            AccountMeta::new_readonly(*wallet, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: vec![1],
+    }
+}
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[Config::SEED_PREFIX], &cat_sol20_proxy::ID).0
+}
+
+pub fn payload_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_PAYLOAD_AUTHORITY], &cat_sol20_proxy::ID).0
+}
+
+pub fn lock_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_LOCK, mint.as_ref()], &cat_sol20_proxy::ID).0
+}
+
+pub fn emitter_address() -> Pubkey {
+    Pubkey::find_program_address(&[WormholeEmitter::SEED_PREFIX], &cat_sol20_proxy::ID).0
+}
+
+pub fn sequence_address() -> Pubkey {
+    core_bridge::sequence_address(&emitter_address())
+}
+
+/// Message account posted for `seed`. Note that the account for sequence `n`
+/// is seeded with `n + 1`.
+pub fn message_address(seed: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX_SENT, &seed.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+/// Message account of the Token Bridge transfer with `sequence`.
+pub fn portal_message_address(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX_PORTAL, &sequence.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn reusable_message_address(owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[SEED_PREFIX_REUSABLE, owner.as_ref()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn foreign_emitter_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ForeignEmitter::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn foreign_attestation_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ForeignAttestation::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+/// Metaplex metadata address of `mint`. Test mints have none.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            mpl_token_metadata::id().as_ref(),
+            mint.as_ref(),
+        ],
+        &mpl_token_metadata::id(),
+    )
+    .0
+}
+
+pub fn approved_emitter_address(chain: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ApprovedEmitter::SEED_PREFIX, &chain.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn received_address(chain: u16, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            Received::SEED_PREFIX,
+            &chain.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+/// Received bitmap covering (`chain`, `sequence`).
+pub fn received_bitmap_address(chain: u16, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ReceivedBitmap::SEED_PREFIX,
+            &chain.to_le_bytes(),
+            &ReceivedBitmap::index(sequence).to_le_bytes(),
+        ],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn refunded_address(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[Refunded::SEED_PREFIX, &sequence.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn outbound_transfer_address(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[OutboundTransfer::SEED_PREFIX, &sequence.to_le_bytes()],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+/// Outbound transfer account of the Token Bridge transfer with `sequence`.
+pub fn portal_outbound_transfer_address(sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            OutboundTransfer::SEED_PREFIX,
+            SEED_PREFIX_PORTAL,
+            &sequence.to_le_bytes(),
+        ],
+        &cat_sol20_proxy::ID,
+    )
+    .0
+}
+
+pub fn initialize_ix(owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::Initialize {
+            owner: *owner,
+            config: config_address(),
+            token_mint: *mint,
+            token_mint_ata: lock_address(mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: core_bridge::bridge_address(),
+            wormhole_fee_collector: core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(wormhole::INITIAL_SEQUENCE),
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::Initialize {}.data(),
+    }
+}
+
+pub fn transfer_ownership_ix(owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::TransferOwnership {
+            owner: *owner,
+            new_owner: *new_owner,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::TransferOwnership {}.data(),
+    }
+}
+
+pub fn register_emitter_ix(owner: &Pubkey, chain: u64, address: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::RegisterEmitter {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::RegisterEmitter {
+            params: cat_sol20_proxy::RegisterEmitterParams { chain, address },
+        }
+        .data(),
+    }
+}
+
+pub fn migrate_config_ix(owner: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::MigrateConfig {
+            owner: *owner,
+            config: config_address(),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::MigrateConfig {}.data(),
+    }
+}
+
+pub fn migrate_emitter_ix(owner: &Pubkey, chain: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::MigrateEmitter {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::MigrateEmitter {
+            params: cat_sol20_proxy::MigrateEmitterParams { chain },
+        }
+        .data(),
+    }
+}
+
+pub fn set_message_params_ix(
+    owner: &Pubkey,
+    batch_id: u32,
+    finality: u8,
+    finalized_threshold: u64,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::SetMessageParams {
+            owner: *owner,
+            config: config_address(),
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::SetMessageParams {
+            params: cat_sol20_proxy::SetMessageParamsParams {
+                batch_id,
+                finality,
+                finalized_threshold,
+            },
+        }
+        .data(),
+    }
+}
+
+pub fn set_emitter_finality_ix(owner: &Pubkey, chain: u64, finality: Option<u8>) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::SetEmitterFinality {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::SetEmitterFinality {
+            params: cat_sol20_proxy::SetEmitterFinalityParams { chain, finality },
+        }
+        .data(),
+    }
+}
+
+pub fn set_emitter_policy_ix(
+    owner: &Pubkey,
+    chain: u64,
+    max_vaa_age: u32,
+    consistency_levels: &[u8],
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::SetEmitterPolicy {
+            owner: *owner,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(chain),
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::SetEmitterPolicy {
+            params: cat_sol20_proxy::SetEmitterPolicyParams {
+                chain,
+                max_vaa_age,
+                consistency_levels: consistency_levels.to_vec(),
+            },
+        }
+        .data(),
+    }
+}
+
+pub fn approve_emitter_ix(owner: &Pubkey, chain: u64, address: [u8; 32]) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::ApproveEmitter {
+            owner: *owner,
+            config: config_address(),
+            approved_emitter: approved_emitter_address(chain),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::ApproveEmitter {
+            params: cat_sol20_proxy::ApproveEmitterParams { chain, address },
+        }
+        .data(),
+    }
+}
+
+/// `post_hello`. `sequence` is the sequence the core bridge will assign to
+/// the `Hello`.
+pub fn post_hello_ix(owner: &Pubkey, mint: &Pubkey, sequence: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::PostHello {
+            owner: *owner,
+            token_mint: *mint,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: core_bridge::bridge_address(),
+            wormhole_fee_collector: core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(sequence + 1),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::PostHello {}.data(),
+    }
+}
+
+/// `receive_hello` of the posted `body`. `owner` gets the approval's rent.
+pub fn receive_hello_ix(payer: &Pubkey, owner: &Pubkey, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::ReceiveHello {
+            payer: *payer,
+            config: config_address(),
+            owner: *owner,
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            approved_emitter: approved_emitter_address(body.emitter_chain.into()),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::ReceiveHello {
+            params: cat_sol20_proxy::ReceiveHelloParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `attest_token` for `mint`. `sequence` is the sequence the core bridge
+/// will assign to the attestation.
+pub fn attest_token_ix(payer: &Pubkey, mint: &Pubkey, sequence: u64) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::AttestToken {
+            payer: *payer,
+            token_mint: *mint,
+            metadata_account: metadata_address(mint),
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: core_bridge::bridge_address(),
+            wormhole_fee_collector: core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(sequence + 1),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::AttestToken {}.data(),
+    }
+}
+
+/// `receive_attestation` of the posted `body`, checked against the native
+/// `mint`.
+pub fn receive_attestation_ix(payer: &Pubkey, mint: &Pubkey, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::ReceiveAttestation {
+            payer: *payer,
+            config: config_address(),
+            metadata_account: metadata_address(mint),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            foreign_attestation: foreign_attestation_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::ReceiveAttestation {
+            params: cat_sol20_proxy::ReceiveAttestationParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+pub fn bridge_out_ix(
+    sender: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    sequence: u64,
+    params: cat_sol20_proxy::BridgeOutParams,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::BridgeOut {
+            owner: *sender,
+            token_mint: *mint,
+            token_user_ata: *token_account,
+            token_mint_ata: lock_address(mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: core_bridge::bridge_address(),
+            wormhole_fee_collector: core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: message_address(sequence + 1),
+            outbound_transfer: outbound_transfer_address(sequence),
+            foreign_emitter: foreign_emitter_address(
+                wormhole_chain_id(params.recipient_chain)
+                    .unwrap_or_default()
+                    .into(),
+            ),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::BridgeOut { params }.data(),
+    }
+}
+
+pub fn bridge_out_portal_ix(
+    sender: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    sequence: u64,
+    params: cat_sol20_proxy::BridgeOutParams,
+) -> Instruction {
+    let token_bridge_emitter = token_bridge::emitter_address();
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::BridgeOutPortal {
+            owner: *sender,
+            token_mint: *mint,
+            token_user_ata: *token_account,
+            config: config_address(),
+            foreign_emitter: foreign_emitter_address(
+                wormhole_chain_id(params.recipient_chain)
+                    .unwrap_or_default()
+                    .into(),
+            ),
+            token_bridge_program: TOKEN_BRIDGE_ID,
+            token_bridge_config: token_bridge::Config::address(),
+            token_bridge_custody: token_bridge::custody_address(mint),
+            token_bridge_authority_signer: token_bridge::authority_signer_address(),
+            token_bridge_custody_signer: token_bridge::custody_signer_address(),
+            token_bridge_emitter,
+            token_bridge_sender: token_bridge::sender_address(&cat_sol20_proxy::ID),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: core_bridge::bridge_address(),
+            wormhole_fee_collector: core_bridge::fee_collector_address(),
+            wormhole_sequence: core_bridge::sequence_address(&token_bridge_emitter),
+            wormhole_message: portal_message_address(sequence),
+            outbound_transfer: portal_outbound_transfer_address(sequence),
+            token_program: token::ID,
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::BridgeOutPortal { params }.data(),
+    }
+}
+
+pub fn bridge_out_reusable_ix(
+    sender: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    sequence: u64,
+    params: cat_sol20_proxy::BridgeOutParams,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::BridgeOutReusable {
+            owner: *sender,
+            token_mint: *mint,
+            token_user_ata: *token_account,
+            token_mint_ata: lock_address(mint),
+            token_program: token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            wormhole_bridge: core_bridge::bridge_address(),
+            wormhole_fee_collector: core_bridge::fee_collector_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_sequence: sequence_address(),
+            wormhole_message: reusable_message_address(sender),
+            outbound_transfer: outbound_transfer_address(sequence),
+            foreign_emitter: foreign_emitter_address(
+                wormhole_chain_id(params.recipient_chain)
+                    .unwrap_or_default()
+                    .into(),
+            ),
+            system_program: system_program::ID,
+            clock: sysvar::clock::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::BridgeOutReusable { params }.data(),
+    }
+}
+
+pub fn bridge_in_ix(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::BridgeIn {
+            owner: *payer,
+            token_mint: *mint,
+            token_user_ata: *token_account,
+            token_mint_ata: lock_address(mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            received: received_address(body.emitter_chain, body.sequence),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::BridgeIn {
+            params: cat_sol20_proxy::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `bridge_in_to_token_account` redeeming the posted `body` into
+/// `token_account`.
+pub fn bridge_in_to_token_account_ix(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::BridgeInToTokenAccount {
+            owner: *payer,
+            token_mint: *mint,
+            token_account: *token_account,
+            token_mint_ata: lock_address(mint),
+            token_program: token::ID,
+            config: config_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            received: received_address(body.emitter_chain, body.sequence),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::BridgeInToTokenAccount {
+            params: cat_sol20_proxy::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `bridge_in_portal` completing the Token Bridge transfer `body` into
+/// `token_account`.
+pub fn bridge_in_portal_ix(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    let transfer = TransferWithPayload::deserialize(&mut &body.payload[..]).unwrap();
+    let redeemer = token_bridge::redeemer_address(&cat_sol20_proxy::ID);
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::BridgeInPortal {
+            owner: *payer,
+            config: config_address(),
+            token_mint: *mint,
+            token_user_ata: *token_account,
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            token_bridge_program: TOKEN_BRIDGE_ID,
+            token_bridge_config: token_bridge::Config::address(),
+            token_bridge_claim: token_bridge::claim_address(
+                body.emitter_chain,
+                &body.emitter_address,
+                body.sequence,
+            ),
+            token_bridge_foreign_endpoint: token_bridge::EndpointRegistration::address(
+                body.emitter_chain,
+                &body.emitter_address,
+            ),
+            token_bridge_custody: token_bridge::custody_address(&transfer.mint()),
+            token_bridge_custody_signer: token_bridge::custody_signer_address(),
+            token_bridge_redeemer: redeemer,
+            token_bridge_redeemer_ata: associated_token::get_associated_token_address(
+                &redeemer, mint,
+            ),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::BridgeInPortal {
+            params: cat_sol20_proxy::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// `bridge_in_with_payload` redeeming the posted `body` into
+/// `recipient_program`'s redeemer and calling it with `RECEIPT`.
+pub fn bridge_in_with_payload_ix(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    recipient_program: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    let redeemer = Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], recipient_program).0;
+    let mut accounts = cat_sol20_proxy::accounts::BridgeInWithPayload {
+        owner: *payer,
+        recipient_program: *recipient_program,
+        redeemer,
+        token_mint: *mint,
+        token_user_ata: associated_token::get_associated_token_address(&redeemer, mint),
+        token_mint_ata: lock_address(mint),
+        token_program: token::ID,
+        associated_token_program: associated_token::ID,
+        config: config_address(),
+        payload_authority: payload_authority_address(),
+        wormhole_program: CORE_BRIDGE_ID,
+        posted: body.posted_vaa_address(),
+        received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+        received: received_address(body.emitter_chain, body.sequence),
+        foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+        system_program: system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.push(AccountMeta::new(RECEIPT, false));
+
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts,
+        data: cat_sol20_proxy::instruction::BridgeInWithPayload {
+            params: cat_sol20_proxy::BridgeInParams {
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+pub fn refund_ix(
+    payer: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    sequence: u64,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::Refund {
+            owner: *payer,
+            token_mint: *mint,
+            token_user_ata: *token_account,
+            token_mint_ata: lock_address(mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            config: config_address(),
+            wormhole_emitter: emitter_address(),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            received: received_address(body.emitter_chain, body.sequence),
+            refunded: refunded_address(sequence),
+            outbound_transfer: outbound_transfer_address(sequence),
+            foreign_emitter: foreign_emitter_address(body.emitter_chain.into()),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::Refund {
+            params: cat_sol20_proxy::RefundParams {
+                vaa_hash: body.body_hash(),
+                sequence,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `refund_ix` passing the program ID for the outbound transfer account of
+/// `sequence`, as clients pass an optional account they leave out.
+pub fn without_outbound_transfer(mut ix: Instruction, sequence: u64) -> Instruction {
+    for meta in &mut ix.accounts {
+        if meta.pubkey == outbound_transfer_address(sequence) {
+            *meta = AccountMeta::new_readonly(ix.program_id, false);
+        }
+    }
+    ix
+}
+
+/// `close_received` closing the received account of the redeemed `body`,
+/// signed by `signer` and sending its rent to `rent_recipient`.
+pub fn close_received_ix(signer: &Pubkey, rent_recipient: &Pubkey, body: &VaaBody) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::CloseReceived {
+            signer: *signer,
+            config: config_address(),
+            rent_recipient: *rent_recipient,
+            received: received_address(body.emitter_chain, body.sequence),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::CloseReceived {
+            params: cat_sol20_proxy::CloseReceivedParams {
+                emitter_chain: body.emitter_chain,
+                sequence: body.sequence,
+            },
+        }
+        .data(),
+    }
+}
+
+/// `close_outbound_transfer` closing the outbound transfer account
+/// `outbound_transfer`, signed by `signer` and sending its rent to
+/// `rent_recipient`.
+pub fn close_outbound_transfer_ix(
+    signer: &Pubkey,
+    rent_recipient: &Pubkey,
+    outbound_transfer: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::CloseOutboundTransfer {
+            signer: *signer,
+            config: config_address(),
+            rent_recipient: *rent_recipient,
+            outbound_transfer: *outbound_transfer,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::CloseOutboundTransfer {}.data(),
+    }
+}
+
+/// `close_legacy_received` closing the legacy received account of the
+/// redeemed `body`, sending its rent to `rent_recipient`.
+pub fn close_legacy_received_ix(
+    owner: &Pubkey,
+    rent_recipient: &Pubkey,
+    body: &VaaBody,
+) -> Instruction {
+    Instruction {
+        program_id: cat_sol20_proxy::ID,
+        accounts: cat_sol20_proxy::accounts::CloseLegacyReceived {
+            owner: *owner,
+            config: config_address(),
+            rent_recipient: *rent_recipient,
+            received: received_address(body.emitter_chain, body.sequence),
+            wormhole_program: CORE_BRIDGE_ID,
+            posted: body.posted_vaa_address(),
+            received_bitmap: received_bitmap_address(body.emitter_chain, body.sequence),
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: cat_sol20_proxy::instruction::CloseLegacyReceived {
+            params: cat_sol20_proxy::CloseLegacyReceivedParams {
+                emitter_chain: body.emitter_chain,
+                sequence: body.sequence,
+                vaa_hash: body.body_hash(),
+            },
+        }
+        .data(),
+    }
+}
+
+/// The foreign emitter refunding the outbound transfer `sequence`, which
+/// carried `payload`.
+pub fn refund_body(vaa_sequence: u64, sequence: u64, payload: CrossChainStruct) -> VaaBody {
+    VaaBody::new(
+        FOREIGN_CHAIN,
+        FOREIGN_EMITTER,
+        vaa_sequence,
+        &CATSOLStructs::Refund { sequence, payload },
+    )
+    .with_timestamp(1_700_000_000)
+    .with_consistency_level(1)
+}
+
+/// A `Hello` posted by `emitter` on `FOREIGN_CHAIN`, naming `named_emitter`.
+pub fn hello_body(sequence: u64, emitter: [u8; 32], named_emitter: [u8; 32]) -> VaaBody {
+    let message = CATSOLStructs::Hello {
+        emitter: named_emitter,
+        chain: U256::from(FOREIGN_CAT_CHAIN),
+        decimals: 18,
+        version: 1,
+    };
+    VaaBody::new(FOREIGN_CHAIN, emitter, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// The foreign emitter attesting a deployment named `name` with `symbol`.
+pub fn attest_body(sequence: u64, symbol: &str, name: &str) -> VaaBody {
+    let message = CATSOLStructs::Attest {
+        token_address: [0xab; 32],
+        token_chain: U256::from(FOREIGN_CAT_CHAIN),
+        decimals: 18,
+        symbol: utils_cat::attest_bytes(symbol),
+        name: utils_cat::attest_bytes(name),
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// A transfer of `amount` (8 decimals) from the foreign emitter to
+/// `recipient` on `dest_chain`.
+pub fn transfer_body(sequence: u64, amount: u64, recipient: &Pubkey, dest_chain: u64) -> VaaBody {
+    let payload = CATSOLStructs::CrossChainPayload {
+        payload: CrossChainStruct {
+            amount: U256::from(amount),
+            token_decimals: 18,
+            source_token_address: FOREIGN_EMITTER,
+            source_user_address: [0xee; 32],
+            source_token_chain: U256::from(FOREIGN_CAT_CHAIN),
+            dest_token_address: emitter_address().to_bytes(),
+            dest_user_address: recipient.to_bytes(),
+            dest_token_chain: U256::from(dest_chain),
+        },
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &payload)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// A transfer of `amount` (8 decimals) from the foreign emitter to
+/// `recipient_program`, carrying `data`.
+pub fn transfer_with_payload_body(
+    sequence: u64,
+    amount: u64,
+    recipient_program: &Pubkey,
+    data: &[u8],
+) -> VaaBody {
+    let transfer = transfer_body(sequence, amount, recipient_program, CONVENTIONAL_SOLANA_ID);
+    let payload = match CATSOLStructs::deserialize(&mut &transfer.payload[..]).unwrap() {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => unreachable!(),
+    };
+    let message = CATSOLStructs::TransferWithPayload {
+        payload,
+        data: data.to_vec(),
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+/// A Token Bridge transfer of `amount` (8 decimals) of `mint` to this
+/// program, sent by `sender` and carrying a CAT transfer to `recipient`.
+pub fn portal_transfer_body(
+    sequence: u64,
+    mint: &Pubkey,
+    amount: u64,
+    recipient: &Pubkey,
+    sender: [u8; 32],
+) -> VaaBody {
+    let transfer = transfer_body(sequence, amount, recipient, CONVENTIONAL_SOLANA_ID);
+    let meta = TransferWithMeta {
+        amount,
+        token_address: mint.to_bytes(),
+        token_chain: wormhole::CHAIN_ID_SOLANA,
+        to_address: cat_sol20_proxy::ID.to_bytes(),
+        to_chain: wormhole::CHAIN_ID_SOLANA,
+        from_address: sender,
+    };
+    let message = TransferWithPayload::new(&meta, transfer.payload);
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_TOKEN_BRIDGE, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+pub fn redeemer_address() -> Pubkey {
+    Pubkey::find_program_address(&[SEED_PREFIX_REDEEMER], &RECEIVER_ID).0
+}
+
+/// What the receiver was last called with, if anything.
+pub async fn receipt(context: &mut ProgramTestContext) -> Option<ReceivePayload> {
+    let account = context
+        .banks_client
+        .get_account(RECEIPT)
+        .await
+        .unwrap()
+        .unwrap();
+    if account.data.iter().all(|byte| *byte == 0) {
+        return None;
+    }
+    Some(ReceivePayload::deserialize(&mut &account.data[..]).unwrap())
+}
+
+/// A transfer of `amount` (8 decimals) from the foreign emitter, flagged
+/// with how `recipient` should be read.
+pub fn transfer_to_body(
+    sequence: u64,
+    amount: u64,
+    recipient: &Pubkey,
+    recipient_type: RecipientType,
+) -> VaaBody {
+    let transfer = transfer_body(sequence, amount, recipient, CONVENTIONAL_SOLANA_ID);
+    let payload = match CATSOLStructs::deserialize(&mut &transfer.payload[..]).unwrap() {
+        CATSOLStructs::CrossChainPayload { payload } => payload,
+        _ => unreachable!(),
+    };
+    let message = CATSOLStructs::Transfer {
+        payload,
+        recipient_type,
+    };
+    VaaBody::new(FOREIGN_CHAIN, FOREIGN_EMITTER, sequence, &message)
+        .with_timestamp(1_700_000_000)
+        .with_consistency_level(1)
+}
+
+pub fn bridge_out_params(amount: u64) -> cat_sol20_proxy::BridgeOutParams {
+    cat_sol20_proxy::BridgeOutParams {
+        amount,
+        recipient_chain: FOREIGN_CAT_CHAIN,
+        recipient: [0xee; 32],
+        recipient_contract: FOREIGN_EMITTER,
+    }
+}
+
+/// New keypair holding `lamports`.
+pub async fn funded_keypair(context: &mut ProgramTestContext, lamports: u64) -> Keypair {
+    let keypair = Keypair::new();
+    let ix = system_instruction::transfer(&context.payer.pubkey(), &keypair.pubkey(), lamports);
+    process(context, &[ix], &[]).await.unwrap();
+    keypair
+}
+
+/// Stand in for a message redeemed by an earlier version of the program:
+/// create its `Received` account, paid for by `payer`.
+pub async fn set_received(context: &mut ProgramTestContext, body: &VaaBody, payer: &Pubkey) {
+    let received = Received {
+        wormhole_message_hash: body.body_hash(),
+        payer: *payer,
+        emitter_chain: body.emitter_chain,
+        sequence: body.sequence,
+        amount: 1_000,
+        recipient: Pubkey::new_unique(),
+    };
+    let mut data = Vec::with_capacity(Received::MAXIMUM_SIZE);
+    received.try_serialize(&mut data).unwrap();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = solana_sdk::account::Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: cat_sol20_proxy::ID,
+        ..Default::default()
+    };
+    context.set_account(
+        &received_address(body.emitter_chain, body.sequence),
+        &account.into(),
+    );
+}
+
+/// Stand in for a message redeemed before the received bitmap: create its
+/// legacy `Received` account.
+pub async fn set_legacy_received(context: &mut ProgramTestContext, body: &VaaBody) {
+    // Only `wormhole_message_hash` after the discriminator.
+    let data = [&Received::DISCRIMINATOR[..], &body.body_hash()].concat();
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let account = solana_sdk::account::Account {
+        lamports: rent.minimum_balance(data.len()),
+        data,
+        owner: cat_sol20_proxy::ID,
+        ..Default::default()
+    };
+    context.set_account(
+        &received_address(body.emitter_chain, body.sequence),
+        &account.into(),
+    );
+}
+
+/// Stand in for an account created by an earlier version of the program: cut
+/// its data down to `len` bytes.
+pub async fn truncate_account(context: &mut ProgramTestContext, address: Pubkey, len: usize) {
+    let mut account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    account.data.truncate(len);
+    account.lamports = rent.minimum_balance(len);
+    context.set_account(&address, &account.into());
+}
+
+/// Whether (`chain`, `sequence`) is marked in its received bitmap.
+pub async fn is_received(context: &mut ProgramTestContext, chain: u16, sequence: u64) -> bool {
+    account::<ReceivedBitmap>(context, received_bitmap_address(chain, sequence))
+        .await
+        .is_received(sequence)
+}
+
+/// Current cluster time.
+pub async fn unix_timestamp(context: &mut ProgramTestContext) -> i64 {
+    context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .unix_timestamp
+}
+
+/// Move the cluster time `seconds` forward.
+pub async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Process `ixs` and return the `Redeemed` events they emitted.
+pub async fn process_redeemed(
+    context: &mut ProgramTestContext,
+    ixs: &[Instruction],
+    signers: &[&Keypair],
+) -> std::result::Result<Vec<Redeemed>, BanksClientError> {
+    use anchor_lang::Discriminator;
+
+    // This runs the transaction on the bank directly, which can race the
+    // unlocking of the previous transaction's accounts, so retry a few times.
+    const MAX_ATTEMPTS: usize = 10;
+    let mut attempts = 0;
+    let outcome = loop {
+        let blockhash = context.get_new_latest_blockhash().await?;
+        let mut all_signers = vec![&context.payer];
+        all_signers.extend_from_slice(signers);
+        let tx = Transaction::new_signed_with_payer(
+            ixs,
+            Some(&context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+        let outcome = context
+            .banks_client
+            .process_transaction_with_metadata(tx)
+            .await?;
+        if outcome.result != Err(TransactionError::AccountInUse) {
+            break outcome;
+        }
+        attempts += 1;
+        assert!(
+            attempts < MAX_ATTEMPTS,
+            "transaction still hit AccountInUse after {MAX_ATTEMPTS} attempts"
+        );
+    };
+    outcome.result?;
+
+    let logs = outcome.metadata.map(|m| m.log_messages).unwrap_or_default();
+    Ok(program_data(&logs)
+        .into_iter()
+        .filter(|data| data.starts_with(&Redeemed::DISCRIMINATOR))
+        .map(|data| Redeemed::try_from_slice(&data[8..]).unwrap())
+        .collect())
+}
+
+/// Copy the posted VAA of `body` to the address of `vaa_hash`, as if the core
+/// bridge had posted it under that hash.
+pub async fn repost_under_hash(
+    context: &mut ProgramTestContext,
+    body: &VaaBody,
+    vaa_hash: [u8; 32],
+) -> Pubkey {
+    let account = context
+        .banks_client
+        .get_account(body.posted_vaa_address())
+        .await
+        .unwrap()
+        .unwrap();
+    let address = Pubkey::find_program_address(
+        &[wormhole::SEED_PREFIX_POSTED_VAA, &vaa_hash],
+        &CORE_BRIDGE_ID,
+    )
+    .0;
+    context.set_account(&address, &account.into());
+    address
+}
+
+pub async fn account<T: AccountDeserialize>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .expect("account does not exist");
+    T::try_deserialize(&mut &account.data[..]).unwrap()
+}
+
+/// Message this program posted for `seed`.
+pub async fn posted(context: &mut ProgramTestContext, seed: u64) -> wormhole::PostedVaaData {
+    account(context, message_address(seed)).await
+}
+
+/// Payload of the message this program posted for `seed`.
+pub async fn posted_message(context: &mut ProgramTestContext, seed: u64) -> CATSOLStructs {
+    CATSOLStructs::try_from_slice(&posted(context, seed).await.payload).unwrap()
+}
+
+pub fn assert_program_error(
+    result: std::result::Result<(), BanksClientError>,
+    expected: ErrorFactory,
+) {
+    assert_instruction_error(result, InstructionError::Custom(expected.into()));
+}
+
+pub fn assert_instruction_error(
+    result: std::result::Result<(), BanksClientError>,
+    expected: InstructionError,
+) {
+    match result.unwrap_err() {
+        BanksClientError::TransactionError(TransactionError::InstructionError(_, err)) => {
+            assert_eq!(err, expected)
+        }
+        err => panic!("unexpected error: {err:?}"),
+    }
+}
@@ -1,5 +1,8 @@
 #!/bin/bash
 
+# Wormhole network the CAT programs are built for.
+NETWORK=${NETWORK:-tilt-devnet}
+
 if [ $# -eq 0 ]; then
   echo "No arguments provided."
 else
@@ -7,22 +10,22 @@ else
   echo "Running test..."
   if [ $1 == "all" ]; then
     echo "Running all tests..."
-    anchor build && anchor deploy
-    anchor test --skip-local-validator
+    anchor build -p test_token && anchor build -p cat_sol20 -- --features $NETWORK && anchor build -p cat_sol20_proxy -- --features $NETWORK && anchor deploy
+    anchor test --skip-build --skip-local-validator -- --features $NETWORK
   elif [ $1 == "test" ]; then
     echo "Running test..."
     anchor build -p test_token && anchor deploy -p test_token
   elif [ $1 == "build" ]; then
     echo "Running all Deployments..."
-    anchor build && anchor deploy
+    anchor build -p test_token && anchor build -p cat_sol20 -- --features $NETWORK && anchor build -p cat_sol20_proxy -- --features $NETWORK && anchor deploy
   elif [ $1 == "new" ]; then
     echo "Running CATSOL20 test..."
-    anchor build -p cat_sol20 && anchor deploy -p cat_sol20
-    anchor test --run tests/CATSOL20 --skip-build --skip-deploy --skip-local-validator
+    anchor build -p cat_sol20 -- --features $NETWORK && anchor deploy -p cat_sol20
+    anchor test --run tests/CATSOL20 --skip-build --skip-deploy --skip-local-validator -- --features $NETWORK
   elif [ $1 == "proxy" ]; then
     echo "Running CATSOL20Proxy test..."
-    anchor build -p cat_sol20_proxy && anchor deploy -p cat_sol20_proxy
-    anchor test --run tests/CATSOL20Proxy --skip-build --skip-deploy --skip-local-validator
+    anchor build -p cat_sol20_proxy -- --features $NETWORK && anchor deploy -p cat_sol20_proxy
+    anchor test --run tests/CATSOL20Proxy --skip-build --skip-deploy --skip-local-validator -- --features $NETWORK
   fi
 
 fi
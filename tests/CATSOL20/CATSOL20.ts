@@ -417,20 +417,10 @@ describe("cat_sol20", () => {
         ], SPL_CAT_PID)
 
         // get sequence
-        const SequenceTracker = await getProgramSequenceTracker(provider.connection, SPL_CAT_PID, CORE_BRIDGE_PID)
-          .then((tracker) =>
-            deriveAddress(
-              [
-                Buffer.from("sent"),
-                (() => {
-                  const buf = Buffer.alloc(8);
-                  buf.writeBigUInt64LE(tracker.sequence + BigInt(1));
-                  return buf;
-                })(),
-              ],
-              SPL_CAT_PID
-            )
-          );
+        const tracker = await getProgramSequenceTracker(provider.connection, SPL_CAT_PID, CORE_BRIDGE_PID);
+        const SequenceTracker = deriveAddress([Buffer.from("sent"), u64LE(tracker.sequence + BigInt(1))], SPL_CAT_PID);
+        // The transfer is recorded under the sequence of its message
+        const outboundTransfer = deriveAddress([Buffer.from("outbound"), u64LE(tracker.sequence)], SPL_CAT_PID);
 
         const wormholeAccounts = getPostMessageCpiAccounts(
           SPL_CAT_PID,
@@ -454,7 +444,8 @@ describe("cat_sol20", () => {
 
         // Parameters
         let amount = new anchor.BN("10000000000000000");
-        let recipientChain = new anchor.BN(2);
+        // CAT chain ID of the Tilt Ethereum devnet (Wormhole chain 2)
+        let recipientChain = new anchor.BN(1337);
         const method = program.methods.bridgeOut({
           amount,
           recipientChain,
@@ -471,6 +462,7 @@ describe("cat_sol20", () => {
           // Wormhole Stuff
           wormholeProgram: CORE_BRIDGE_PID,
           foreignEmitter: emitterAcc,
+          outboundTransfer,
           config: configAcc,
           ...wormholeAccounts,
         }).signers([newOwner])
@@ -526,16 +518,9 @@ describe("cat_sol20", () => {
         const payload = getParsedPayload(parsedVAA.payload);
 
         const postedVAAKey = derivePostedVaaKey(CORE_BRIDGE_PID, parsedVAA.hash);
-        const recievedKey = PublicKey.findProgramAddressSync(
-          [
-            Buffer.from("received"),
-            (() => {
-              const buf = Buffer.alloc(10);
-              buf.writeUInt16LE(parsedVAA.emitterChain, 0);
-              buf.writeBigInt64LE(parsedVAA.sequence, 2);
-              return buf;
-            })(),
-          ], SPL_CAT_PID)[0];
+        // Redemptions are tracked per emitter chain and sequence
+        const recievedKey = deriveAddress([Buffer.from("received"), u16LE(parsedVAA.emitterChain), u64LE(parsedVAA.sequence)], SPL_CAT_PID);
+        const receivedBitmap = deriveAddress([Buffer.from("received_bitmap"), u16LE(parsedVAA.emitterChain), u64LE(parsedVAA.sequence / SEQUENCES_PER_BITMAP)], SPL_CAT_PID);
 
 
         const [configAcc, _] = PublicKey.findProgramAddressSync([
@@ -548,7 +533,8 @@ describe("cat_sol20", () => {
         );
 
         const foreignChainId = Buffer.alloc(8);
-        foreignChainId.writeBigUInt64LE(payload.sourceTokenChain);
+        // Emitters are registered under the Wormhole chain ID of the VAA
+        foreignChainId.writeBigUInt64LE(BigInt(parsedVAA.emitterChain));
 
         const [emitterAcc, emitterBmp] = PublicKey.findProgramAddressSync([
           Buffer.from("foreign_emitter"),
@@ -568,6 +554,7 @@ describe("cat_sol20", () => {
           wormholeProgram: CORE_BRIDGE_PID,
           foreignEmitter: emitterAcc,
           posted: postedVAAKey,
+          receivedBitmap,
           received: recievedKey,
           config: configAcc,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -608,4 +595,19 @@ function getParsedPayload(vaa: Buffer) {
     destUserAddress: new PublicKey(destUserAddress),
     destTokenChain: destTokenChain.readBigUInt64BE(),
   }
-}
\ No newline at end of file
+}
+
+// Sequences covered by one received bitmap account
+const SEQUENCES_PER_BITMAP = BigInt(8 * 1024);
+
+function u16LE(value: number) {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(value);
+  return buf;
+}
+
+function u64LE(value: bigint) {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(value);
+  return buf;
+}
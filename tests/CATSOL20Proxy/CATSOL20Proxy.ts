@@ -207,20 +207,10 @@ describe("cat_sol20_proxy", () => {
       const tokenMintATA = PublicKey.findProgramAddressSync([LOCK_PDA_SEED, testTokenMintPDA.toBuffer()], SPL_CAT_PROXY_PID)[0];
 
       // get sequence
-      const SequenceTracker = await getProgramSequenceTracker(provider.connection, SPL_CAT_PROXY_PID, CORE_BRIDGE_PID)
-        .then((tracker) =>
-          deriveAddress(
-            [
-              Buffer.from("sent"),
-              (() => {
-                const buf = Buffer.alloc(8);
-                buf.writeBigUInt64LE(tracker.sequence + BigInt(1));
-                return buf;
-              })(),
-            ],
-            SPL_CAT_PROXY_PID
-          )
-        );
+      const tracker = await getProgramSequenceTracker(provider.connection, SPL_CAT_PROXY_PID, CORE_BRIDGE_PID);
+      const SequenceTracker = deriveAddress([Buffer.from("sent"), u64LE(tracker.sequence + BigInt(1))], SPL_CAT_PROXY_PID);
+      // The transfer is recorded under the sequence of its message
+      const outboundTransfer = deriveAddress([Buffer.from("outbound"), u64LE(tracker.sequence)], SPL_CAT_PROXY_PID);
 
       const wormholeAccounts = getPostMessageCpiAccounts(
         SPL_CAT_PROXY_PID,
@@ -240,7 +230,8 @@ describe("cat_sol20_proxy", () => {
       // Parameters
 
       let amount = new anchor.BN(10000).mul(oneToken);
-      let recipientChain = new anchor.BN(2);
+      // CAT chain ID of the Tilt Ethereum devnet (Wormhole chain 2)
+      let recipientChain = new anchor.BN(1337);
 
       // Approve
       const transaction = new anchor.web3.Transaction();
@@ -273,6 +264,7 @@ describe("cat_sol20_proxy", () => {
         // Wormhole Stuff
         wormholeProgram: CORE_BRIDGE_PID,
         foreignEmitter: emitterAcc,
+        outboundTransfer,
         config: configAcc,
         ...wormholeAccounts,
       }).signers([KEYPAIR]).rpc();
@@ -326,16 +318,9 @@ describe("cat_sol20_proxy", () => {
       const payload = getParsedPayload(parsedVAA.payload);
 
       const postedVAAKey = derivePostedVaaKey(CORE_BRIDGE_PID, parsedVAA.hash);
-      const recievedKey = PublicKey.findProgramAddressSync(
-        [
-          Buffer.from("received"),
-          (() => {
-            const buf = Buffer.alloc(10);
-            buf.writeUInt16LE(parsedVAA.emitterChain, 0);
-            buf.writeBigInt64LE(parsedVAA.sequence, 2);
-            return buf;
-          })(),
-        ], SPL_CAT_PROXY_PID)[0];
+      // Redemptions are tracked per emitter chain and sequence
+      const recievedKey = deriveAddress([Buffer.from("received"), u16LE(parsedVAA.emitterChain), u64LE(parsedVAA.sequence)], SPL_CAT_PROXY_PID);
+      const receivedBitmap = deriveAddress([Buffer.from("received_bitmap"), u16LE(parsedVAA.emitterChain), u64LE(parsedVAA.sequence / SEQUENCES_PER_BITMAP)], SPL_CAT_PROXY_PID);
 
 
       const [configAcc, _] = PublicKey.findProgramAddressSync([
@@ -351,14 +336,15 @@ describe("cat_sol20_proxy", () => {
       const tokenMintATA = PublicKey.findProgramAddressSync([LOCK_PDA_SEED, testTokenMintPDA.toBuffer()], SPL_CAT_PROXY_PID)[0];
 
       const foreignChainId = Buffer.alloc(8);
-      foreignChainId.writeBigUInt64LE(payload.sourceTokenChain);
+      // Emitters are registered under the Wormhole chain ID of the VAA
+      foreignChainId.writeBigUInt64LE(BigInt(parsedVAA.emitterChain));
 
       const [emitterAcc, emitterBmp] = PublicKey.findProgramAddressSync([
         Buffer.from("foreign_emitter"),
         foreignChainId,
       ], SPL_CAT_PROXY_PID)
 
-      const tx = program.methods.bridgeIn({
+      const tx = await program.methods.bridgeIn({
         vaaHash: Array.from(parsedVAA.hash)
       }).accounts({
         owner: KEYPAIR.publicKey,
@@ -370,6 +356,7 @@ describe("cat_sol20_proxy", () => {
         wormholeProgram: CORE_BRIDGE_PID,
         foreignEmitter: emitterAcc,
         posted: postedVAAKey,
+        receivedBitmap,
         received: recievedKey,
         config: configAcc,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -404,4 +391,19 @@ function getParsedPayload(vaa: Buffer) {
     destUserAddress: new PublicKey(destUserAddress),
     destTokenChain: destTokenChain.readBigUInt64BE(),
   }
-}
\ No newline at end of file
+}
+
+// Sequences covered by one received bitmap account
+const SEQUENCES_PER_BITMAP = BigInt(8 * 1024);
+
+function u16LE(value: number) {
+  const buf = Buffer.alloc(2);
+  buf.writeUInt16LE(value);
+  return buf;
+}
+
+function u64LE(value: bigint) {
+  const buf = Buffer.alloc(8);
+  buf.writeBigUInt64LE(value);
+  return buf;
+}